@@ -1,7 +1,10 @@
 use reqwasm::http::{Request, Response};
 use serde::{Deserialize, Serialize};
 use shared::{
-    model::{Paste, PasteSummary},
+    model::{
+        ApiToken, CreateApiToken, CreatedApiToken, Paste, PasteFilter, PasteRevision, PasteSummary,
+        UploadedPaste,
+    },
     PasteId, UserPasteId,
 };
 
@@ -41,6 +44,26 @@ pub async fn create_paste(content: CreatePaste<'_>) -> Result<PasteId> {
         return Err(handle_error_response(resp).await);
     }
 
+    Ok(resp.json::<UploadedPaste>().await?.id)
+}
+
+/// Imports a build from a link to another site, returns the id of the created paste.
+pub async fn import_paste(url: &str) -> Result<PasteId> {
+    #[derive(Serialize)]
+    struct ImportPaste<'a> {
+        url: &'a str,
+    }
+
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::post("/api/internal/import")
+        .body(serde_json::to_string(&ImportPaste { url })?)
+        .send()
+        .await?;
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
     Ok(resp.json::<PasteId>().await?)
 }
 
@@ -61,6 +84,22 @@ pub async fn get_paste(id: &PasteId) -> Result<Paste> {
     Ok(resp.json().await?)
 }
 
+/// Loads the rendered passive tree of the tree spec at `index` as SVG.
+pub async fn get_tree(id: &PasteId, index: usize) -> Result<String> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::get(&id.to_tree_url(index)).send().await?;
+
+    if resp.status() == 404 {
+        return Err(Error::NotFound("tree", index.to_string()));
+    }
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.text().await?)
+}
+
 pub async fn delete_paste(id: &UserPasteId) -> Result<()> {
     let _in_flight = crate::progress::start_request();
     let resp = Request::delete(&format!("/api/internal/paste/{id}"))
@@ -74,12 +113,27 @@ pub async fn delete_paste(id: &UserPasteId) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_user(user: &str) -> Result<Vec<PasteSummary>> {
+pub async fn undelete_paste(id: &UserPasteId) -> Result<()> {
     let _in_flight = crate::progress::start_request();
-    let resp = Request::get(&format!("/api/internal/user/{user}"))
+    let resp = Request::post(&format!("/api/internal/paste/{id}/undelete"))
         .send()
         .await?;
 
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(())
+}
+
+pub async fn get_user(user: &str, filter: &PasteFilter) -> Result<Vec<PasteSummary>> {
+    let _in_flight = crate::progress::start_request();
+    let url = match filter.to_query() {
+        query if query.is_empty() => format!("/api/internal/user/{user}"),
+        query => format!("/api/internal/user/{user}?{query}"),
+    };
+    let resp = Request::get(&url).send().await?;
+
     if resp.status() == 404 {
         return Err(Error::NotFound("user", user.to_string()));
     }
@@ -91,6 +145,77 @@ pub async fn get_user(user: &str) -> Result<Vec<PasteSummary>> {
     Ok(resp.json().await?)
 }
 
+#[allow(dead_code)] // Only used in !SSR
+pub async fn get_revisions(id: &UserPasteId) -> Result<Vec<PasteRevision>> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::get(&id.to_revisions_url()).send().await?;
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.json().await?)
+}
+
+#[allow(dead_code)] // Only used in !SSR
+pub async fn get_revision_content(id: &UserPasteId, revision: u32) -> Result<String> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::get(&id.to_revision_raw_url(revision))
+        .send()
+        .await?;
+
+    if resp.status() == 404 {
+        return Err(Error::NotFound("revision", format!("{id}@{revision}")));
+    }
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.text().await?)
+}
+
+#[allow(dead_code)] // Only used in !SSR
+pub async fn get_tokens() -> Result<Vec<ApiToken>> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::get("/api/internal/tokens").send().await?;
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.json().await?)
+}
+
+#[allow(dead_code)] // Only used in !SSR
+pub async fn create_token(token: &CreateApiToken) -> Result<CreatedApiToken> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::post("/api/internal/tokens")
+        .body(serde_json::to_string(token)?)
+        .send()
+        .await?;
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(resp.json().await?)
+}
+
+#[allow(dead_code)] // Only used in !SSR
+pub async fn delete_token(id: &str) -> Result<()> {
+    let _in_flight = crate::progress::start_request();
+    let resp = Request::delete(&format!("/api/internal/tokens/{id}"))
+        .send()
+        .await?;
+
+    if !resp.ok() {
+        return Err(handle_error_response(resp).await);
+    }
+
+    Ok(())
+}
+
 async fn handle_error_response(resp: Response) -> Error {
     if let Ok(err) = resp.json::<ErrorResponse>().await {
         Error::ApiError(err.code, err.message)
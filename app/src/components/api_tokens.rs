@@ -0,0 +1,146 @@
+use shared::{
+    model::{ApiToken, ApiTokenScope, CreateApiToken},
+    User,
+};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
+
+use crate::{
+    session::SessionValue,
+    utils::{memo_cond, pretty_date_ts, IteratorExt},
+};
+
+/// Lets the owner of a profile create, list and revoke personal API tokens.
+#[component(inline_props)]
+pub fn ApiTokens<'a, G: Html>(cx: Scope<'a>, name: &'a User) -> View<G> {
+    let session = use_context::<SessionValue>(cx);
+
+    let is_current_user = create_memo(cx, || {
+        let session = session.get();
+        Some(name.as_str()) == session.user().map(|u| u.name.as_str())
+    });
+
+    let content = memo_cond!(cx, is_current_user, render(cx), view! { cx, });
+
+    view! { cx, (&*content.get()) }
+}
+
+fn render<G: Html>(cx: Scope) -> View<G> {
+    let tokens = create_signal(cx, Vec::<ApiToken>::new());
+    let created = create_signal(cx, String::new());
+    let error = create_signal(cx, String::new());
+
+    let token_name = create_signal(cx, String::new());
+    let read_private = create_signal(cx, false);
+    let write = create_signal(cx, true);
+    let delete = create_signal(cx, false);
+
+    if G::IS_BROWSER {
+        spawn_local_scoped(cx, async move {
+            match crate::api::get_tokens().await {
+                Ok(t) => tokens.set(t),
+                Err(err) => tracing::info!("failed to load tokens: {err:?}"),
+            }
+        });
+    }
+
+    let on_create = move |_| {
+        let scopes = [
+            (ApiTokenScope::ReadPrivate, *read_private.get()),
+            (ApiTokenScope::Write, *write.get()),
+            (ApiTokenScope::Delete, *delete.get()),
+        ]
+        .into_iter()
+        .filter_map(|(scope, enabled)| enabled.then_some(scope))
+        .collect();
+        let request = CreateApiToken {
+            name: token_name.get().trim().to_owned(),
+            scopes,
+        };
+
+        spawn_local_scoped(cx, async move {
+            match crate::api::create_token(&request).await {
+                Ok(t) => {
+                    error.set(String::new());
+                    created.set(t.token);
+                    token_name.set(String::new());
+                    tokens.modify().push(t.info);
+                }
+                Err(err) => error.set(err.to_string()),
+            }
+        });
+    };
+
+    let revoke = move |id: String| {
+        spawn_local_scoped(cx, async move {
+            match crate::api::delete_token(&id).await {
+                Ok(()) => tokens.modify().retain(|t| t.id != id),
+                Err(err) => error.set(err.to_string()),
+            }
+        });
+    };
+
+    let list = create_memo(cx, move || {
+        tokens
+            .get()
+            .iter()
+            .map(|token| {
+                let id = token.id.clone();
+                let name = token.name.clone();
+                let scopes = token
+                    .scopes
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let created = pretty_date_ts(token.created);
+
+                view! { cx,
+                    li(class="flex items-center gap-x-3") {
+                        span(class="flex-auto truncate") { (name) }
+                        span(class="text-slate-500") { (scopes) }
+                        span(class="text-slate-500") { (created) }
+                        button(
+                            on:click=move |_| revoke(id.clone()),
+                            class="text-red-500 hover:underline hover:cursor-pointer",
+                        ) { "Revoke" }
+                    }
+                }
+            })
+            .collect_view()
+    });
+
+    view! { cx,
+        div(class="flex flex-col gap-y-2 mt-12") {
+            h2(class="dark:text-slate-100 text-slate-900 border-b border-solid") { "API Tokens" }
+            div(class="text-sm text-slate-400") {
+                "Tokens authenticate uploads as " code { "Authorization: Bearer <token>" } "."
+            }
+            div(class="flex flex-wrap gap-3 items-center text-sm") {
+                input(
+                    class="input flex-auto",
+                    type="text",
+                    maxlength=50,
+                    aria-label="Token name",
+                    placeholder="Token name",
+                    bind:value=token_name,
+                ) {}
+                label { input(type="checkbox", class="mr-1", bind:checked=read_private) {} "read-private" }
+                label { input(type="checkbox", class="mr-1", bind:checked=write) {} "write" }
+                label { input(type="checkbox", class="mr-1", bind:checked=delete) {} "delete" }
+                button(on:click=on_create, class="btn btn-primary") { "Create" }
+            }
+            div(class="text-red-500 empty:hidden") { (*error.get()) }
+            div(class="text-sm break-all empty:hidden") {
+                (if created.get().is_empty() {
+                    view! { cx, }
+                } else {
+                    view! { cx,
+                        "Copy the token now, it will not be shown again: "
+                        code(class="select-all") { (*created.get()) }
+                    }
+                })
+            }
+            ul(class="flex flex-col gap-y-1 text-sm") { (&*list.get()) }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use shared::{validation, Id, UserPasteId};
 use sycamore::{prelude::*, reactive::use_context};
 use wasm_bindgen::JsCast;
 
-use crate::{session::SessionValue, svg::SPINNER, utils::memo_cond};
+use crate::{components::PasteRevisions, session::SessionValue, svg::SPINNER, utils::memo_cond};
 
 pub enum CreatePasteProps {
     None,
@@ -271,6 +271,11 @@ pub fn CreatePaste<G: Html>(cx: Scope, props: CreatePasteProps) -> View<G> {
         view! { cx, }
     );
 
+    let revisions = match props.paste_id() {
+        Some(id) => view! { cx, PasteRevisions(id=id, content=value) },
+        None => view! { cx, },
+    };
+
     let cancel = if is_update {
         view! { cx,
             button(
@@ -327,6 +332,7 @@ pub fn CreatePaste<G: Html>(cx: Scope, props: CreatePasteProps) -> View<G> {
                 ) {
                 }
             }
+            (revisions)
         }
     }
 }
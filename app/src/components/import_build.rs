@@ -0,0 +1,68 @@
+use shared::import::{Import, IMPORTERS};
+use sycamore::prelude::*;
+
+use crate::{svg, utils::memo_cond};
+
+#[component]
+pub fn ImportBuild<G: Html>(cx: Scope) -> View<G> {
+    let value = create_signal(cx, String::new());
+    let loading = create_signal(cx, false);
+    let error = create_signal(cx, String::new());
+
+    let import = create_memo(cx, || Import::parse(&value.get()));
+
+    let btn_disabled = create_memo(cx, || *loading.get() || import.get().is_none());
+
+    let submit = move |_| {
+        if *loading.get() || import.get().is_none() {
+            return;
+        }
+
+        error.set(String::new());
+
+        let url = value.get();
+        let future = async move {
+            match crate::api::import_paste(&url).await {
+                Err(err) => {
+                    loading.set(false);
+                    error.set(err.to_string());
+                    tracing::info!("{:?}", err);
+                }
+                Ok(id) => sycamore_router::navigate(&id.to_url()),
+            }
+        };
+
+        loading.set(true);
+        sycamore::futures::spawn_local_scoped(cx, future);
+    };
+
+    let btn_content = memo_cond!(cx, loading, svg::SPINNER, "Import");
+
+    let sources = IMPORTERS
+        .iter()
+        .map(|importer| importer.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    view! { cx,
+        div(class="flex flex-col gap-y-1") {
+            div(class="dark:text-slate-200 text-slate-800") { "Import a build" }
+            div(class="text-sm text-slate-500") { "Supports links from " (sources) }
+            form(class="flex flex-wrap items-center justify-end gap-3") {
+                input(
+                    class="input flex-1 basis-[14rem]",
+                    bind:value=value,
+                    on:input=|_| error.set(String::new())
+                ) {}
+                button(
+                    class="btn btn-primary min-w-[100px]",
+                    type="submit",
+                    disabled=*btn_disabled.get(),
+                    on:click=submit,
+                    dangerously_set_inner_html=&btn_content.get()
+                ) {}
+            }
+            div(class="text-red-500") { (*error.get()) }
+        }
+    }
+}
@@ -1,33 +1,45 @@
+mod api_tokens;
 mod create_paste;
-mod import_pastebin;
+mod import_build;
 mod login_status;
 mod paste_history;
+mod paste_revisions;
 mod paste_toolbox;
+mod pob_build_diff;
 mod pob_colored_select;
 mod pob_colored_text;
 mod pob_gear_preview;
+mod pob_gem_shopping_list;
 mod pob_gems;
 mod pob_item;
 mod pob_item_set;
 mod pob_tree_preview;
+mod pob_tree_progression;
 mod popup;
+mod snapshot_character;
 mod static_popup;
 mod tree_node;
 mod view_paste;
 
+pub use self::api_tokens::ApiTokens;
 pub use self::create_paste::{CreatePaste, CreatePasteProps};
-pub use self::import_pastebin::ImportPastebin;
+pub use self::import_build::ImportBuild;
 pub use self::login_status::LoginStatus;
 pub use self::paste_history::PasteHistory;
+pub use self::paste_revisions::PasteRevisions;
 pub use self::paste_toolbox::PasteToolbox;
+pub use self::pob_build_diff::PobBuildDiff;
 pub use self::pob_colored_select::PobColoredSelect;
 pub use self::pob_colored_text::PobColoredText;
 pub use self::pob_gear_preview::PobGearPreview;
+pub use self::pob_gem_shopping_list::PobGemShoppingList;
 pub use self::pob_gems::PobGems;
 pub use self::pob_item::PobItem;
 pub use self::pob_item_set::PobItemSet;
 pub use self::pob_tree_preview::PobTreePreview;
+pub use self::pob_tree_progression::PobTreeProgression;
 pub use self::popup::Popup;
+pub use self::snapshot_character::SnapshotCharacter;
 pub use self::static_popup::StaticPopup;
 pub use self::tree_node::TreeNode;
 pub use self::view_paste::{ViewPaste, ViewPasteProps};
@@ -0,0 +1,79 @@
+use shared::{model::PasteRevision, UserPasteId};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
+
+use crate::utils::{pretty_date_ts, IteratorExt};
+
+/// Lists previous revisions of a user paste and allows restoring them into `content`.
+#[component(inline_props)]
+pub fn PasteRevisions<'a, G: Html>(
+    cx: Scope<'a>,
+    id: &'a UserPasteId,
+    content: &'a Signal<String>,
+) -> View<G> {
+    let revisions = create_signal(cx, Vec::<PasteRevision>::new());
+    let error = create_signal(cx, String::new());
+
+    if G::IS_BROWSER {
+        spawn_local_scoped(cx, async move {
+            match crate::api::get_revisions(id).await {
+                Ok(r) => revisions.set(r),
+                Err(err) => tracing::info!("failed to load revisions: {err:?}"),
+            }
+        });
+    }
+
+    let restore = move |revision: u32| {
+        spawn_local_scoped(cx, async move {
+            match crate::api::get_revision_content(id, revision).await {
+                Ok(c) => {
+                    error.set(String::new());
+                    content.set(c);
+                }
+                Err(err) => error.set(err.to_string()),
+            }
+        });
+    };
+
+    let list = create_memo(cx, move || {
+        let revisions = revisions.get();
+        if revisions.is_empty() {
+            return View::empty();
+        }
+
+        let items = revisions
+            .iter()
+            .rev()
+            .map(|r| {
+                let revision = r.revision;
+                let title = r.title.clone().unwrap_or_default();
+                let since = pretty_date_ts(r.last_modified);
+                let sha1 = r.sha1.get(..8).unwrap_or(&r.sha1).to_owned();
+                let href = id.to_revision_raw_url(revision);
+
+                view! { cx,
+                    li(class="flex items-center gap-x-3") {
+                        span(class="text-slate-500") { "#" (revision) }
+                        a(href=href, rel="external", class="flex-auto truncate hover:underline") { (title) }
+                        span(class="text-slate-500") { (since) }
+                        span(class="font-mono text-slate-500") { (sha1) }
+                        button(
+                            on:click=move |_| restore(revision),
+                            title="Load this revision into the editor, update to restore it",
+                            class="hover:underline hover:cursor-pointer",
+                        ) { "Restore" }
+                    }
+                }
+            })
+            .collect_view();
+
+        view! { cx,
+            div(class="flex flex-col gap-y-2") {
+                h2(class="dark:text-slate-100 text-slate-900 border-b border-solid") { "Revisions" }
+                div(class="text-red-500 empty:hidden") { (*error.get()) }
+                ul(class="flex flex-col gap-y-1 text-sm") { (items) }
+            }
+        }
+    });
+
+    view! { cx, (&*list.get()) }
+}
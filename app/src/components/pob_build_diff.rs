@@ -0,0 +1,209 @@
+use pob::diff::{Change, GemChange, SkillDiff, SlotDiff, StatDiff, TreeDiff};
+use sycamore::prelude::*;
+
+use crate::{build::Build, utils::IteratorExt};
+
+#[component(inline_props)]
+pub fn PobBuildDiff<'a, G: Html>(cx: Scope<'a>, old: &'a Build, new: &'a Build) -> View<G> {
+    let diff = pob::diff::diff(old.pob(), new.pob());
+
+    if diff.is_empty() {
+        return view! { cx, div() { "Both builds are identical" } };
+    }
+
+    let stats = section(cx, "Stats", render_stats(cx, diff.stats));
+    let skills = section(cx, "Gems", render_skills(cx, diff.skills));
+    let gear = section(cx, "Gear", render_gear(cx, diff.gear));
+    let trees = section(cx, "Tree", render_trees(cx, diff.trees));
+
+    view! { cx,
+        div(class="flex flex-col gap-y-12") {
+            (stats)
+            (skills)
+            (gear)
+            (trees)
+        }
+    }
+}
+
+fn section<G: Html>(cx: Scope, title: &'static str, content: Option<View<G>>) -> View<G> {
+    let Some(content) = content else {
+        return View::empty();
+    };
+
+    view! { cx,
+        div {
+            h2(class="text-lg dark:text-slate-100 text-slate-900 mb-2 border-b border-solid") { (title) }
+            div(class="sm:ml-3") { (content) }
+        }
+    }
+}
+
+fn render_stats<G: Html>(cx: Scope, stats: Vec<StatDiff>) -> Option<View<G>> {
+    if stats.is_empty() {
+        return None;
+    }
+
+    let rows = stats
+        .into_iter()
+        .map(|stat| {
+            let name = stat.stat.name().trim_start_matches("Spec:");
+            let old = format_value(stat.old);
+            let new = format_value(stat.new);
+            let delta = stat.delta();
+            let (color, sign) = if delta >= 0.0 {
+                ("text-green-500", "+")
+            } else {
+                ("text-red-500", "")
+            };
+            let delta = format!("{sign}{}", format_value(Some(delta)));
+
+            view! { cx,
+                tr {
+                    td(class="pr-5") { (name) }
+                    td(class="pr-5 text-right") { (old) }
+                    td(class="pr-5 text-right") { (new) }
+                    td(class=format!("text-right {color}")) { (delta) }
+                }
+            }
+        })
+        .collect_view();
+
+    Some(view! { cx,
+        table(class="text-sm") {
+            thead {
+                tr(class="text-left dark:text-slate-400 text-slate-600") {
+                    th(class="pr-5") { "Stat" }
+                    th(class="pr-5 text-right") { "Old" }
+                    th(class="pr-5 text-right") { "New" }
+                    th(class="text-right") { "Delta" }
+                }
+            }
+            tbody { (rows) }
+        }
+    })
+}
+
+fn render_skills<G: Html>(cx: Scope, skills: Vec<SkillDiff>) -> Option<View<G>> {
+    if skills.is_empty() {
+        return None;
+    }
+
+    let skills = skills
+        .into_iter()
+        .map(|skill| {
+            let name = match skill.slot {
+                Some(slot) => format!("{} ({slot})", skill.name),
+                None => skill.name.to_owned(),
+            };
+            let change = match skill.change {
+                Change::Added => "added",
+                Change::Removed => "removed",
+                Change::Changed => "",
+            };
+
+            let added = skill
+                .added_gems
+                .into_iter()
+                .map(|gem| format!("+ {gem}"))
+                .map(|gem| view! { cx, li(class="text-green-500") { (gem) } });
+            let removed = skill
+                .removed_gems
+                .into_iter()
+                .map(|gem| format!("- {gem}"))
+                .map(|gem| view! { cx, li(class="text-red-500") { (gem) } });
+            let changed = skill
+                .changed_gems
+                .into_iter()
+                .map(format_gem_change)
+                .map(|gem| view! { cx, li { (gem) } });
+            let gems = added.chain(removed).chain(changed).collect_view();
+
+            view! { cx,
+                div(class="break-inside-avoid mb-3") {
+                    div(class="dark:text-slate-100 text-slate-900") {
+                        (name) " "
+                        span(class="text-xs text-slate-500") { (change) }
+                    }
+                    ul(class="text-sm ml-3") { (gems) }
+                }
+            }
+        })
+        .collect_view();
+
+    Some(view! { cx, div(class="columns-2xs gap-5") { (skills) } })
+}
+
+fn render_gear<G: Html>(cx: Scope, gear: Vec<SlotDiff>) -> Option<View<G>> {
+    if gear.is_empty() {
+        return None;
+    }
+
+    let rows = gear
+        .into_iter()
+        .map(|slot| {
+            let old = item_name(slot.old);
+            let new = item_name(slot.new);
+            view! { cx,
+                tr {
+                    td(class="pr-5") { (slot.slot) }
+                    td(class="pr-5 text-red-500") { (old) }
+                    td(class="text-green-500") { (new) }
+                }
+            }
+        })
+        .collect_view();
+
+    Some(view! { cx, table(class="text-sm") { tbody { (rows) } } })
+}
+
+fn render_trees<G: Html>(cx: Scope, trees: Vec<TreeDiff>) -> Option<View<G>> {
+    if trees.is_empty() {
+        return None;
+    }
+
+    let trees = trees
+        .into_iter()
+        .map(|tree| {
+            let title = tree
+                .title
+                .map(|title| title.to_owned())
+                .unwrap_or_else(|| format!("Tree {}", tree.index + 1));
+            let summary = format!(
+                "+{} nodes, -{} nodes, {} mastery changes",
+                tree.added_nodes.len(),
+                tree.removed_nodes.len(),
+                tree.mastery_effects.len()
+            );
+
+            view! { cx,
+                li {
+                    span(class="dark:text-slate-100 text-slate-900 pr-3") { (title) }
+                    span(class="text-sm") { (summary) }
+                }
+            }
+        })
+        .collect_view();
+
+    Some(view! { cx, ul { (trees) } })
+}
+
+fn format_gem_change(gem: GemChange) -> String {
+    format!(
+        "{} {}/{} → {}/{}",
+        gem.name, gem.old_level, gem.old_quality, gem.new_level, gem.new_quality
+    )
+}
+
+fn format_value(value: Option<f32>) -> String {
+    match value {
+        Some(value) => ((value * 100.0).round() / 100.0).to_string(),
+        None => "-".to_owned(),
+    }
+}
+
+fn item_name(item: Option<&str>) -> String {
+    item.and_then(|item| pob::Item::parse(item).ok())
+        .map(|item| item.name.unwrap_or(item.base).to_owned())
+        .unwrap_or_else(|| "-".to_owned())
+}
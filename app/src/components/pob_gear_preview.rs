@@ -3,7 +3,7 @@ use pob::PathOfBuilding;
 use sycamore::prelude::*;
 use wasm_bindgen::JsCast;
 
-use super::{PobColoredSelect, PobItem, PobItemSet, Popup};
+use super::{pob_item::render_unique_links, PobColoredSelect, PobItem, PobItemSet, Popup};
 use crate::build::Build;
 
 #[component]
@@ -51,6 +51,40 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
         }
     });
 
+    let uniques = create_memo(cx, move || {
+        let Some(item_set) = *item_set.get() else {
+            return View::empty();
+        };
+
+        let uniques = item_set
+            .gear
+            .slots()
+            .filter_map(|(_, item)| item)
+            .chain(item_set.gear.sockets.iter().copied())
+            .filter_map(|item| pob::Item::parse(item).ok())
+            .filter_map(|item| Some((item.unique()?, item)))
+            .unique_by(|(unique, _)| *unique)
+            .map(|(unique, item)| {
+                let name = match unique.variant {
+                    Some(variant) => format!("{} ({variant})", unique.name),
+                    None => unique.name.to_owned(),
+                };
+                let links = render_unique_links(cx, &item);
+
+                view! { cx,
+                    li(class="flex gap-3") {
+                        span(class="flex-auto", style="color: #af6025") { (name) }
+                        (links)
+                    }
+                }
+            })
+            .collect_view();
+
+        view! { cx,
+            ul(class="text-sm mt-3 sm:px-3") { (uniques) }
+        }
+    });
+
     let mouseover = |event: web_sys::Event| {
         let a = event
             .target()
@@ -82,5 +116,6 @@ pub fn PobGearPreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
                 (&*items.get())
             }
         }
+        (&*uniques.get())
     }
 }
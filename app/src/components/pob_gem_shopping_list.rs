@@ -0,0 +1,81 @@
+use shared::model::data::{GemPurchase, GemShoppingList};
+use sycamore::prelude::*;
+
+use crate::{build::Build, utils::IteratorExt};
+
+#[component(inline_props)]
+pub fn PobGemShoppingList<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+    let Some(list) = build.data().gem_shopping_list.as_ref() else {
+        return View::empty();
+    };
+
+    view! { cx,
+        div(class="grid grid-cols-1 sm:grid-cols-2 xl:grid-cols-3 gap-x-10 gap-y-4") {
+            (render_quests(cx, list))
+            (render_unavailable(cx, list))
+        }
+    }
+}
+
+fn render_quests<G: Html>(cx: Scope, list: &GemShoppingList) -> View<G> {
+    list.quests
+        .iter()
+        .map(|quest| {
+            let title = format!("Act {} – {}", quest.act, quest.quest);
+            let npc = quest.npc.clone();
+            let gems = render_gems(cx, &quest.gems, false);
+
+            view! { cx,
+                div(class="break-inside-avoid") {
+                    div {
+                        span(class="dark:text-slate-100 text-slate-900 pr-3") { (title) }
+                        span(class="text-sm") { (npc) }
+                    }
+                    ul(class="text-sm ml-3") { (gems) }
+                }
+            }
+        })
+        .collect_view()
+}
+
+fn render_unavailable<G: Html>(cx: Scope, list: &GemShoppingList) -> View<G> {
+    if list.unavailable.is_empty() {
+        return View::empty();
+    }
+
+    let gems = render_gems(cx, &list.unavailable, true);
+    view! { cx,
+        div(class="break-inside-avoid") {
+            div(class="dark:text-slate-100 text-slate-900") { "Not sold to the class" }
+            ul(class="text-sm ml-3") { (gems) }
+        }
+    }
+}
+
+fn render_gems<G: Html>(cx: Scope, gems: &[GemPurchase], unavailable: bool) -> View<G> {
+    gems.iter()
+        .map(|gem| {
+            let name = gem.name.clone();
+            let level = format!("Level {}", gem.level);
+            let note = if gem.lilly_roth {
+                "Lilly Roth"
+            } else if gem.other_character {
+                "Buy with another character"
+            } else if unavailable {
+                "Not sold by vendors"
+            } else {
+                ""
+            };
+
+            view! { cx,
+                li {
+                    label(class="cursor-pointer", title=level) {
+                        input(type="checkbox", class="mr-2") {}
+                        (name)
+                        span(class="ml-2 text-amber-500") { (note) }
+                    }
+                }
+            }
+        })
+        .collect_view()
+}
@@ -36,12 +36,12 @@ pub fn PobGems<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
     let on_change = move |index| {
         let Some(index) = index else { return };
         if let Some(ss) = build.skill_sets().into_iter().nth(index) {
-            content.set(render_skills::<G>(cx, ss.skills, build.data()));
+            content.set(render_skills::<G>(cx, ss.skills, build));
         }
     };
 
     if let Some(ss) = skill_sets.into_iter().find(|ss| ss.is_selected) {
-        content.set(render_skills(cx, ss.skills, build.data()));
+        content.set(render_skills(cx, ss.skills, build));
     }
 
     let attach = create_signal(cx, None);
@@ -157,8 +157,9 @@ fn render_popup<'a, G: GenericNode + Html>(
 fn render_skills<'a, G: GenericNode + Html>(
     cx: Scope<'a>,
     skills: Vec<Skill<'a>>,
-    data: &'a data::Data,
+    build: &'a Build,
 ) -> View<G> {
+    let data = build.data();
     let iter_skills = skills
         .into_iter()
         .filter(is_enabled)
@@ -185,7 +186,10 @@ fn render_skills<'a, G: GenericNode + Html>(
                 group
                     // Show only skills which have an active gem or are in the weapons (e.g. Squire)
                     .filter(|skill| has_active_gem(skill) || is_in_weapon_slot(skill))
-                    .map(|skill| render_skill(cx, skill, data)),
+                    .map(|skill| {
+                        let links = skill.slot.and_then(|slot| slot_links(build, slot));
+                        render_skill(cx, skill, links, data)
+                    }),
             );
         }
     }
@@ -212,6 +216,20 @@ fn is_enabled(skill: &Skill) -> bool {
     true
 }
 
+/// Largest link group of the item equipped in `slot` of the active item set.
+fn slot_links(build: &Build, slot: &str) -> Option<usize> {
+    let item_sets = build.item_sets();
+    let item_set = item_sets
+        .iter()
+        .find(|set| set.is_selected)
+        .or_else(|| item_sets.first())?;
+
+    let (_, item) = item_set.gear.slots().find(|(name, _)| *name == slot)?;
+    let item = pob::Item::parse(item?).ok()?;
+
+    Some(item.max_links()).filter(|&links| links > 0)
+}
+
 fn is_enchant(skill: &Skill) -> bool {
     skill.gems.len() == 1
         && skill.gems[0]
@@ -228,7 +246,21 @@ fn is_in_weapon_slot(skill: &Skill) -> bool {
     skill.slot == Some("Weapon 1") || skill.slot == Some("Weapon 2")
 }
 
-fn render_skill<'a, G: Html>(cx: Scope<'a>, skill: Skill<'a>, data: &'a data::Data) -> View<G> {
+fn render_skill<'a, G: Html>(
+    cx: Scope<'a>,
+    skill: Skill<'a>,
+    links: Option<usize>,
+    data: &'a data::Data,
+) -> View<G> {
+    let num_gems = skill.gems.iter().filter(|gem| gem.is_enabled).count();
+    let links_warning = match links {
+        Some(links) if num_gems > links => {
+            let text = format!("{num_gems} gems in a {links}-link");
+            view! { cx, div(class="text-amber-500 text-xs mt-1") { (text) } }
+        }
+        _ => View::default(),
+    };
+
     let gems = skill
         .gems
         .into_iter()
@@ -354,6 +386,7 @@ fn render_skill<'a, G: Html>(cx: Scope<'a>, skill: Skill<'a>, data: &'a data::Da
         div(class="break-inside-avoid mt-5 first:mt-0 bg-slate-900 px-5 py-2.5 rounded-xl") {
             div(dangerously_set_inner_html=svg, data-slot=slot, class="float-right w-6") {}
             (gems)
+            (links_warning)
         }
     }
 }
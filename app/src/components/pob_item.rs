@@ -5,7 +5,7 @@ use crate::utils::{view_cond, IteratorExt};
 
 #[component]
 pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
-    let render_mod = |m: pob::Mod<'a>| {
+    let render_mod = |m: pob::Mod<'a>, badges: View<G>| {
         let line: String = m.line.to_owned();
 
         let style = if m.fractured {
@@ -18,7 +18,18 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
             "color: #88f"
         };
 
-        view! { cx, li(style=style) { (line) } }
+        view! { cx, li(style=style) { (line) (badges) } }
+    };
+
+    // Tiers are only meaningful for mods rolled from the mod pool.
+    let show_tiers = item.rarity.is_rare();
+    let base = item.base;
+    let render_explicit = move |m: pob::Mod<'a>| {
+        let badges = match show_tiers {
+            true => render_tier_badges(cx, &m, base),
+            false => View::empty(),
+        };
+        render_mod(m, badges)
     };
 
     let influence1 = item.influence1.map_or_else(View::empty, move |influence| view! { cx,
@@ -28,16 +39,29 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
         div(class="absolute right-[2px] top-0 bottom-0 w-[26px]", style=influence_style(influence)) {}
     });
 
-    let enchants = item.enchants().map(render_mod).collect_vec();
-    let implicits = item.implicits().map(render_mod).collect_vec();
+    let enchants = item
+        .enchants()
+        .map(|m| render_mod(m, View::empty()))
+        .collect_vec();
+    let implicits = item
+        .implicits()
+        .map(|m| render_mod(m, View::empty()))
+        .collect_vec();
     let explicit_groups = item.explicits().group_by(|m| m.tag);
     let explicits = explicit_groups
         .into_iter()
-        .map(|(_, mods)| mods.map(render_mod).collect_vec())
+        .map(|(_, mods)| mods.map(render_explicit).collect_vec())
         .map(|mods| view! { cx, Mods(mods) })
         .collect_view();
 
     let mut stats = Vec::new();
+    if let Some(variant) = item.unique().and_then(|unique| unique.variant) {
+        stats.push(render_property(
+            cx,
+            "Variant:".to_owned(),
+            variant.to_owned(),
+        ))
+    }
     if let Some(alt_quality) = item.alt_quality {
         stats.push(render_property(
             cx,
@@ -46,6 +70,20 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
         ))
     }
 
+    let roll_quality = item
+        .roll_quality()
+        .filter(|_| show_tiers)
+        .map(|quality| {
+            let quality = render_property(
+                cx,
+                "Roll Quality:".to_owned(),
+                format!("{:.0}%", quality * 100.0),
+            );
+            view! { cx, li(title="Average roll of all explicit mods within their tier") { (quality) } }
+        })
+        .into_iter()
+        .collect_vec();
+
     let mut unmet = Vec::new();
     if item.split {
         unmet.push(view! { cx, li(style="color: #88f") { "Split" } });
@@ -57,6 +95,21 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
         unmet.push(view! { cx, li(style="color: #d20000") { "Corrupted" } });
     }
 
+    let sockets = render_sockets(cx, &item.sockets());
+    // Uniques link to the trade site next to their wiki pages.
+    let (has_links, links) = match item.rarity.is_unique() {
+        true => (true, render_unique_links(cx, &item)),
+        false => {
+            let url = crate::trade::search_url(&item);
+            (url.is_some(), render_link(cx, url, "Trade"))
+        }
+    };
+    let links = view_cond!(cx, has_links, {
+        div(class="flex justify-center gap-3 pt-1 text-sm font-sans") {
+            (links)
+        }
+    });
+
     let name = item.name.unwrap_or_default().to_owned();
     let base = item.base.to_owned();
 
@@ -75,11 +128,14 @@ pub fn PobItem<'a, G: Html>(cx: Scope<'a>, item: pob::Item<'a>) -> View<G> {
                 (influence2)
             }
             div(class="p-2 pt-1") {
+                (sockets)
                 Mods(stats)
                 Mods(enchants)
                 Mods(implicits)
                 (explicits)
+                Mods(roll_quality)
                 Mods(unmet)
+                (links)
             }
         }
     }
@@ -102,6 +158,111 @@ fn render_property<G: Html>(cx: Scope<'_>, key: String, value: String) -> View<G
     }
 }
 
+/// Wiki, poedb and trade links of a unique item, see [`crate::unique::links`].
+pub fn render_unique_links<G: Html>(cx: Scope<'_>, item: &pob::Item<'_>) -> View<G> {
+    let Some(links) = crate::unique::links(item) else {
+        return View::empty();
+    };
+
+    view! { cx,
+        (render_link(cx, links.wiki, "Wiki"))
+        (render_link(cx, links.poedb, "PoEDB"))
+        (render_link(cx, links.trade, "Trade"))
+    }
+}
+
+/// External link, nothing is rendered without an url.
+fn render_link<G: Html>(cx: Scope<'_>, url: Option<String>, text: &'static str) -> View<G> {
+    let Some(url) = url else {
+        return View::empty();
+    };
+
+    view! { cx,
+        a(href=url, target="_blank", rel="noopener", class="hover:underline") { (text) }
+    }
+}
+
+fn render_tier_badges<G: Html>(cx: Scope<'_>, m: &pob::Mod<'_>, base: &str) -> View<G> {
+    const BADGE: &str = "ml-1.5 px-1 rounded-sm text-xs align-middle font-sans";
+
+    if m.crafted {
+        return view! { cx,
+            span(class=BADGE, style="color: #b4b4ff; background-color: #b4b4ff33", title="Crafted mod") { "Crafted" }
+        };
+    }
+
+    let fractured = view_cond!(cx, m.fractured, {
+        span(class=BADGE, style="color: #a29162; background-color: #a2916233", title="Fractured mod") { "Fractured" }
+    });
+
+    let Some(tier) = m.tier(base) else {
+        return fractured;
+    };
+
+    let generation_type = match tier.generation_type {
+        pob::GenerationType::Prefix => "Prefix",
+        pob::GenerationType::Suffix => "Suffix",
+    };
+    let title = format!(
+        "{generation_type} \"{}\", tier {}, {:.0}% of the tier range",
+        tier.name,
+        tier.tier,
+        tier.roll * 100.0
+    );
+    let style = match tier.tier {
+        1 => "color: #ffd700; background-color: #ffd70033",
+        2 | 3 => "color: #5ac85a; background-color: #5ac85a33",
+        _ => "color: #7f7f7f; background-color: #7f7f7f33",
+    };
+    let text = format!("T{}", tier.tier);
+
+    view! { cx,
+        span(class=BADGE, style=style, title=title) { (text) }
+        (fractured)
+    }
+}
+
+fn render_sockets<G: Html>(cx: Scope<'_>, groups: &[pob::SocketGroup]) -> View<G> {
+    if groups.is_empty() {
+        return View::empty();
+    }
+
+    let groups = groups
+        .iter()
+        .map(|group| {
+            let sockets = group
+                .sockets
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| {
+                    let style = socket_style(color);
+                    let link = view_cond!(cx, i > 0, { span(class="w-1.5 h-0.5 bg-stone-400") {} });
+                    view! { cx,
+                        (link)
+                        span(class="w-3 h-3 rounded-full border border-black", style=style) {}
+                    }
+                })
+                .collect_view();
+
+            view! { cx, span(class="flex items-center") { (sockets) } }
+        })
+        .collect_view();
+
+    view! { cx,
+        div(class="flex justify-center gap-2 py-1", title="Sockets") { (groups) }
+    }
+}
+
+fn socket_style(color: pob::SocketColor) -> &'static str {
+    match color {
+        pob::SocketColor::Red => "background-color: #c83c3c",
+        pob::SocketColor::Green => "background-color: #5ac85a",
+        pob::SocketColor::Blue => "background-color: #5a78dc",
+        pob::SocketColor::White => "background-color: #e6e6e6",
+        pob::SocketColor::Abyss => "background-color: #3c3c3c",
+    }
+}
+
 fn rarity_str(rarity: pob::Rarity) -> &'static str {
     match rarity {
         pob::Rarity::Normal => "White",
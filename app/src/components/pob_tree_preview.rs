@@ -1,23 +1,25 @@
+use std::cell::Cell;
+
 use itertools::Itertools;
 use pob::{PathOfBuilding, Socket, TreeSpec};
-use shared::model::data;
-use sycamore::prelude::*;
+use shared::{model::data, PasteId};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
 use wasm_bindgen::JsCast;
-use web_sys::{Event, HtmlElement};
+use web_sys::{Element, Event, HtmlElement};
 
 use crate::{
     build::Build,
     components::{PobColoredSelect, PobItem, Popup, TreeNode},
-    consts,
-    tree::SvgTree,
-    utils::{hooks::scoped_event_passive, IteratorExt},
+    consts, tree,
+    utils::{from_ref, IteratorExt},
 };
 
 #[derive(Debug)]
 struct Tree<'build> {
+    /// Index of the spec in the build.
+    index: usize,
     name: String,
     tree_url: String,
-    svg_url: &'static str,
     spec: TreeSpec<'build>,
     nodes: &'build data::Nodes,
     overrides: Vec<Override<'build>>,
@@ -49,18 +51,18 @@ struct Override<'build> {
     node_id: u32,
 }
 
-#[component]
-pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+#[component(inline_props)]
+pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, id: &'a PasteId, build: &'a Build) -> View<G> {
     let trees = build
         .trees()
-        .filter_map(|(nodes, spec)| {
+        .enumerate()
+        .filter_map(|(index, (nodes, spec))| {
             let tree_url = get_tree_url(&spec)?;
-            let svg_url = SvgTree::url(&spec);
             let overrides = extract_overrides(&spec.overrides);
             Some(Tree {
+                index,
                 name: spec.title.unwrap_or("<Default>").to_owned(),
                 tree_url,
-                svg_url,
                 spec,
                 nodes,
                 overrides,
@@ -74,74 +76,71 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
 
     let trees = create_ref(cx, trees);
     let current_tree = create_signal(cx, trees.iter().find_or_first(|t| t.spec.active).unwrap());
-    let tree_loaded = create_signal(cx, false);
     let node_ref = create_node_ref(cx);
 
-    let current_svg = create_signal(cx, current_tree.get().svg_url);
-    create_effect(cx, || {
-        let new_svg = current_tree.get().svg_url;
-        // Debounce the svg and reset loading state when it changed.
-        if new_svg != *current_svg.get() {
-            tree_loaded.set(false);
-            current_svg.set(new_svg);
-        }
-    });
+    // Trees are only rendered on the server, the client loads them when the selection changes.
+    if G::IS_BROWSER {
+        let loaded = create_ref(cx, Cell::new(current_tree.get().index));
+        create_effect(cx, move || {
+            let index = current_tree.get().index;
+            if loaded.replace(index) == index {
+                return;
+            }
+
+            spawn_local_scoped(cx, async move {
+                match crate::api::get_tree(id, index).await {
+                    Ok(svg) if current_tree.get().index == index => {
+                        from_ref::<Element>(node_ref).set_inner_html(&svg);
+                    }
+                    Ok(_) => {}
+                    Err(err) => tracing::warn!("failed to load tree {index}: {err:?}"),
+                }
+            });
+        });
+    }
 
-    let events = std::sync::Once::new();
     let attach = create_signal(cx, None);
     let popup = create_signal(cx, view! { cx, });
-    let on_mouseover_tree = move |event: web_sys::Event| {
-        let target: HtmlElement = event.target().unwrap().unchecked_into();
+    let on_mouseover_tree = move |event: Event| {
+        let target: Element = event.target().unwrap().unchecked_into();
 
         let Some(id) = target.id().strip_prefix('n').and_then(|s| s.parse().ok()) else {
             attach.set(None);
             return;
         };
 
+        let current_tree = current_tree.get();
         let item = current_tree
-            .get()
             .socket(id)
             .and_then(|socket| build.item_by_id(socket.item_id))
             .and_then(|item| pob::Item::parse(item).ok());
 
         let content = if let Some(item) = item {
             view! { cx, PobItem(item) }
-        } else {
-            let dataset = target.dataset();
-
-            let kind = dataset.get("kind");
-            let name = dataset.get("name").unwrap_or_default();
-
-            let stats = if let Some(mastery) = current_tree.get().mastery(id) {
+        } else if let Some(name) = target.get_attribute("data-name") {
+            // Node data is part of the rendered tree, the client only knows the latest tree.
+            let kind = target
+                .get_attribute("data-kind")
+                .and_then(|kind| tree::kind_name(&kind))
+                .map(Into::into);
+
+            let stats = if let Some(mastery) = current_tree.mastery(id) {
                 vec![mastery.to_owned()]
             } else {
-                dataset
-                    .get("stats")
-                    .map(|s| s.split(";;").map(Into::into).collect())
-                    .unwrap_or_default()
+                let stats = target.get_attribute("data-stats").unwrap_or_default();
+                stats.lines().map(str::to_owned).collect()
             };
 
             view! { cx, TreeNode(kind=kind, name=name, stats=stats) }
+        } else {
+            attach.set(None);
+            return;
         };
 
         popup.set(content);
-        attach.set(Some(target.unchecked_into()));
+        attach.set(Some(target));
     };
 
-    create_effect(cx, move || {
-        let tree = current_tree.get();
-        if !*tree_loaded.get() {
-            return;
-        }
-
-        let s = SvgTree::from_ref(node_ref).unwrap();
-        s.load(&tree.spec);
-
-        events.call_once(|| {
-            scoped_event_passive(cx, s.element(), "mouseover", on_mouseover_tree);
-        });
-    });
-
     // TODO: this updates the currently active tree, but it doesn't read from it
     // the select would need to be updated as well if the tree changes, kinda tricky...
     let select = render_select(cx, trees, move |index, tree| {
@@ -168,9 +167,7 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
             return;
         };
 
-        if let Some(tree) = SvgTree::from_ref(node_ref) {
-            tree.highlight(node_id.split(','));
-        }
+        tree::highlight(&from_ref(node_ref), node_id.split(','));
     };
     let on_mouseout_side = |event: Event| {
         let target: HtmlElement = event.target().unwrap().unchecked_into();
@@ -178,29 +175,41 @@ pub fn PobTreePreview<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
             return;
         };
 
-        if let Some(tree) = SvgTree::from_ref(node_ref) {
-            tree.clear_highlight();
+        tree::clear_highlight(&from_ref(node_ref));
+    };
+
+    let tree_view = if G::IS_BROWSER {
+        // Keeps the tree rendered by the server.
+        view! { cx,
+            div(
+                ref=node_ref,
+                class="passive-tree h-full w-full touch-pan",
+                on:mouseover=on_mouseover_tree,
+                on:mouseout=|_: Event| { attach.set(None) }
+            ) {}
+        }
+    } else {
+        let svg = tree::render(&current_tree.get().spec);
+        view! { cx,
+            div(
+                ref=node_ref,
+                class="passive-tree h-full w-full touch-pan",
+                dangerously_set_inner_html=&svg,
+                on:mouseover=on_mouseover_tree,
+                on:mouseout=|_: Event| { attach.set(None) }
+            ) {}
         }
     };
 
     view! { cx,
-        Popup(attach=attach, parent=Some(node_ref)) { (&*popup.get()) }
+        Popup(attach=attach, parent=None) { (&*popup.get()) }
         div(class="flex flex-wrap align-center") {
             div(class="h-9 max-w-full") { (select) }
             div(class="flex-1 text-right sm:mr-3 whitespace-nowrap") { (&*tree_level.get()) }
         }
         div(class="grid grid-cols-10 gap-3") {
             div(class="col-span-10 lg:col-span-7 h-[450px] md:h-[800px] cursor-move md:overflow-auto mt-2") {
-                object(
-                    ref=node_ref,
-                    data=current_svg.get(),
-                    class="h-full w-full bg-center bg-no-repeat touch-pan
-                    transition-[background-image] duration-1000 will-change-[background-image]",
-                    type="image/svg+xml",
-                    title="Skilltree Preview",
-                    on:load=|_: Event| { tree_loaded.set(true); },
-                    on:mouseout=|_: Event| { attach.set(None) }
-                ) {}
+                (tree_view)
             }
 
             div(class="col-span-10 lg:col-span-3 flex flex-col gap-3 h-full relative") {
@@ -0,0 +1,88 @@
+use shared::model::data::TreeStep;
+use sycamore::prelude::*;
+
+use crate::{build::Build, utils::IteratorExt};
+
+#[component(inline_props)]
+pub fn PobTreeProgression<'a, G: Html>(cx: Scope<'a>, build: &'a Build) -> View<G> {
+    let steps = build
+        .data()
+        .tree_progression
+        .iter()
+        .map(|step| render_step(cx, step))
+        .collect_view();
+
+    view! { cx,
+        ol(class="flex flex-col gap-4") { (steps) }
+    }
+}
+
+fn render_step<G: Html>(cx: Scope, step: &TreeStep) -> View<G> {
+    let title = step
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Tree {}", step.index + 1));
+
+    let (points, previous_points) = (step.points, step.previous_points);
+    let summary = format!(
+        "{} points ({}), {} ascendancy ({})",
+        points.passives,
+        format_delta(points.passives, previous_points.passives),
+        points.ascendancy,
+        format_delta(points.ascendancy, previous_points.ascendancy),
+    );
+
+    let (added, removed) = (&step.added, &step.removed);
+
+    let mut lines = Vec::new();
+    for name in &added.keystones {
+        lines.push(Line::Added(format!("{name} (Keystone)")));
+    }
+    for name in &removed.keystones {
+        lines.push(Line::Removed(format!("{name} (Keystone)")));
+    }
+    lines.extend(added.notables.iter().cloned().map(Line::Added));
+    lines.extend(removed.notables.iter().cloned().map(Line::Removed));
+    lines.extend(added.mastery_effects.iter().cloned().map(Line::Added));
+    lines.extend(removed.mastery_effects.iter().cloned().map(Line::Removed));
+
+    if added.small > 0 || removed.small > 0 {
+        lines.push(Line::Other(format!(
+            "+{} / -{} small passives",
+            added.small, removed.small
+        )));
+    }
+
+    let lines = lines
+        .into_iter()
+        .map(|line| match line {
+            Line::Added(line) => view! { cx, li(class="text-green-500") { "+ " (line) } },
+            Line::Removed(line) => view! { cx, li(class="text-red-500") { "- " (line) } },
+            Line::Other(line) => view! { cx, li { (line) } },
+        })
+        .collect_view();
+
+    view! { cx,
+        li(class="break-inside-avoid") {
+            div {
+                span(class="dark:text-slate-100 text-slate-900 pr-3") { (title) }
+                span(class="text-sm") { (summary) }
+            }
+            ul(class="text-sm ml-3") { (lines) }
+        }
+    }
+}
+
+enum Line {
+    Added(String),
+    Removed(String),
+    Other(String),
+}
+
+fn format_delta(new: usize, old: usize) -> String {
+    if new >= old {
+        format!("+{}", new - old)
+    } else {
+        format!("-{}", old - new)
+    }
+}
@@ -0,0 +1,58 @@
+use shared::User;
+use sycamore::prelude::*;
+
+use crate::{session::SessionValue, utils::memo_cond};
+
+/// Lets the owner of a profile snapshot one of their characters into a new build.
+///
+/// The snapshot requires access to the characters of the account,
+/// the user is sent through the login flow with the additional scope.
+#[component(inline_props)]
+pub fn SnapshotCharacter<'a, G: Html>(cx: Scope<'a>, name: &'a User) -> View<G> {
+    let session = use_context::<SessionValue>(cx);
+
+    let is_current_user = create_memo(cx, || {
+        let session = session.get();
+        Some(name.as_str()) == session.user().map(|u| u.name.as_str())
+    });
+
+    let content = memo_cond!(cx, is_current_user, render(cx), view! { cx, });
+
+    view! { cx, (&*content.get()) }
+}
+
+fn render<G: Html>(cx: Scope) -> View<G> {
+    let character = create_signal(cx, String::new());
+
+    let href = create_memo(cx, || {
+        format!("/login/character/{}", character.get().trim())
+    });
+    let button_class = create_memo(cx, || match character.get().trim().is_empty() {
+        true => "btn btn-primary pointer-events-none opacity-50",
+        false => "btn btn-primary",
+    });
+
+    view! { cx,
+        div(class="flex flex-col gap-y-2 mt-12") {
+            h2(class="dark:text-slate-100 text-slate-900 border-b border-solid") { "Snapshot Character" }
+            div(class="text-sm text-slate-400") {
+                "Creates a new build from the items, skills and passives of one of your characters."
+            }
+            div(class="flex flex-wrap gap-3 items-center text-sm") {
+                input(
+                    class="input flex-auto",
+                    type="text",
+                    maxlength=23,
+                    aria-label="Character name",
+                    placeholder="Character name",
+                    bind:value=character,
+                ) {}
+                a(
+                    href=href.get(),
+                    rel="external",
+                    class=button_class.get(),
+                ) { "Snapshot" }
+            }
+        }
+    }
+}
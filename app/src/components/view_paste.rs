@@ -7,7 +7,7 @@ use web_sys::HtmlTextAreaElement;
 use super::PobGearPreview;
 use crate::{
     build::Build,
-    components::{PobColoredText, PobGems, PobTreePreview},
+    components::{PobColoredText, PobGemShoppingList, PobGems, PobTreePreview, PobTreeProgression},
     consts::IMG_ONERROR_HIDDEN,
     pob::{self, Element},
     storage::Storage,
@@ -51,9 +51,10 @@ pub fn ViewPaste<'a, G: Html>(
         build,
     }: ViewPasteProps<'a>,
 ) -> View<G> {
+    let id = create_ref(cx, id);
     let title = title.unwrap_or_else(|| pob::title(build.pob()));
 
-    push_paste_to_history::<G>(cx, &id, &title, last_modified, build);
+    push_paste_to_history::<G>(cx, id, &title, last_modified, build);
 
     let version = build.max_tree_version().unwrap_or_default();
     let since = crate::utils::pretty_date_ts(last_modified);
@@ -69,10 +70,31 @@ pub fn ViewPaste<'a, G: Html>(
             }
         }
     });
+    let has_gem_shopping_list = build
+        .data()
+        .gem_shopping_list
+        .as_ref()
+        .is_some_and(|list| !list.quests.is_empty() || !list.unavailable.is_empty());
+    let gem_shopping_list_url = format!("/api/v1/paste/{id}/gems");
+    let gem_shopping_list = view_cond!(cx, has_gem_shopping_list, {
+        div(class="basis-full") {
+            h2(class="flex justify-between text-lg dark:text-slate-100 text-slate-900 mb-2 mt-12 border-b border-solid") {
+                "Gem Shopping List"
+                a(href=gem_shopping_list_url, class="text-sm hover:underline", title="Shopping list as JSON") { "JSON" }
+            }
+            div(class="sm:ml-3") { PobGemShoppingList(build=build) }
+        }
+    });
     let tree_preview = view_cond!(cx, has_displayable_tree(build.pob()), {
         div(class="basis-full") {
             h2(class="text-lg dark:text-slate-100 text-slate-900 mb-2 mt-12 border-b border-solid") { "Tree Preview" }
-            PobTreePreview(build)
+            PobTreePreview(id=id, build=build)
+        }
+    });
+    let tree_progression = view_cond!(cx, build.tree_specs().len() > 1, {
+        div(class="basis-full") {
+            h2(class="text-lg dark:text-slate-100 text-slate-900 mb-2 mt-12 border-b border-solid") { "Tree Progression" }
+            div(class="sm:ml-3") { PobTreeProgression(build=build) }
         }
     });
 
@@ -127,6 +149,21 @@ pub fn ViewPaste<'a, G: Html>(
         .map(|stat| view! { cx, div(class="flex-row gap-x-5") { (stat) } })
         .collect_view();
 
+    let warning_list = build
+        .data()
+        .warnings
+        .iter()
+        .map(|warning| {
+            let warning = warning.clone();
+            view! { cx, li { (warning) } }
+        })
+        .collect_view();
+    let warnings = view_cond!(cx, !build.data().warnings.is_empty(), {
+        ul(class="list-disc list-inside text-sm text-amber-500 mt-2", title="Build warnings") {
+            (warning_list)
+        }
+    });
+
     let src = crate::assets::ascendancy_image(build.ascendancy_or_class());
 
     view! { cx,
@@ -144,6 +181,7 @@ pub fn ViewPaste<'a, G: Html>(
                     }
                 }
                 (summary)
+                (warnings)
             }
             div(class="flex flex-col flex-initial gap-y-3 md:w-96") {
                 textarea(
@@ -183,7 +221,9 @@ pub fn ViewPaste<'a, G: Html>(
                 PobGems(build)
             }
         }
+        (gem_shopping_list)
         (tree_preview)
+        (tree_progression)
         (notes)
         div(class="h-[150px]") {}
     }
@@ -62,6 +62,18 @@ impl Context {
         }
     }
 
+    pub fn compare(
+        a: PasteId,
+        paste_a: shared::model::Paste,
+        b: PasteId,
+        paste_b: shared::model::Paste,
+    ) -> Self {
+        Self {
+            route: Ok(Route::Compare(a, b)),
+            inner: Inner::Compare(Box::new((paste_a.into(), paste_b.into()))),
+        }
+    }
+
     pub fn route(&self) -> Result<&Route, &crate::Error> {
         self.route.as_ref()
     }
@@ -81,6 +93,13 @@ impl Context {
         }
     }
 
+    pub fn into_compare(self) -> Option<(Paste, Paste)> {
+        match self.inner {
+            Inner::Compare(pastes) => Some(*pastes),
+            _ => None,
+        }
+    }
+
     pub fn get_user(&self) -> Option<&Vec<PasteSummary>> {
         match self.inner {
             Inner::User(ref pastes) => Some(pastes),
@@ -100,15 +119,22 @@ enum Inner {
     None,
     Paste(Paste),
     User(Vec<PasteSummary>),
+    Compare(Box<(Paste, Paste)>),
 }
 
-impl From<shared::model::Paste> for Inner {
+impl From<shared::model::Paste> for Paste {
     fn from(p: shared::model::Paste) -> Self {
-        Self::Paste(Paste {
+        Self {
             metadata: p.metadata,
             last_modified: p.last_modified,
             content: p.content,
             data: p.data,
-        })
+        }
+    }
+}
+
+impl From<shared::model::Paste> for Inner {
+    fn from(p: shared::model::Paste) -> Self {
+        Self::Paste(p.into())
     }
 }
@@ -6,6 +6,8 @@ pub struct HeadArgs {
     pub meta: Meta,
     pub prefetch: Vec<Prefetch>,
     pub preload: Vec<Prefetch>,
+    /// Origin of the request, used to make relative meta urls absolute.
+    pub origin: String,
 }
 
 #[component]
@@ -14,6 +16,8 @@ pub fn Head<G: Html>(cx: Scope, args: HeadArgs) -> View<G> {
     let title = meta.title.clone();
     let image = match meta.image.is_empty() {
         true => crate::assets::logo().into(),
+        // Crawlers expect absolute urls for `og:image`.
+        false if meta.image.starts_with('/') => format!("{}{}", args.origin, meta.image).into(),
         false => meta.image,
     };
 
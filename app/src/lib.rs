@@ -19,15 +19,18 @@ mod router;
 mod session;
 mod storage;
 mod svg;
+mod trade;
 mod tree;
+mod unique;
 mod utils;
 
 #[cfg(feature = "ssr")]
 mod head;
 
+pub use assets::logo;
 pub use context::Context;
 pub use error::{Error, Result};
-pub use meta::{Meta, Prefetch};
+pub use meta::{get_color, Meta, Prefetch};
 pub use response_context::ResponseContext;
 pub use router::Route;
 pub use session::User;
@@ -38,6 +41,9 @@ pub fn render_to_string(context: Context) -> (String, ResponseContext) {
     ResponseContext::with(|| sycamore::render_to_string(|cx| view! { cx, App(Some(context)) }))
 }
 
+#[cfg(feature = "ssr")]
+pub use tree::render as render_tree;
+
 #[cfg(feature = "ssr")]
 pub type Head = head::HeadArgs;
 
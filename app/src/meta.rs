@@ -90,7 +90,7 @@ pub(crate) fn get_paste_summary(pob: &impl PathOfBuilding) -> Vec<String> {
         .collect()
 }
 
-pub(crate) fn get_color(aoc: AscendancyOrClass) -> &'static str {
+pub fn get_color(aoc: AscendancyOrClass) -> &'static str {
     match aoc.class() {
         Class::Duelist => "#96afc8",
         Class::Marauder => "#af5a32",
@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+
+use shared::PasteId;
+use sycamore::prelude::*;
+
+use crate::{
+    build::Build, components::PobBuildDiff, future::LocalBoxFuture, pob, router::RoutedComponent,
+    utils::find_text, Meta, Result,
+};
+
+pub struct ComparePage {
+    old: (PasteId, Build),
+    new: (PasteId, Build),
+}
+
+impl RoutedComponent for ComparePage {
+    type RouteArg = (PasteId, PasteId);
+
+    fn from_context((old_id, new_id): Self::RouteArg, ctx: crate::Context) -> Result<Self> {
+        let (old, new) = ctx.into_compare().unwrap();
+        Ok(Self {
+            old: (old_id, old.try_into()?),
+            new: (new_id, new.try_into()?),
+        })
+    }
+
+    fn from_hydration((old_id, new_id): Self::RouteArg, element: web_sys::Element) -> Result<Self> {
+        // The diff does not depend on the additional build data.
+        let old = find_text(&element, "[data-marker-old]").unwrap_or_default();
+        let new = find_text(&element, "[data-marker-new]").unwrap_or_default();
+
+        Ok(Self {
+            old: (old_id, Build::new(old, Default::default())?),
+            new: (new_id, Build::new(new, Default::default())?),
+        })
+    }
+
+    fn from_dynamic<'a>((old_id, new_id): Self::RouteArg) -> LocalBoxFuture<'a, Result<Self>> {
+        Box::pin(async move {
+            let old = crate::api::get_paste(&old_id).await?;
+            let new = crate::api::get_paste(&new_id).await?;
+
+            Ok(Self {
+                old: (old_id, old.try_into()?),
+                new: (new_id, new.try_into()?),
+            })
+        })
+    }
+
+    fn meta(&self) -> Result<Meta> {
+        let old = pob::title(self.old.1.pob());
+        let new = pob::title(self.new.1.pob());
+
+        Ok(Meta {
+            title: "Compare Builds".into(),
+            description: format!("{old}\nvs.\n{new}").into(),
+            ..Default::default()
+        })
+    }
+
+    fn render<G: Html>(self, cx: Scope) -> View<G> {
+        view! { cx, ComparePageComponent(self) }
+    }
+}
+
+#[component]
+fn ComparePageComponent<G: Html>(cx: Scope, ComparePage { old, new }: ComparePage) -> View<G> {
+    let (old_id, old) = old;
+    let (new_id, new) = new;
+
+    let old_title = pob::title(old.pob());
+    let new_title = pob::title(new.pob());
+    let old_content = old.content.clone();
+    let new_content = new.content.clone();
+
+    let old = create_ref(cx, old);
+    let new = create_ref(cx, new);
+
+    view! { cx,
+        pre(class="hidden", data-marker-old="") { (old_content) }
+        pre(class="hidden", data-marker-new="") { (new_content) }
+        h1(class="flex flex-col md:flex-row gap-x-3 text-xl mb-12 dark:text-slate-100 text-slate-900") {
+            a(href=old_id.to_url(), class="hover:underline") { (old_title) }
+            span(class="text-slate-500") { "vs." }
+            a(href=new_id.to_url(), class="hover:underline") { (new_title) }
+        }
+        PobBuildDiff(old=old, new=new)
+        div(class="h-[150px]") {}
+    }
+}
@@ -1,7 +1,7 @@
 use sycamore::prelude::*;
 
 use crate::{
-    components::{CreatePaste, CreatePasteProps, ImportPastebin},
+    components::{CreatePaste, CreatePasteProps, ImportBuild},
     future::LocalBoxFuture,
     router::RoutedComponent,
     Meta, Result,
@@ -32,7 +32,7 @@ impl RoutedComponent for IndexPage {
         view! { cx,
             div(class="flex flex-col gap-12") {
                 CreatePaste(CreatePasteProps::default())
-                ImportPastebin()
+                ImportBuild()
             }
         }
     }
@@ -82,7 +82,7 @@ impl RoutedComponent for PastePage {
 
         let description = meta::get_paste_summary(pob).join("\n").into();
 
-        let image = crate::assets::ascendancy_image(pob.ascendancy_or_class()).into();
+        let image = PasteId::Paste(self.id.clone()).to_card_url().into();
         let color = meta::get_color(pob.ascendancy_or_class());
 
         Ok(Meta {
@@ -1,8 +1,11 @@
-use shared::{model::PasteSummary, User};
-use sycamore::prelude::*;
+use shared::{
+    model::{PasteFilter, PasteSort, PasteSummary},
+    AscendancyOrClass, User,
+};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
 
 use crate::{
-    components::PasteToolbox,
+    components::{ApiTokens, PasteToolbox, PobColoredSelect, SnapshotCharacter},
     consts::{IMG_ONERROR_HIDDEN, IMG_ONERROR_INVISIBLE},
     future::LocalBoxFuture,
     router::RoutedComponent,
@@ -35,7 +38,7 @@ impl RoutedComponent for UserPage {
 
     fn from_dynamic<'a>(name: Self::RouteArg) -> LocalBoxFuture<'a, Result<Self>> {
         Box::pin(async move {
-            let pastes = crate::api::get_user(&name).await?;
+            let pastes = crate::api::get_user(&name, &PasteFilter::default()).await?;
             Ok(Self { name, pastes })
         })
     }
@@ -76,6 +79,114 @@ impl RoutedComponent for UserPage {
 pub fn UserPageComponent<G: Html>(cx: Scope, UserPage { name, pastes }: UserPage) -> View<G> {
     let data_ssr = serialize_for_attribute::<G>(&pastes);
 
+    let list = create_signal(cx, View::empty());
+    let filter_bar = if pastes.len() > 1 {
+        render_filter_bar(cx, name.clone(), &pastes, list)
+    } else {
+        View::empty()
+    };
+    list.set(render_list(cx, pastes));
+    let owner = create_ref(cx, name.clone());
+
+    view! { cx,
+        h1(class="text-amber-50 text-xl mb-4") {
+            span { (name) }
+            span { "'s builds" }
+        }
+        (filter_bar)
+        div(data-ssr=data_ssr, class="flex flex-col gap-2") {
+            (&*list.get())
+        }
+        SnapshotCharacter(name=owner)
+        ApiTokens(name=owner)
+    }
+}
+
+fn render_filter_bar<'a, G: Html>(
+    cx: Scope<'a>,
+    name: User,
+    pastes: &[PasteSummary],
+    list: &'a Signal<View<G>>,
+) -> View<G> {
+    let mut classes = pastes
+        .iter()
+        .map(|paste| paste.ascendancy_or_class)
+        .collect::<Vec<_>>();
+    classes.sort_unstable();
+    classes.dedup();
+    let classes = create_ref(cx, classes);
+
+    let title = create_signal(cx, String::new());
+    let ascendancy_or_class = create_signal(cx, None::<AscendancyOrClass>);
+    let sort = create_signal(cx, PasteSort::default());
+    let name = create_ref(cx, name);
+
+    let refresh = move || {
+        let filter = PasteFilter {
+            ascendancy_or_class: *ascendancy_or_class.get(),
+            title: Some(title.get().trim().to_owned()).filter(|t| !t.is_empty()),
+            sort: *sort.get(),
+            ..Default::default()
+        };
+        spawn_local_scoped(cx, async move {
+            match crate::api::get_user(name, &filter).await {
+                Ok(pastes) => list.set(render_list(cx, pastes)),
+                Err(err) => tracing::warn!("failed to filter pastes: {err:?}"),
+            }
+        });
+    };
+
+    let class_options = std::iter::once("All Classes".to_owned())
+        .chain(classes.iter().map(|c| c.as_str().to_owned()))
+        .collect();
+    let on_class_change = move |index: Option<usize>| {
+        let index = index.unwrap_or(0);
+        ascendancy_or_class.set(index.checked_sub(1).and_then(|i| classes.get(i).copied()));
+        refresh();
+    };
+
+    let sort_options = PasteSort::ALL
+        .iter()
+        .map(|sort| sort_name(*sort).to_owned())
+        .collect();
+    let on_sort_change = move |index: Option<usize>| {
+        sort.set(PasteSort::ALL[index.unwrap_or(0)]);
+        refresh();
+    };
+
+    view! { cx,
+        div(class="flex flex-wrap gap-3 items-center mb-4") {
+            input(
+                class="input flex-auto",
+                type="search",
+                aria-label="Search by title",
+                placeholder="Search by title",
+                bind:value=title,
+                on:change=move |_| refresh(),
+            ) {}
+            div(class="flex-initial") {
+                PobColoredSelect(options=class_options, selected=None, label="Filter by class", on_change=on_class_change)
+            }
+            div(class="flex-initial") {
+                PobColoredSelect(options=sort_options, selected=None, label="Sort builds", on_change=on_sort_change)
+            }
+        }
+    }
+}
+
+fn sort_name(sort: PasteSort) -> &'static str {
+    match sort {
+        PasteSort::Rank => "Pinned",
+        PasteSort::Newest => "Newest",
+        PasteSort::Oldest => "Oldest",
+        PasteSort::Title => "Title",
+        PasteSort::AscendancyOrClass => "Class",
+        PasteSort::MainSkillName => "Main Skill",
+        PasteSort::Version => "Version",
+    }
+}
+
+fn render_list<G: Html>(cx: Scope, pastes: Vec<PasteSummary>) -> View<G> {
     let p = pastes
         .into_iter()
         .map(|summary| {
@@ -84,28 +195,41 @@ pub fn UserPageComponent<G: Html>(cx: Scope, UserPage { name, pastes }: UserPage
             let content = memo_cond!(
                 cx,
                 deleted,
-                view! { cx, },
+                deleted_to_view(cx, summary, deleted),
                 summary_to_view(cx, summary, deleted)
             );
             view! { cx, (&*content.get()) }
         })
         .collect::<Vec<_>>();
 
-    let p = if !p.is_empty() {
+    if !p.is_empty() {
         View::new_fragment(p)
     } else {
         view! { cx,
             span(class="text-center") { "There is nothing here .." }
         }
+    }
+}
+
+fn deleted_to_view<'a, G: Html>(
+    cx: Scope<'a>,
+    summary: &'a PasteSummary,
+    deleted: &'a Signal<bool>,
+) -> View<G> {
+    let on_undo = move |_| {
+        let id = summary.id.clone().unwrap_user();
+        spawn_local_scoped(cx, async move {
+            match crate::api::undelete_paste(&id).await {
+                Ok(()) => deleted.set(false),
+                Err(err) => tracing::error!("undelete failed: {:?}", err),
+            }
+        });
     };
 
     view! { cx,
-        h1(class="text-amber-50 text-xl mb-4") {
-            span { (name) }
-            span { "'s builds" }
-        }
-        div(data-ssr=data_ssr, class="flex flex-col gap-2") {
-            (p)
+        div(class="p-3 flex gap-3 items-center text-sm text-slate-400") {
+            span { "Deleted " (summary.title) }
+            button(on:click=on_undo, class="hover:underline hover:cursor-pointer") { "Undo" }
         }
     }
 }
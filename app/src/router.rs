@@ -1,4 +1,4 @@
-use shared::{Id, User};
+use shared::{Id, PasteId, User};
 use sycamore::prelude::*;
 use sycamore_router::{HistoryIntegration, Router as DynRouter};
 use web_sys::Element;
@@ -25,6 +25,8 @@ pub enum Route {
     UserPaste(User, Id),
     #[to("/u/<name>/<id>/edit")]
     UserEditPaste(User, Id),
+    #[to("/compare/<a>/<b>")]
+    Compare(PasteId, PasteId),
     #[not_found]
     NotFound,
 }
@@ -136,6 +138,7 @@ enum Page {
     User(pages::UserPage),
     UserPaste(pages::UserPastePage),
     UserEditPaste(pages::UserEditPastePage),
+    Compare(pages::ComparePage),
     Error(u16, String),
 }
 
@@ -152,6 +155,8 @@ impl Page {
                     Self::UserPaste(pages::UserPastePage::from_context((user.clone(), id.clone()), ctx)?),
                 Ok(Route::UserEditPaste(user, id)) =>
                     Self::UserEditPaste(pages::UserEditPastePage::from_context((user.clone(), id.clone()), ctx)?),
+                Ok(Route::Compare(a, b)) =>
+                    Self::Compare(pages::ComparePage::from_context((a.clone(), b.clone()), ctx)?),
                 Ok(Route::NotFound) => Self::not_found(),
                 Err(err) => Self::resolve_err(err),
             })
@@ -209,6 +214,9 @@ impl Page {
                 Route::UserEditPaste(user, id) => Self::UserEditPaste(
                     pages::UserEditPastePage::from_hydration((user.clone(), id.clone()), element)?
                 ),
+                Route::Compare(a, b) => Self::Compare(
+                    pages::ComparePage::from_hydration((a.clone(), b.clone()), element)?
+                ),
                 Route::NotFound => Self::not_found(),
             })
         };
@@ -233,6 +241,9 @@ impl Page {
                 Route::UserEditPaste(user, id) => {
                     Self::UserEditPaste(pages::UserEditPastePage::from_dynamic((user.clone(), id.clone())).await?)
                 },
+                Route::Compare(a, b) => {
+                    Self::Compare(pages::ComparePage::from_dynamic((a.clone(), b.clone())).await?)
+                },
                 Route::NotFound => Self::not_found(),
             })
         };
@@ -280,6 +291,7 @@ impl Page {
             Self::User(ref page) => page.meta(),
             Self::UserPaste(ref page) => page.meta(),
             Self::UserEditPaste(ref page) => page.meta(),
+            Self::Compare(ref page) => page.meta(),
             Self::Error(_, message) => Ok(Meta::error(message)),
         }
     }
@@ -324,6 +336,7 @@ fn render<G: Html>(cx: Scope, page: Page) -> View<G> {
         Page::User(page) => page.render(cx),
         Page::UserPaste(page) => page.render(cx),
         Page::UserEditPaste(page) => page.render(cx),
+        Page::Compare(page) => page.render(cx),
         Page::Error(status_code, message) => view! { cx,
             // This needs to be in a component to not interfere with hydration.
             // A new hydration level is introduced per component, this
@@ -0,0 +1,21 @@
+const TRADE_SEARCH: &str = "https://www.pathofexile.com/trade/search";
+
+/// League searched on the trade site.
+///
+/// The current challenge league is configured at build time with the `POBBIN_TRADE_LEAGUE`
+/// environment variable, `Standard` is the only league which is always available.
+fn trade_league() -> &'static str {
+    option_env!("POBBIN_TRADE_LEAGUE")
+        .map(str::trim)
+        .filter(|league| !league.is_empty())
+        .unwrap_or("Standard")
+}
+
+/// Link to the official trade site, searching for items similar to `item`, see [`pob::trade`].
+pub fn search_url(item: &pob::Item) -> Option<String> {
+    let search = serde_json::to_string(&pob::trade::search(item)).ok()?;
+    let search = percent_encoding::utf8_percent_encode(&search, percent_encoding::NON_ALPHANUMERIC);
+    let league =
+        percent_encoding::utf8_percent_encode(trade_league(), percent_encoding::NON_ALPHANUMERIC);
+    Some(format!("{TRADE_SEARCH}/{league}?q={search}"))
+}
@@ -1,76 +1,80 @@
-use js_sys::{Array, Object, Uint32Array};
 use pob::TreeSpec;
-use sycamore::prelude::{GenericNode, NodeRef};
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::HtmlElement;
+use poe_tree::Version;
+use wasm_bindgen::JsCast;
 
-use crate::utils::{from_ref, reflect_set};
+const HIGHLIGHT: &str = "highlight";
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen]
-    type TreeObj;
-
-    #[wasm_bindgen(method, js_name=tree_load)]
-    fn load(this: &TreeObj, data: JsValue);
-
-    #[wasm_bindgen(method, js_name=tree_highlight)]
-    fn highlight(this: &TreeObj, data: JsValue);
+/// Tree version of the spec, specs without a version use the latest tree.
+///
+/// The client only includes the latest tree, older versions are only available
+/// while rendering on the server.
+pub fn version(spec: &TreeSpec) -> Option<Version> {
+    match spec.version {
+        Some(version) => version.parse().ok(),
+        None => Some(Version::latest()),
+    }
 }
 
-pub struct SvgTree(TreeObj);
-
-impl SvgTree {
-    pub fn url(spec: &TreeSpec) -> &'static str {
-        match spec.version {
-            Some("3_15") | Some("3.15") => "/assets/3.15.svg",
-            Some("3_16") | Some("3.16") => "/assets/3.16.svg",
-            Some("3_17") | Some("3.17") => "/assets/3.17.svg",
-            Some("3_18") | Some("3.18") => "/assets/3.18.svg",
-            Some("3_19") | Some("3.19") => "/assets/3.19.svg",
-            Some("3_20") | Some("3.20") => "/assets/3.20.svg",
-            Some("3_21") | Some("3.21") => "/assets/3.21.svg",
-            Some("3_22") | Some("3.22") => "/assets/3.22.svg",
-            _ => "/assets/3.23.svg",
-        }
+/// Kind of the node as used for the node header, see [`crate::components::TreeNode`].
+///
+/// `kind` is the kind of a rendered node, see [`poe_tree::svg`].
+pub fn kind_name(kind: &str) -> Option<&'static str> {
+    match kind {
+        "Keystone" => Some("Keystone"),
+        "Notable" | "AlternateAscendancyNotable" => Some("Notable"),
+        "Mastery" => Some("Mastery"),
+        _ => None,
     }
+}
 
-    pub fn from_ref<G: GenericNode>(node_ref: &NodeRef<G>) -> Option<Self> {
-        let inner = from_ref::<web_sys::HtmlObjectElement>(node_ref)
-            .content_window()?
-            .unchecked_into::<TreeObj>();
+/// Renders the passive tree of the spec as SVG, specs of an unknown version use the latest tree.
+///
+/// Only used on the server, the client loads trees from [`shared::PasteId::to_tree_url`].
+pub fn render(spec: &TreeSpec) -> String {
+    let sockets = spec
+        .sockets
+        .iter()
+        .map(|socket| socket.node_id)
+        .collect::<Vec<_>>();
 
-        Some(Self(inner))
-    }
+    let svg_spec = poe_tree::svg::Spec {
+        class_id: spec.class_id,
+        ascendancy_id: spec.ascendancy_id,
+        alternate_ascendancy_id: spec.alternate_ascendancy_id,
+        nodes: spec.nodes,
+        sockets: &sockets,
+    };
 
-    pub fn element(&self) -> HtmlElement {
-        self.0.clone().unchecked_into()
-    }
-
-    pub fn load(&self, spec: &TreeSpec<'_>) {
-        let obj = Object::new();
-        reflect_set(&obj, "nodes", Uint32Array::from(spec.nodes));
-        reflect_set(&obj, "classId", spec.class_id);
-        reflect_set(&obj, "ascendancyId", spec.ascendancy_id);
-        reflect_set(&obj, "alternateAscendancyId", spec.alternate_ascendancy_id);
+    let version = version(spec).unwrap_or_else(Version::latest);
+    poe_tree::svg::render(version, &svg_spec)
+}
 
-        self.0.load(obj.into());
-    }
+/// Highlights nodes in a rendered tree, replacing the current highlight.
+pub fn highlight<I, T>(root: &web_sys::Element, nodes: I)
+where
+    I: Iterator<Item = T>,
+    T: std::fmt::Display,
+{
+    clear_highlight(root);
 
-    pub fn highlight<I, T>(&self, nodes: I)
-    where
-        I: Iterator<Item = T>,
-        T: Into<JsValue>,
-    {
-        let obj = Array::new();
-        for node_id in nodes {
-            obj.push(&node_id.into());
+    for node_id in nodes {
+        if let Ok(Some(node)) = root.query_selector(&format!("#n{node_id}")) {
+            let _ = node.class_list().add_1(HIGHLIGHT);
         }
-
-        self.0.highlight(obj.into());
     }
+}
+
+pub fn clear_highlight(root: &web_sys::Element) {
+    let Ok(nodes) = root.query_selector_all(&format!(".{HIGHLIGHT}")) else {
+        return;
+    };
 
-    pub fn clear_highlight(&self) {
-        self.0.highlight(Array::new().into());
+    for i in 0..nodes.length() {
+        if let Some(node) = nodes.item(i) {
+            let _ = node
+                .unchecked_into::<web_sys::Element>()
+                .class_list()
+                .remove_1(HIGHLIGHT);
+        }
     }
 }
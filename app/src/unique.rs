@@ -0,0 +1,30 @@
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+
+/// Characters kept as is in wiki and poedb page names.
+const PAGE: &AsciiSet = &NON_ALPHANUMERIC.remove(b'_').remove(b'-').remove(b'\'');
+
+/// External pages for a unique item, see [`pob::Item::unique`].
+pub struct Links {
+    pub wiki: Option<String>,
+    pub poedb: Option<String>,
+    pub trade: Option<String>,
+}
+
+/// Links for unique and relic items, all versions of a unique link to the same pages.
+///
+/// Unknown uniques have no wiki and poedb pages, their names may not be actual unique names.
+pub fn links(item: &pob::Item) -> Option<Links> {
+    let unique = item.unique()?;
+    let page = unique.name.replace(' ', "_");
+    let page = percent_encoding::utf8_percent_encode(&page, PAGE).to_string();
+
+    Some(Links {
+        wiki: unique
+            .known
+            .then(|| format!("https://www.poewiki.net/wiki/{page}")),
+        poedb: unique
+            .known
+            .then(|| format!("https://poedb.tw/us/{}", page.replace('\'', ""))),
+        trade: crate::trade::search_url(item),
+    })
+}
@@ -1,9 +1,8 @@
 use std::str::FromStr;
 
-use js_sys::Reflect;
 use serde::{de::DeserializeOwned, Serialize};
 use sycamore::prelude::*;
-use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 pub mod hooks;
 pub mod links;
@@ -93,11 +92,6 @@ pub fn document<T: JsCast>() -> T {
         .unchecked_into()
 }
 
-pub fn reflect_set<T: Into<JsValue>>(target: &JsValue, name: &'static str, value: T) {
-    // could cache the name here
-    Reflect::set(target, &name.into(), &value.into()).unwrap();
-}
-
 /// Checks if the current viewport size is at least `md:`.
 pub fn is_at_least_medium_breakpoint() -> bool {
     web_sys::window()
@@ -0,0 +1,54 @@
+use anyhow::{bail, Context, Result};
+use shared::PasteId;
+
+const USER_AGENT: &str = concat!("pobbin-cli/", env!("CARGO_PKG_VERSION"));
+
+/// Minimal client for the endpoints used by Path of Building.
+pub struct Client {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Uploads an export code through `POST /pob/` and returns the id of the paste.
+    pub fn upload(&self, code: &str, token: Option<&str>) -> Result<PasteId> {
+        let mut request = self.agent.post(&format!("{}/pob/", self.base_url));
+        if let Some(token) = token {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+
+        let id = match request.send_string(code) {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(status, response)) => {
+                let message = response.into_string().unwrap_or_default();
+                bail!("upload failed with status code {status}: {message}");
+            }
+            Err(err) => return Err(err).context("upload failed"),
+        };
+
+        id.trim()
+            .parse()
+            .with_context(|| format!("server returned an invalid paste id '{id}'"))
+    }
+
+    /// Downloads the export code of a paste.
+    pub fn fetch(&self, id: &PasteId) -> Result<String> {
+        let url = format!("{}{}", self.base_url, id.to_pob_load_url());
+
+        match self.agent.get(&url).call() {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(404, _)) => bail!("paste '{id}' does not exist"),
+            Err(ureq::Error::Status(status, _)) => {
+                bail!("fetching paste '{id}' failed with status code {status}")
+            }
+            Err(err) => Err(err).context("fetching paste failed"),
+        }
+    }
+}
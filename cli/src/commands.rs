@@ -0,0 +1,206 @@
+use std::io::Write;
+
+use anyhow::Result;
+use pob::{Item, PathOfBuilding, PathOfBuildingExt, SerdePathOfBuilding, Stat};
+use serde::Serialize;
+
+pub fn summary(out: &mut impl Write, pob: &SerdePathOfBuilding, json: bool) -> Result<()> {
+    if json {
+        return print_json(
+            out,
+            &serde_json::json!({
+                "build": pob::api::build(pob),
+                "stats": pob::api::stats(pob),
+            }),
+        );
+    }
+
+    writeln!(
+        out,
+        "Level {} {}",
+        pob.level(),
+        pob.ascendancy_or_class().as_str()
+    )?;
+    if let Some(main_skill) = pob.main_skill_name() {
+        writeln!(out, "Main Skill: {main_skill}")?;
+    }
+    if let Some(version) = pob.max_tree_version() {
+        writeln!(out, "Tree Version: {version}")?;
+    }
+
+    writeln!(out)?;
+    for &stat in Stat::ALL {
+        if let Some(value) = pob.stat(stat) {
+            writeln!(out, "{}: {value}", stat.name())?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn items(out: &mut impl Write, pob: &SerdePathOfBuilding, json: bool) -> Result<()> {
+    if json {
+        return print_json(out, &pob::api::item_sets(pob));
+    }
+
+    for set in pob.item_sets() {
+        print_set_header(out, set.id, set.title, set.is_selected)?;
+
+        let jewels = set.gear.sockets.iter().map(|&item| ("Jewel", Some(item)));
+        for (slot, item) in set.gear.slots().chain(jewels) {
+            let Some(item) = item.and_then(|item| Item::parse(item).ok()) else {
+                continue;
+            };
+
+            match item.name {
+                Some(name) if name != item.base => {
+                    writeln!(out, "  {slot}: {name}, {}", item.base)?
+                }
+                _ => writeln!(out, "  {slot}: {}", item.base)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn gems(out: &mut impl Write, pob: &SerdePathOfBuilding, json: bool) -> Result<()> {
+    if json {
+        return print_json(out, &pob::api::skill_sets(pob));
+    }
+
+    for set in pob.skill_sets() {
+        print_set_header(out, set.id, set.title, set.is_selected)?;
+
+        for skill in set.skills.iter().filter(|skill| !skill.gems.is_empty()) {
+            let slot = skill.slot.unwrap_or("-");
+            let disabled = if skill.is_enabled { "" } else { " (disabled)" };
+            writeln!(out, "  [{slot}]{disabled}")?;
+
+            for gem in &skill.gems {
+                let disabled = if gem.is_enabled { "" } else { " (disabled)" };
+                writeln!(
+                    out,
+                    "    {} {}/{}{disabled}",
+                    gem.name, gem.level, gem.quality
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn tree(out: &mut impl Write, pob: &SerdePathOfBuilding, json: bool) -> Result<()> {
+    if json {
+        return print_json(out, &pob::api::tree_specs(pob));
+    }
+
+    for spec in pob.tree_specs() {
+        let active = if spec.active { " (active)" } else { "" };
+        let title = spec.title.unwrap_or("<Default>");
+        let version = spec.version.unwrap_or("?").replace('_', ".");
+        writeln!(
+            out,
+            "{title} [{version}]{active}: {} nodes, {} masteries",
+            spec.nodes.len(),
+            spec.mastery_effects.len()
+        )?;
+        if let Some(url) = spec.url {
+            writeln!(out, "  {url}")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_set_header(
+    out: &mut impl Write,
+    id: u16,
+    title: Option<&str>,
+    is_selected: bool,
+) -> Result<()> {
+    let active = if is_selected { " (active)" } else { "" };
+    match title {
+        Some(title) => writeln!(out, "{title}{active}")?,
+        None => writeln!(out, "Set {id}{active}")?,
+    }
+
+    Ok(())
+}
+
+fn print_json(out: &mut impl Write, value: &impl Serialize) -> Result<()> {
+    writeln!(out, "{}", serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(xml: &str) -> SerdePathOfBuilding {
+        SerdePathOfBuilding::from_xml(xml).unwrap()
+    }
+
+    fn output(
+        command: fn(&mut Vec<u8>, &SerdePathOfBuilding, bool) -> Result<()>,
+        pob: &SerdePathOfBuilding,
+        json: bool,
+    ) -> String {
+        let mut out = Vec::new();
+        command(&mut out, pob, json).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn summary_output() {
+        let pob = build(include_str!("../../pob/test/320_impending_doom.xml"));
+
+        let out = output(summary, &pob, false);
+        assert!(out.starts_with(
+            "Level 96 Occultist\nMain Skill: Doom Blast\nTree Version: 3.20\n\nArmour: 23062\n"
+        ));
+
+        let json: serde_json::Value = serde_json::from_str(&output(summary, &pob, true)).unwrap();
+        assert!(json["build"].is_object());
+        assert!(json["stats"].is_object());
+    }
+
+    #[test]
+    fn items_output() {
+        let pob = build(include_str!("../../pob/test/318_skillset.xml"));
+
+        let out = output(items, &pob, false);
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Set 1 (active)");
+        assert_eq!(lines[1], "  Weapon 1: Darkscorn, Assassin Bow");
+        assert!(lines.contains(&"  Weapon 1 Swap: Apocalypse Edge, Tiger's Paw"));
+        assert_eq!(
+            lines.last(),
+            Some(&"  Jewel: Morbid Sight, Searching Eye Jewel")
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&output(items, &pob, true)).unwrap();
+        assert!(json.is_array());
+    }
+
+    #[test]
+    fn gems_output() {
+        let pob = build(include_str!("../../pob/test/318_skillset.xml"));
+
+        let out = output(gems, &pob, false);
+        assert!(out.starts_with("Arc SS (active)\n  [-]\n    Arc 20/20\n    Arcane Surge 20/20\n"));
+        assert!(out.contains("\nCrackLance\n  [-]\n    Crackling Lance 20/20\n"));
+    }
+
+    #[test]
+    fn tree_output() {
+        let pob = build(include_str!("../../pob/test/318_skillset.xml"));
+
+        assert_eq!(
+            output(tree, &pob, false),
+            "<Default> [3.18] (active): 1 nodes, 0 masteries\n  \
+             https://www.pathofexile.com/passive-skill-tree/AAAABgAAAAAA\n"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::{io::Read, path::Path};
+
+use anyhow::{Context, Result};
+use pob::SerdePathOfBuilding;
+use shared::PasteId;
+
+/// Reads the raw input, from stdin if the path is missing or `-`.
+fn read(path: Option<&Path>) -> Result<String> {
+    let content = match path {
+        Some(path) if path != Path::new("-") => std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?,
+        _ => {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .context("failed to read stdin")?;
+            content
+        }
+    };
+
+    Ok(content.trim().to_owned())
+}
+
+fn is_xml(content: &str) -> bool {
+    content.starts_with('<')
+}
+
+/// Reads a build as XML, decoding it if the input is an export code.
+pub fn read_xml(path: Option<&Path>) -> Result<String> {
+    let content = read(path)?;
+    if is_xml(&content) {
+        return Ok(content);
+    }
+
+    pob::decompress(&content).context("input is not a valid export code")
+}
+
+/// Reads a build as export code, encoding it if the input is XML.
+pub fn read_export(path: Option<&Path>) -> Result<String> {
+    let content = read(path)?;
+    if !is_xml(&content) {
+        return Ok(content);
+    }
+
+    pob::compress(&content).context("failed to encode build")
+}
+
+pub fn read_build(path: Option<&Path>) -> Result<SerdePathOfBuilding> {
+    let xml = read_xml(path)?;
+    SerdePathOfBuilding::from_xml(&xml).context("failed to parse build")
+}
+
+/// Parses a paste id, either directly (`abc123`, `user:abc123`) or from a paste url
+/// (`https://pobb.in/abc123`, `https://pobb.in/u/user/abc123`).
+pub fn paste_id(s: &str) -> Result<PasteId> {
+    let s = s.trim();
+    let Some((_, rest)) = s.split_once("://") else {
+        return s
+            .parse()
+            .with_context(|| format!("'{s}' is not a valid paste id"));
+    };
+
+    let path = rest
+        .split(['?', '#'])
+        .next()
+        .and_then(|rest| rest.split_once('/'))
+        .map_or("", |(_, path)| path);
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+
+    let id = match (segments.next(), segments.next(), segments.next()) {
+        (Some("u"), Some(user), Some(id)) => format!("{user}:{id}"),
+        (Some(id), _, _) if id != "u" => id.to_owned(),
+        _ => anyhow::bail!("'{s}' is not a paste url"),
+    };
+
+    id.parse()
+        .with_context(|| format!("'{s}' is not a valid paste url"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_xml() {
+        assert!(is_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?><PathOfBuilding>"#
+        ));
+        assert!(is_xml("<PathOfBuilding></PathOfBuilding>"));
+        assert!(!is_xml("eNrtfVl3o0iS8HP9"));
+    }
+
+    #[test]
+    fn read_xml_and_export() {
+        let xml = include_str!("../../pob/test/320_impending_doom.xml").trim();
+        let dir = std::env::temp_dir().join(format!("pobbin-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let xml_path = dir.join("build.xml");
+        std::fs::write(&xml_path, xml).unwrap();
+        let code = read_export(Some(&xml_path)).unwrap();
+        assert!(!is_xml(&code));
+        assert_eq!(read_xml(Some(&xml_path)).unwrap(), xml);
+
+        let code_path = dir.join("build.txt");
+        std::fs::write(&code_path, format!("{code}\n")).unwrap();
+        assert_eq!(read_export(Some(&code_path)).unwrap(), code);
+        assert_eq!(read_xml(Some(&code_path)).unwrap(), xml);
+
+        std::fs::write(&code_path, "not a build").unwrap();
+        assert!(read_xml(Some(&code_path)).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn paste_ids() {
+        let parse = |s| paste_id(s).map(|id| id.to_string()).ok();
+
+        assert_eq!(parse("abc123").as_deref(), Some("abc123"));
+        assert_eq!(parse("someone:abc123").as_deref(), Some("someone:abc123"));
+        assert_eq!(parse("https://pobb.in/abc123").as_deref(), Some("abc123"));
+        assert_eq!(
+            parse("https://pobb.in/abc123/raw").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            parse("https://pobb.in/u/someone/abc123?foo=bar").as_deref(),
+            Some("someone:abc123")
+        );
+        assert_eq!(
+            parse("http://localhost:8787/u/someone/abc123#tree").as_deref(),
+            Some("someone:abc123")
+        );
+
+        assert_eq!(parse("https://pobb.in/"), None);
+        assert_eq!(parse("https://pobb.in/u/someone"), None);
+        assert_eq!(parse("https://pobb.in"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use std::{io::Write, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use shared::PasteId;
+
+mod client;
+mod commands;
+mod input;
+
+/// Parse, inspect and upload Path of Building builds.
+#[derive(Debug, Parser)]
+#[command(name = "pobbin", version)]
+struct Cli {
+    /// Base url of the pobb.in instance.
+    #[arg(
+        long,
+        env = "POBBIN_URL",
+        default_value = "https://pobb.in",
+        global = true
+    )]
+    url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Decodes a build export code into its XML.
+    Decode(BuildArgs),
+    /// Prints character information and all available stats.
+    Summary(BuildArgs),
+    /// Lists all item sets and their equipped items.
+    Items(BuildArgs),
+    /// Lists all skill sets and their gems.
+    Gems(BuildArgs),
+    /// Lists all passive tree specs.
+    Tree(BuildArgs),
+    /// Uploads a build and prints the url of the created paste.
+    Upload {
+        #[command(flatten)]
+        build: BuildArgs,
+        /// Personal API token, uploads the build to the token owner's profile.
+        #[arg(long, env = "POBBIN_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+    /// Downloads the export code of a paste, e.g. `abc123`, `user:abc123` or a paste url.
+    Fetch {
+        #[arg(value_parser = input::paste_id)]
+        id: PasteId,
+        /// Prints the decoded XML instead of the export code.
+        #[arg(long)]
+        xml: bool,
+    },
+}
+
+#[derive(Debug, clap::Args)]
+struct BuildArgs {
+    /// Export code or XML file, reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
+    /// Prints the output as JSON, using the types of the public API.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        // The output was closed early, e.g. piped into `head`.
+        Err(err) if is_broken_pipe(&err) => Ok(()),
+        result => result,
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let client = client::Client::new(&cli.url);
+    let mut out = std::io::stdout().lock();
+
+    match cli.command {
+        Command::Decode(args) => {
+            let xml = input::read_xml(args.input.as_deref())?;
+            writeln!(out, "{xml}")?;
+        }
+        Command::Summary(args) => commands::summary(
+            &mut out,
+            &input::read_build(args.input.as_deref())?,
+            args.json,
+        )?,
+        Command::Items(args) => commands::items(
+            &mut out,
+            &input::read_build(args.input.as_deref())?,
+            args.json,
+        )?,
+        Command::Gems(args) => commands::gems(
+            &mut out,
+            &input::read_build(args.input.as_deref())?,
+            args.json,
+        )?,
+        Command::Tree(args) => commands::tree(
+            &mut out,
+            &input::read_build(args.input.as_deref())?,
+            args.json,
+        )?,
+        Command::Upload { build, token } => {
+            let code = input::read_export(build.input.as_deref())?;
+            let id = client.upload(&code, token.as_deref())?;
+            writeln!(out, "{}{}", cli.url.trim_end_matches('/'), id.to_url())?;
+        }
+        Command::Fetch { id, xml } => {
+            let code = client.fetch(&id)?;
+            match xml {
+                true => writeln!(
+                    out,
+                    "{}",
+                    pob::decompress(&code).context("paste is not a valid build")?
+                )?,
+                false => writeln!(out, "{code}")?,
+            }
+        }
+    }
+
+    out.flush()?;
+
+    Ok(())
+}
+
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain()
+        .filter_map(|err| err.downcast_ref::<std::io::Error>())
+        .any(|err| err.kind() == std::io::ErrorKind::BrokenPipe)
+}
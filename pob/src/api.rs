@@ -0,0 +1,178 @@
+//! Conversions into the stable types of the public JSON API.
+use shared::api::v1;
+
+use crate::{Item, Mod, PathOfBuilding, PathOfBuildingExt, Rarity, Stat};
+
+/// Build summary, `id`, `title` and `last_modified` are left empty
+/// and have to be filled in from the paste.
+pub fn build(pob: &impl PathOfBuilding) -> v1::Build {
+    v1::Build {
+        id: String::new(),
+        title: String::new(),
+        last_modified: 0,
+        level: pob.level(),
+        class: pob.class().as_str().to_owned(),
+        ascendancy: pob.ascendancy().map(|a| a.as_str().to_owned()),
+        main_skill: pob.main_skill_name().map(|s| s.to_owned()),
+        version: pob.max_tree_version(),
+    }
+}
+
+/// All player and minion stats which are available and numeric.
+pub fn stats(pob: &impl PathOfBuilding) -> v1::Stats {
+    let collect = |get: &dyn Fn(Stat) -> Option<f32>| {
+        Stat::ALL
+            .iter()
+            .filter_map(|&stat| Some((stat.name().to_owned(), get(stat)?)))
+            .collect()
+    };
+
+    v1::Stats {
+        player: collect(&|stat| pob.stat_parse(stat)),
+        minion: collect(&|stat| pob.minion_stat_parse(stat)),
+    }
+}
+
+pub fn skill_sets(pob: &impl PathOfBuilding) -> Vec<v1::SkillSet> {
+    pob.skill_sets()
+        .into_iter()
+        .map(|set| v1::SkillSet {
+            id: set.id,
+            title: set.title.map(|t| t.to_owned()),
+            active: set.is_selected,
+            skills: set
+                .skills
+                .into_iter()
+                .map(|skill| v1::Skill {
+                    label: skill.label.map(|l| l.to_owned()),
+                    slot: skill.slot.map(|s| s.to_owned()),
+                    enabled: skill.is_enabled,
+                    main: set.is_selected && skill.is_selected,
+                    gems: skill
+                        .gems
+                        .into_iter()
+                        .map(|gem| v1::Gem {
+                            name: gem.name.to_owned(),
+                            skill_id: gem.skill_id.map(|s| s.to_owned()),
+                            level: gem.level,
+                            quality: gem.quality,
+                            enabled: gem.is_enabled,
+                            support: gem.is_support,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Item sets with all equipped items, items which fail to parse are skipped.
+pub fn item_sets(pob: &impl PathOfBuilding) -> Vec<v1::ItemSet> {
+    pob.item_sets()
+        .into_iter()
+        .map(|set| {
+            let jewels = set.gear.sockets.iter().map(|&item| ("Jewel", Some(item)));
+            let slots = set
+                .gear
+                .slots()
+                .chain(jewels)
+                .filter_map(|(slot, item)| {
+                    Some(v1::ItemSlot {
+                        slot: slot.to_owned(),
+                        item: item_from_raw(item?)?,
+                    })
+                })
+                .collect();
+
+            v1::ItemSet {
+                id: set.id,
+                title: set.title.map(|t| t.to_owned()),
+                active: set.is_selected,
+                slots,
+            }
+        })
+        .collect()
+}
+
+pub fn tree_specs(pob: &impl PathOfBuilding) -> Vec<v1::TreeSpec> {
+    pob.tree_specs()
+        .into_iter()
+        .map(|spec| v1::TreeSpec {
+            title: spec.title.map(|t| t.to_owned()),
+            version: spec.version.map(|v| v.replace('_', ".")),
+            active: spec.active,
+            class_id: spec.class_id,
+            ascendancy_id: spec.ascendancy_id,
+            url: spec.url.map(|u| u.to_owned()),
+            nodes: spec.nodes.to_vec(),
+            mastery_effects: spec
+                .mastery_effects
+                .iter()
+                .map(|&(node, effect)| v1::MasteryEffect { node, effect })
+                .collect(),
+        })
+        .collect()
+}
+
+fn item_from_raw(raw: &str) -> Option<v1::Item> {
+    let item = Item::parse(raw).ok()?;
+
+    Some(v1::Item {
+        rarity: match item.rarity {
+            Rarity::Normal => v1::Rarity::Normal,
+            Rarity::Magic => v1::Rarity::Magic,
+            Rarity::Rare => v1::Rarity::Rare,
+            Rarity::Unique => v1::Rarity::Unique,
+            Rarity::Relic => v1::Rarity::Relic,
+        },
+        name: item.name.map(|n| n.to_owned()),
+        base: item.base.to_owned(),
+        item_level: item.item_level,
+        quality: item.quality,
+        enchants: lines(item.enchants()),
+        implicits: lines(item.implicits()),
+        explicits: lines(item.explicits()),
+        corrupted: item.corrupted,
+        raw: raw.to_owned(),
+    })
+}
+
+fn lines<'a>(mods: impl Iterator<Item = Mod<'a>>) -> Vec<String> {
+    mods.map(|m| m.line.to_owned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+
+    #[test]
+    fn convert_build() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+
+        let build = build(&pob);
+        assert_eq!(build.level, pob.level());
+        assert_eq!(build.class, pob.class().as_str());
+
+        let stats = stats(&pob);
+        assert!(stats.player.contains_key("Life"));
+
+        let skill_sets = skill_sets(&pob);
+        assert_eq!(skill_sets.len(), pob.skill_sets().len());
+        let main = skill_sets
+            .iter()
+            .flat_map(|set| &set.skills)
+            .filter(|skill| skill.main)
+            .count();
+        assert_eq!(main, 1);
+
+        let item_sets = item_sets(&pob);
+        assert!(item_sets.iter().any(|set| !set.slots.is_empty()));
+
+        let tree_specs = tree_specs(&pob);
+        assert_eq!(tree_specs.len(), pob.tree_specs().len());
+        assert_eq!(tree_specs.iter().filter(|spec| spec.active).count(), 1);
+    }
+}
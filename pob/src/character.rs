@@ -0,0 +1,271 @@
+//! Characters as returned by the official Path of Exile character API.
+//!
+//! Only the parts required to convert a character into a build are modelled,
+//! see [`crate::SerdePathOfBuilding::from_character`].
+use std::{collections::HashMap, fmt::Write as _};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Character {
+    pub name: String,
+    /// Class or ascendancy name.
+    pub class: String,
+    pub level: u8,
+    #[serde(default)]
+    pub league: Option<String>,
+    #[serde(default)]
+    pub equipment: Vec<CharacterItem>,
+    #[serde(default)]
+    pub jewels: Vec<CharacterItem>,
+    #[serde(default)]
+    pub passives: Passives,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Passives {
+    #[serde(default)]
+    pub hashes: Vec<u32>,
+    /// Cluster jewel nodes.
+    #[serde(default)]
+    pub hashes_ex: Vec<u32>,
+    /// Selected mastery effects by mastery node.
+    #[serde(default)]
+    pub mastery_effects: HashMap<String, u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterItem {
+    #[serde(default)]
+    pub name: String,
+    pub type_line: String,
+    #[serde(default)]
+    pub base_type: Option<String>,
+    #[serde(default)]
+    pub frame_type: u8,
+    #[serde(default)]
+    pub ilvl: u8,
+    #[serde(default)]
+    pub inventory_id: Option<String>,
+    #[serde(default)]
+    pub x: u8,
+    /// Only set for gems.
+    #[serde(default)]
+    pub support: Option<bool>,
+    /// Index into the sockets of the parent item, only set for socketed items.
+    #[serde(default)]
+    pub socket: Option<usize>,
+    #[serde(default)]
+    pub corrupted: bool,
+    #[serde(default)]
+    pub synthesised: bool,
+    #[serde(default)]
+    pub influences: HashMap<String, bool>,
+    #[serde(default)]
+    pub properties: Vec<Property>,
+    #[serde(default)]
+    pub requirements: Vec<Property>,
+    #[serde(default)]
+    pub sockets: Vec<ItemSocket>,
+    #[serde(default)]
+    pub socketed_items: Vec<CharacterItem>,
+    #[serde(default)]
+    pub enchant_mods: Vec<String>,
+    #[serde(default)]
+    pub implicit_mods: Vec<String>,
+    #[serde(default)]
+    pub fractured_mods: Vec<String>,
+    #[serde(default)]
+    pub explicit_mods: Vec<String>,
+    #[serde(default)]
+    pub crafted_mods: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Property {
+    pub name: String,
+    /// Pairs of display value and display style.
+    #[serde(default)]
+    pub values: Vec<(String, u32)>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemSocket {
+    pub group: u8,
+    #[serde(default, rename = "sColour")]
+    pub colour: String,
+}
+
+impl CharacterItem {
+    /// PoB slot name of the inventory the item is equipped in.
+    pub fn slot(&self) -> Option<String> {
+        let slot = match self.inventory_id.as_deref()? {
+            "Weapon" => "Weapon 1",
+            "Offhand" => "Weapon 2",
+            "Weapon2" => "Weapon 1 Swap",
+            "Offhand2" => "Weapon 2 Swap",
+            "Helm" => "Helmet",
+            "BodyArmour" => "Body Armour",
+            "Gloves" => "Gloves",
+            "Boots" => "Boots",
+            "Amulet" => "Amulet",
+            "Ring" => "Ring 1",
+            "Ring2" => "Ring 2",
+            "Belt" => "Belt",
+            "Flask" => return Some(format!("Flask {}", self.x + 1)),
+            _ => return None,
+        };
+
+        Some(slot.to_owned())
+    }
+
+    pub fn is_gem(&self) -> bool {
+        self.support.is_some()
+    }
+
+    /// Numeric value of a property, e.g. `20` for a `Quality` of `+20%`.
+    pub fn property(&self, name: &str) -> Option<u8> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.values.first())
+            .and_then(|(value, _)| leading_number(value))
+    }
+
+    fn level_requirement(&self) -> Option<u8> {
+        self.requirements
+            .iter()
+            .find(|p| p.name == "Level")
+            .and_then(|p| p.values.first())
+            .and_then(|(value, _)| leading_number(value))
+    }
+
+    /// Group index of the socket the item is socketed in.
+    pub fn socket_group(&self, socket: usize) -> Option<u8> {
+        self.sockets.get(socket).map(|s| s.group)
+    }
+
+    /// Formats the item in the text format used by PoB.
+    ///
+    /// Returns `None` for items which are not equipment, e.g. gems.
+    pub fn to_pob_item(&self) -> Option<String> {
+        let rarity = match self.frame_type {
+            0 => "NORMAL",
+            1 => "MAGIC",
+            2 => "RARE",
+            3 => "UNIQUE",
+            9 => "RELIC",
+            _ => return None,
+        };
+
+        let mut item = String::new();
+        let _ = writeln!(item, "Rarity: {rarity}");
+        match self.frame_type {
+            0 | 1 => {
+                let _ = writeln!(item, "{}", self.type_line);
+            }
+            _ => {
+                let base = self.base_type.as_deref().unwrap_or(&self.type_line);
+                let _ = writeln!(item, "{}\n{base}", strip_markup(&self.name));
+            }
+        }
+
+        if self.ilvl > 0 {
+            let _ = writeln!(item, "Item Level: {}", self.ilvl);
+        }
+        if let Some(quality) = self.property("Quality") {
+            let _ = writeln!(item, "Quality: {quality}");
+        }
+        if let Some(level) = self.level_requirement() {
+            let _ = writeln!(item, "LevelReq: {level}");
+        }
+        if !self.sockets.is_empty() {
+            let _ = writeln!(item, "Sockets: {}", self.format_sockets());
+        }
+
+        let mut influences = INFLUENCES
+            .iter()
+            .filter(|(key, _)| self.influences.get(*key).copied().unwrap_or(false))
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>();
+        if self.synthesised {
+            influences.push("Synthesised Item");
+        }
+        for influence in influences {
+            let _ = writeln!(item, "{influence}");
+        }
+
+        let implicits = self.enchant_mods.len() + self.implicit_mods.len();
+        let _ = writeln!(item, "Implicits: {implicits}");
+        for m in &self.enchant_mods {
+            let _ = writeln!(item, "{{crafted}}{m}");
+        }
+        for m in &self.implicit_mods {
+            let _ = writeln!(item, "{m}");
+        }
+        for m in &self.fractured_mods {
+            let _ = writeln!(item, "{{fractured}}{m}");
+        }
+        for m in &self.explicit_mods {
+            let _ = writeln!(item, "{m}");
+        }
+        for m in &self.crafted_mods {
+            let _ = writeln!(item, "{{crafted}}{m}");
+        }
+        if self.corrupted {
+            let _ = writeln!(item, "Corrupted");
+        }
+
+        Some(item.trim_end().to_owned())
+    }
+
+    fn format_sockets(&self) -> String {
+        let mut result = String::new();
+        let mut group = None;
+        for socket in &self.sockets {
+            match group {
+                Some(group) if group == socket.group => result.push('-'),
+                Some(_) => result.push(' '),
+                None => (),
+            }
+            group = Some(socket.group);
+            result.push_str(&socket.colour);
+        }
+        result
+    }
+}
+
+const INFLUENCES: &[(&str, &str)] = &[
+    ("shaper", "Shaper Item"),
+    ("elder", "Elder Item"),
+    ("crusader", "Crusader Item"),
+    ("hunter", "Hunter Item"),
+    ("redeemer", "Redeemer Item"),
+    ("warlord", "Warlord Item"),
+    ("searing", "Searing Exarch Item"),
+    ("tangle", "Eater of Worlds Item"),
+];
+
+/// Removes the formatting markup from names, e.g. `<<set:MS>><<set:M>><<set:S>>Doom Emblem`.
+fn strip_markup(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(start) = rest.find("<<") {
+        let Some(end) = rest[start..].find(">>") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn leading_number(value: &str) -> Option<u8> {
+    let value = value.trim_start_matches('+');
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
@@ -0,0 +1,502 @@
+//! Structured differences between two builds.
+//!
+//! Tree specs are paired by their position, skills and gear are compared
+//! between the selected skill and item set of each build. Gear includes the
+//! swap weapons and socketed jewels.
+
+use crate::{Gem, PathOfBuilding, PathOfBuildingExt, Skill, Stat, TreeSpec};
+
+#[derive(Debug, Default)]
+pub struct BuildDiff<'a> {
+    pub trees: Vec<TreeDiff<'a>>,
+    pub skills: Vec<SkillDiff<'a>>,
+    pub gear: Vec<SlotDiff<'a>>,
+    pub stats: Vec<StatDiff>,
+}
+
+impl<'a> BuildDiff<'a> {
+    /// Whether both builds are equivalent in all compared areas.
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+            && self.skills.is_empty()
+            && self.gear.is_empty()
+            && self.stats.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TreeDiff<'a> {
+    /// Index of the tree spec in both builds, or of the newer tree spec for [`tree_progression`].
+    pub index: usize,
+    /// Title of the new tree spec, falls back to the title of the old tree spec.
+    pub title: Option<&'a str>,
+    pub added_nodes: Vec<u32>,
+    pub removed_nodes: Vec<u32>,
+    pub mastery_effects: Vec<MasteryEffectChange>,
+}
+
+impl<'a> TreeDiff<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.mastery_effects.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MasteryEffectChange {
+    pub node: u32,
+    pub old: Option<u32>,
+    pub new: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug)]
+pub struct SkillDiff<'a> {
+    /// Label or name of the first active gem of the skill.
+    pub name: &'a str,
+    pub slot: Option<&'a str>,
+    pub change: Change,
+    pub added_gems: Vec<&'a str>,
+    pub removed_gems: Vec<&'a str>,
+    pub changed_gems: Vec<GemChange<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GemChange<'a> {
+    pub name: &'a str,
+    pub old_level: u8,
+    pub new_level: u8,
+    pub old_quality: u8,
+    pub new_quality: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotDiff<'a> {
+    pub slot: &'static str,
+    /// Raw item text in the old build.
+    pub old: Option<&'a str>,
+    /// Raw item text in the new build.
+    pub new: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatDiff {
+    pub stat: Stat,
+    pub old: Option<f32>,
+    pub new: Option<f32>,
+}
+
+impl StatDiff {
+    /// Difference between the new and old value, missing values count as `0`.
+    pub fn delta(&self) -> f32 {
+        self.new.unwrap_or_default() - self.old.unwrap_or_default()
+    }
+}
+
+/// Compares the build `a` (old) to the build `b` (new).
+pub fn diff<'a, A, B>(a: &'a A, b: &'a B) -> BuildDiff<'a>
+where
+    A: PathOfBuilding,
+    B: PathOfBuilding,
+{
+    BuildDiff {
+        trees: diff_trees(a.tree_specs(), b.tree_specs()),
+        skills: diff_skills(a, b),
+        gear: diff_gear(a, b),
+        stats: diff_stats(a, b),
+    }
+}
+
+/// Changes between consecutive tree specs of a build, e.g. the steps of a leveling guide.
+///
+/// The first tree spec is compared to an empty tree. Unlike [`diff`], empty
+/// diffs are kept, the result always contains one diff per tree spec.
+pub fn tree_progression(pob: &impl PathOfBuilding) -> Vec<TreeDiff<'_>> {
+    let specs = pob.tree_specs();
+
+    (0..specs.len())
+        .map(|index| {
+            let previous = index.checked_sub(1).map(|previous| &specs[previous]);
+            diff_tree(index, previous, Some(&specs[index]))
+        })
+        .collect()
+}
+
+fn diff_trees<'a>(a: Vec<TreeSpec<'a>>, b: Vec<TreeSpec<'a>>) -> Vec<TreeDiff<'a>> {
+    let len = a.len().max(b.len());
+    let mut a = a
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::repeat_with(|| None));
+    let mut b = b
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::repeat_with(|| None));
+
+    (0..len)
+        .filter_map(|index| {
+            let (a, b) = (a.next().flatten(), b.next().flatten());
+            let diff = diff_tree(index, a.as_ref(), b.as_ref());
+            (!diff.is_empty()).then_some(diff)
+        })
+        .collect()
+}
+
+fn diff_tree<'a>(index: usize, a: Option<&TreeSpec<'a>>, b: Option<&TreeSpec<'a>>) -> TreeDiff<'a> {
+    let a_nodes = a.map_or(&[][..], |spec| spec.nodes);
+    let b_nodes = b.map_or(&[][..], |spec| spec.nodes);
+    let a_effects = a.map_or(&[][..], |spec| spec.mastery_effects);
+    let b_effects = b.map_or(&[][..], |spec| spec.mastery_effects);
+
+    let mut added_nodes = b_nodes
+        .iter()
+        .filter(|node| !a_nodes.contains(node))
+        .copied()
+        .collect::<Vec<_>>();
+    let mut removed_nodes = a_nodes
+        .iter()
+        .filter(|node| !b_nodes.contains(node))
+        .copied()
+        .collect::<Vec<_>>();
+    added_nodes.sort_unstable();
+    removed_nodes.sort_unstable();
+
+    let effect = |effects: &[(u32, u32)], node| {
+        effects
+            .iter()
+            .find(|(n, _)| *n == node)
+            .map(|(_, effect)| *effect)
+    };
+    let mut masteries = a_effects
+        .iter()
+        .chain(b_effects)
+        .map(|(node, _)| *node)
+        .collect::<Vec<_>>();
+    masteries.sort_unstable();
+    masteries.dedup();
+    let mastery_effects = masteries
+        .into_iter()
+        .map(|node| MasteryEffectChange {
+            node,
+            old: effect(a_effects, node),
+            new: effect(b_effects, node),
+        })
+        .filter(|change| change.old != change.new)
+        .collect();
+
+    TreeDiff {
+        index,
+        title: b
+            .and_then(|spec| spec.title)
+            .or(a.and_then(|spec| spec.title)),
+        added_nodes,
+        removed_nodes,
+        mastery_effects,
+    }
+}
+
+fn diff_skills<'a>(a: &'a impl PathOfBuilding, b: &'a impl PathOfBuilding) -> Vec<SkillDiff<'a>> {
+    let mut a = selected_skills(a);
+    let b = selected_skills(b);
+
+    let mut result = Vec::new();
+    for new in b {
+        let key = skill_name(&new);
+        let old = a
+            .iter()
+            .position(|old| skill_name(old) == key)
+            .map(|pos| a.remove(pos));
+
+        let diff = match old {
+            Some(old) => diff_skill(old, new),
+            None => SkillDiff {
+                name: key,
+                slot: new.slot,
+                change: Change::Added,
+                added_gems: new.gems.iter().map(|gem| gem.name).collect(),
+                removed_gems: Vec::new(),
+                changed_gems: Vec::new(),
+            },
+        };
+
+        if diff.change != Change::Changed || !diff.is_empty() {
+            result.push(diff);
+        }
+    }
+
+    result.extend(a.into_iter().map(|old| SkillDiff {
+        name: skill_name(&old),
+        slot: old.slot,
+        change: Change::Removed,
+        added_gems: Vec::new(),
+        removed_gems: old.gems.iter().map(|gem| gem.name).collect(),
+        changed_gems: Vec::new(),
+    }));
+
+    result
+}
+
+fn diff_skill<'a>(old: Skill<'a>, new: Skill<'a>) -> SkillDiff<'a> {
+    let name = skill_name(&new);
+    let slot = new.slot.or(old.slot);
+    let mut old_gems = old.gems;
+
+    let mut added_gems = Vec::new();
+    let mut changed_gems = Vec::new();
+    for gem in new.gems {
+        let Some(pos) = old_gems.iter().position(|old| old.name == gem.name) else {
+            added_gems.push(gem.name);
+            continue;
+        };
+
+        let Gem { level, quality, .. } = old_gems.remove(pos);
+        if level != gem.level || quality != gem.quality {
+            changed_gems.push(GemChange {
+                name: gem.name,
+                old_level: level,
+                new_level: gem.level,
+                old_quality: quality,
+                new_quality: gem.quality,
+            });
+        }
+    }
+
+    SkillDiff {
+        name,
+        slot,
+        change: Change::Changed,
+        added_gems,
+        removed_gems: old_gems.into_iter().map(|gem| gem.name).collect(),
+        changed_gems,
+    }
+}
+
+impl<'a> SkillDiff<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.added_gems.is_empty() && self.removed_gems.is_empty() && self.changed_gems.is_empty()
+    }
+}
+
+fn selected_skills(pob: &impl PathOfBuilding) -> Vec<Skill<'_>> {
+    let mut skill_sets = pob.skill_sets();
+    let index = skill_sets.iter().position(|ss| ss.is_selected).unwrap_or(0);
+    if index < skill_sets.len() {
+        skill_sets.swap_remove(index).skills
+    } else {
+        Vec::new()
+    }
+}
+
+fn skill_name<'a>(skill: &Skill<'a>) -> &'a str {
+    skill
+        .label
+        .filter(|label| !label.is_empty())
+        .or_else(|| {
+            skill
+                .gems
+                .iter()
+                .find(|gem| gem.is_active)
+                .map(|gem| gem.name)
+        })
+        .or_else(|| skill.gems.first().map(|gem| gem.name))
+        .unwrap_or_default()
+}
+
+fn diff_gear<'a>(a: &'a impl PathOfBuilding, b: &'a impl PathOfBuilding) -> Vec<SlotDiff<'a>> {
+    let selected = |pob: &'a dyn PathOfBuilding| {
+        let mut sets = pob.item_sets();
+        let index = sets.iter().position(|set| set.is_selected).unwrap_or(0);
+        if index < sets.len() {
+            sets.swap_remove(index).gear
+        } else {
+            Default::default()
+        }
+    };
+
+    let (a, b) = (selected(a), selected(b));
+    let mut result = std::iter::zip(a.slots(), b.slots())
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((slot, old), (_, new))| SlotDiff { slot, old, new })
+        .collect::<Vec<_>>();
+
+    // Jewel sockets are matched by the socketed jewel, moving a jewel is not a change.
+    let mut old_jewels = a.sockets;
+    for new in b.sockets {
+        match old_jewels.iter().position(|old| *old == new) {
+            Some(pos) => {
+                old_jewels.remove(pos);
+            }
+            None => result.push(SlotDiff {
+                slot: "Jewel",
+                old: None,
+                new: Some(new),
+            }),
+        }
+    }
+    result.extend(old_jewels.into_iter().map(|old| SlotDiff {
+        slot: "Jewel",
+        old: Some(old),
+        new: None,
+    }));
+
+    result
+}
+
+fn diff_stats(a: &impl PathOfBuilding, b: &impl PathOfBuilding) -> Vec<StatDiff> {
+    Stat::ALL
+        .iter()
+        .map(|&stat| StatDiff {
+            stat,
+            old: a.stat_parse(stat),
+            new: b.stat_parse(stat),
+        })
+        .filter(|diff| diff.old != diff.new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+
+    #[test]
+    fn diff_identical() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert!(diff(&pob, &pob).is_empty());
+    }
+
+    #[test]
+    fn diff_changes() {
+        let a = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let b = V316_POISON_OCC
+            .replace(
+                r#"<PlayerStat stat="Life" value="6148"/>"#,
+                r#"<PlayerStat stat="Life" value="6000"/>"#,
+            )
+            .replace(
+                r#"level="20" enableGlobal1="true" skillId="SupportAilments""#,
+                r#"level="21" enableGlobal1="true" skillId="SupportAilments""#,
+            )
+            .replace(
+                r#"masteryEffects="{292,38454}""#,
+                r#"masteryEffects="{292,1}""#,
+            );
+        let b = SerdePathOfBuilding::from_xml(&b).unwrap();
+
+        let diff = diff(&a, &b);
+
+        assert_eq!(1, diff.stats.len());
+        assert_eq!(Some(6148.0), diff.stats[0].old);
+        assert_eq!(Some(6000.0), diff.stats[0].new);
+        assert_eq!(-148.0, diff.stats[0].delta());
+
+        assert_eq!(1, diff.skills.len());
+        assert_eq!("Poisonous Concoction", diff.skills[0].name);
+        assert_eq!(Change::Changed, diff.skills[0].change);
+        assert_eq!(
+            vec![GemChange {
+                name: "Unbound Ailments",
+                old_level: 20,
+                new_level: 21,
+                old_quality: 20,
+                new_quality: 20,
+            }],
+            diff.skills[0].changed_gems
+        );
+
+        assert_eq!(1, diff.trees.len());
+        assert_eq!(Some("Pre-swap Levelling"), diff.trees[0].title);
+        assert_eq!(
+            vec![MasteryEffectChange {
+                node: 292,
+                old: Some(38454),
+                new: Some(1),
+            }],
+            diff.trees[0].mastery_effects
+        );
+        assert!(diff.trees[0].added_nodes.is_empty());
+        assert!(diff.gear.is_empty());
+    }
+
+    #[test]
+    fn diff_added_tree_spec() {
+        let a = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let b = SerdePathOfBuilding::from_xml(include_str!("../test/316_empty.xml")).unwrap();
+
+        let diff = diff(&b, &a);
+        assert_eq!(a.tree_specs().len(), diff.trees.len());
+        assert!(diff.trees[1].removed_nodes.is_empty());
+        assert_eq!(
+            a.tree_specs()[1].nodes.len(),
+            diff.trees[1].added_nodes.len()
+        );
+        assert!(diff
+            .skills
+            .iter()
+            .all(|skill| skill.change == Change::Added));
+    }
+
+    #[test]
+    fn diff_swap_and_jewels() {
+        let a = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let b = V316_POISON_OCC
+            .replace(
+                r#"<Slot name="Weapon 2" itemId="9"/>"#,
+                r#"<Slot name="Weapon 2 Swap" itemId="9"/>"#,
+            )
+            .replace(
+                r#"<Slot name="Belt Abyssal Socket 1" itemId="19"/>"#,
+                r#"<Slot name="Belt Abyssal Socket 1" itemId="18"/>"#,
+            );
+        let b = SerdePathOfBuilding::from_xml(&b).unwrap();
+
+        let diff = diff(&a, &b);
+        let slots = diff
+            .gear
+            .iter()
+            .map(|slot| (slot.slot, slot.old.is_some(), slot.new.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            slots,
+            [
+                ("Weapon 2", true, false),
+                ("Weapon 2 Swap", false, true),
+                ("Jewel", false, true),
+                ("Jewel", true, false),
+            ]
+        );
+        assert_eq!(diff.gear[0].old, diff.gear[1].new);
+    }
+
+    #[test]
+    fn tree_progression_steps() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        let specs = pob.tree_specs();
+        let progression = tree_progression(&pob);
+
+        assert_eq!(specs.len(), progression.len());
+
+        let mut first = specs[0].nodes.to_vec();
+        first.sort_unstable();
+        assert_eq!(first, progression[0].added_nodes);
+        assert!(progression[0].removed_nodes.is_empty());
+
+        for (index, step) in progression.iter().enumerate().skip(1) {
+            assert_eq!(index, step.index);
+            let (old, new) = (specs[index - 1].nodes, specs[index].nodes);
+            assert_eq!(
+                new.len() + step.removed_nodes.len(),
+                old.len() + step.added_nodes.len()
+            );
+        }
+    }
+}
@@ -13,6 +13,15 @@ pub enum Error {
     #[error("failed to deflate/decompress input: {0}")]
     Deflate(std::io::Error),
 
+    #[error("failed to compress input: {0}")]
+    Compress(std::io::Error),
+
     #[error("failed to parse build at: {0} ({1})")]
     ParseXml(String, quick_xml::de::DeError),
+
+    #[error("failed to write build: {0}")]
+    WriteXml(quick_xml::Error),
+
+    #[error("unknown character class: {0}")]
+    UnknownClass(String),
 }
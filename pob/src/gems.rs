@@ -1,3 +1,5 @@
+use crate::Gem;
+
 /// A support/gem can grant additional skills which can be configured in PoB.
 ///
 /// PoB treates those granted skills as normal skills 'attached' to the gem.
@@ -56,3 +58,24 @@ pub fn pob_id_as_game_id(id: &str) -> Option<&'static str> {
         _ => None,
     }
 }
+
+/// Skill types of an active skill gem, PoB does not export gem tags.
+///
+/// Unknown gems return an empty slice.
+pub(crate) fn active_skill_types(gem: &Gem) -> &'static [&'static str] {
+    data_gem(gem).map(|gem| gem.skill_types).unwrap_or(&[])
+}
+
+/// Skill types of which a skill needs at least one to be supported by the support gem.
+///
+/// Returns `None` for unknown supports.
+pub(crate) fn support_requirement(gem: &Gem) -> Option<&'static [&'static str]> {
+    let types = data_gem(gem)?.support_types;
+    (!types.is_empty()).then_some(types)
+}
+
+fn data_gem(gem: &Gem) -> Option<&'static poe_data::gems::Gem> {
+    gem.gem_id
+        .and_then(poe_data::gems::by_id)
+        .or_else(|| poe_data::gems::by_name(gem.name))
+}
@@ -1,5 +1,7 @@
 use std::iter::FusedIterator;
 
+use poe_data::mods::GenerationType;
+
 #[derive(Debug, thiserror::Error)]
 #[error("cannot parse item {0}")]
 pub struct InvalidItem(&'static str);
@@ -84,7 +86,10 @@ pub struct Item<'a> {
     pub split: bool,
     pub corrupted: bool,
 
+    /// 1-based index of the selected variant, as a string.
     selected_variant: &'a str,
+    variants: &'a str,
+    sockets: &'a str,
     implicits: &'a str,
     explicits: &'a str,
 }
@@ -129,6 +134,9 @@ impl<'a> Item<'a> {
         let mut influence2 = None;
 
         let mut selected_variant = "";
+        let mut variants = "";
+        let mut first_variant = None;
+        let mut sockets = "";
         let mut implicits = "";
 
         loop {
@@ -159,6 +167,12 @@ impl<'a> Item<'a> {
                         implicits = unsafe { get_n_lines(item, &mut lines, num) };
                     }
                     "Selected Variant" => selected_variant = arg,
+                    "Variant" => {
+                        // Variants are listed on consecutive lines.
+                        let first = *first_variant.get_or_insert(cmd);
+                        variants = unsafe { extract_slice_between(item, first, Some(arg)) };
+                    }
+                    "Sockets" => sockets = arg,
                     _ => {
                         if let Some((a, q)) = parse_alt_quality(cmd, arg) {
                             alt_quality = Some(a);
@@ -209,9 +223,13 @@ impl<'a> Item<'a> {
             .map(|start| unsafe { extract_slice_between(item, start, mods_end) })
             .unwrap_or("");
 
-        // Postprocess magic items based on mod count ...
+        // Magic item names include the affix names, resolve the real base if it is known,
+        // otherwise guess based on the mod count.
         if matches!(rarity, Rarity::Magic) {
-            base = extract_magic_base(base, explicits.lines().count());
+            base = match poe_data::bases::find_magic_base(base) {
+                Some(magic_base) => magic_base.name,
+                None => extract_magic_base(base, explicits.lines().count()),
+            };
         }
 
         if influence1.is_none() && explicits.lines().any(|m| m.starts_with("{fractured}")) {
@@ -239,6 +257,8 @@ impl<'a> Item<'a> {
             mirrored,
             split,
             selected_variant,
+            variants,
+            sockets,
             implicits,
             explicits,
         })
@@ -252,6 +272,47 @@ impl<'a> Item<'a> {
         self.name.map(fixup_item_name)
     }
 
+    /// Names of all variants of the item, e.g. `Pre 3.0.0` and `Current`.
+    pub fn variants(&self) -> impl Iterator<Item = &'a str> {
+        self.variants
+            .lines()
+            .filter_map(|line| line.strip_prefix("Variant: "))
+    }
+
+    /// Name of the selected variant, `None` if the item has no variants.
+    pub fn selected_variant(&self) -> Option<&'a str> {
+        let index = self.selected_variant.parse::<usize>().ok()?;
+        self.variants().nth(index.checked_sub(1)?)
+    }
+
+    /// Canonical identity of a unique or relic item, `None` for all other items.
+    ///
+    /// Names are resolved against the known uniques, see [`poe_data::uniques`].
+    /// Names of unknown uniques fall back to [`Item::fixed_item_name`].
+    pub fn unique(&self) -> Option<UniqueItem<'a>> {
+        if !self.rarity.is_unique() {
+            return None;
+        }
+
+        let name = self.name?;
+        let (name, foulborn, unique) = match find_unique(name) {
+            Some((unique, foulborn)) => (unique.name, foulborn, Some(unique)),
+            None => {
+                let (name, foulborn) = strip_foulborn(fixup_item_name(name));
+                (name, foulborn, None)
+            }
+        };
+
+        let variant = self.selected_variant();
+        Some(UniqueItem {
+            name,
+            variant,
+            relic: matches!(self.rarity, Rarity::Relic),
+            foulborn,
+            known: unique.is_some(),
+        })
+    }
+
     pub fn enchants(&self) -> impl Iterator<Item = Mod<'a>> {
         ModLines::new(self.implicits)
             .map(Mod::parse)
@@ -272,19 +333,145 @@ impl<'a> Item<'a> {
             .filter(|m| m.has_variant(self.selected_variant))
     }
 
+    /// Average roll of all explicits with a known tier, see [`Mod::tier`].
+    pub fn roll_quality(&self) -> Option<f32> {
+        let rolls = self
+            .explicits()
+            .filter_map(|m| m.tier(self.base))
+            .map(|tier| tier.roll)
+            .collect::<Vec<_>>();
+
+        if rolls.is_empty() {
+            return None;
+        }
+        Some(rolls.iter().sum::<f32>() / rolls.len() as f32)
+    }
+
+    /// All enchants, implicits and explicits of the item.
+    pub fn mods(&self) -> impl Iterator<Item = Mod<'a>> {
+        ModLines::new(self.implicits)
+            .chain(ModLines::new(self.explicits))
+            .map(Mod::parse)
+            .filter(|m| m.has_variant(self.selected_variant))
+    }
+
+    /// Linked socket groups of the item, e.g. `R-G-B B` yields a 3-link and a single socket.
+    ///
+    /// Unknown socket colors are skipped.
+    pub fn sockets(&self) -> Vec<SocketGroup> {
+        self.sockets
+            .split_whitespace()
+            .map(|group| SocketGroup {
+                sockets: group.split('-').filter_map(SocketColor::parse).collect(),
+            })
+            .filter(|group| !group.sockets.is_empty())
+            .collect()
+    }
+
+    /// Size of the largest linked socket group, `0` if the item has no sockets.
+    pub fn max_links(&self) -> usize {
+        self.sockets()
+            .iter()
+            .map(|group| group.links())
+            .max()
+            .unwrap_or(0)
+    }
+
     pub fn is_cluster_jewel(&self) -> bool {
         self.base.contains("Cluster Jewel")
     }
 }
 
+/// Prefix of the names of mutated, Foulborn, uniques.
+const FOULBORN: &str = "Foulborn ";
+
+/// Identity of a unique item, shared by all relic and Foulborn versions of the unique.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UniqueItem<'a> {
+    /// Name of the unique, without creator prefixes and the Foulborn prefix.
+    pub name: &'a str,
+    /// Selected variant, e.g. `Large Ring` for a Thread of Hope.
+    pub variant: Option<&'a str>,
+    pub relic: bool,
+    pub foulborn: bool,
+    /// Whether the unique is a known unique.
+    pub known: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocketColor {
+    Red,
+    Green,
+    Blue,
+    White,
+    Abyss,
+}
+
+impl SocketColor {
+    fn parse(value: &str) -> Option<Self> {
+        let color = match value {
+            "R" => Self::Red,
+            "G" => Self::Green,
+            "B" => Self::Blue,
+            "W" => Self::White,
+            "A" => Self::Abyss,
+            _ => return None,
+        };
+
+        Some(color)
+    }
+}
+
+/// A group of linked sockets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SocketGroup {
+    pub sockets: Vec<SocketColor>,
+}
+
+impl SocketGroup {
+    /// Amount of linked sockets in this group.
+    ///
+    /// Abyss sockets can not be linked and are not counted.
+    pub fn links(&self) -> usize {
+        self.sockets
+            .iter()
+            .filter(|&&color| color != SocketColor::Abyss)
+            .count()
+    }
+}
+
 #[derive(Debug)]
 pub struct Mod<'a> {
     pub fractured: bool,
     pub crafted: bool,
     pub line: &'a str,
     pub tag: Option<&'a str>,
+    /// Roll position of ranges in the mod line, between `0` and `1`.
+    pub range: Option<f32>,
 
     variant: Option<&'a str>,
+    tags: Option<&'a str>,
+}
+
+/// Tier of a rolled mod in the mod pool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModTier {
+    /// Tier of the mod, `1` is the best tier.
+    pub tier: u8,
+    /// Affix name of the tier, e.g. `Prime` or `of the Whelpling`.
+    pub name: &'static str,
+    pub generation_type: GenerationType,
+    /// How close the roll is to the maximum of the tier, between `0` and `1`.
+    pub roll: f32,
+}
+
+/// A numeric value of a mod line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModValue {
+    /// The rolled value, for ranges this is resolved from the roll position.
+    pub value: f32,
+    /// Minimum and maximum of the value, if the mod line contains a range.
+    pub range: Option<(f32, f32)>,
 }
 
 impl<'a> Mod<'a> {
@@ -292,6 +479,8 @@ impl<'a> Mod<'a> {
         let mut fractured = false;
         let mut crafted = false;
         let mut variant = None;
+        let mut tags = None;
+        let mut range = None;
         let mut tag = None;
 
         while let Some((attr, other)) = mod_line.trim_start_matches('{').split_once('}') {
@@ -302,7 +491,9 @@ impl<'a> Mod<'a> {
                 "variant" => variant = Some(value),
                 "fractured" => fractured = true,
                 "crafted" => crafted = true,
-                "tags" | "custom" | "range" => (),
+                "tags" => tags = Some(value),
+                "range" => range = value.parse().ok(),
+                "custom" => (),
                 t => tag = Some(t),
             }
         }
@@ -312,10 +503,76 @@ impl<'a> Mod<'a> {
             crafted,
             line: mod_line,
             tag,
+            range,
             variant,
+            tags,
         }
     }
 
+    /// Variants this mod is available on, empty if it is available on all variants.
+    pub fn variants(&self) -> impl Iterator<Item = &'a str> {
+        self.variant.into_iter().flat_map(|v| v.split(','))
+    }
+
+    /// Mod tags, e.g. `life` or `elemental`.
+    pub fn tags(&self) -> impl Iterator<Item = &'a str> {
+        self.tags
+            .into_iter()
+            .flat_map(|t| t.split(','))
+            .filter(|t| !t.is_empty())
+    }
+
+    /// All numeric values of the mod line, in order of appearance.
+    ///
+    /// Unrolled ranges like `(10-20)` are resolved using the roll position.
+    pub fn values(&self) -> Vec<ModValue> {
+        parse_mod_values(self.line, self.range).1
+    }
+
+    /// The mod line with every numeric value replaced by `#`,
+    /// e.g. `+(80-89) to maximum Life` becomes `# to maximum Life`.
+    pub fn template(&self) -> String {
+        parse_mod_values(self.line, self.range).0
+    }
+
+    /// Tier of the mod on the item `base`, resolved from the rolled values.
+    ///
+    /// Tiers are ranked among the mods which can roll on the base.
+    /// Crafted mods, mods which are not part of the mod pool, e.g. unique mods,
+    /// and mods on unknown bases have no tier.
+    pub fn tier(&self, base: &str) -> Option<ModTier> {
+        if self.crafted {
+            return None;
+        }
+
+        let base = poe_data::bases::by_name(base)?;
+        let (template, values) = parse_mod_values(self.line, self.range);
+        let values = values.iter().map(|v| v.value).collect::<Vec<_>>();
+        let m = poe_data::mods::find(base, &template, &values)?;
+        let tier = m.tier(base)?;
+
+        let rolls = m
+            .stats
+            .iter()
+            .zip(&values)
+            .map(|(&(min, max), &value)| match max > min {
+                true => (value - min) / (max - min),
+                false => 1.0,
+            })
+            .collect::<Vec<_>>();
+        let roll = match rolls.is_empty() {
+            true => 1.0,
+            false => rolls.iter().sum::<f32>() / rolls.len() as f32,
+        };
+
+        Some(ModTier {
+            tier,
+            name: m.name,
+            generation_type: m.generation_type,
+            roll,
+        })
+    }
+
     fn has_variant(&self, target: &str) -> bool {
         if target.is_empty() {
             return true;
@@ -327,6 +584,107 @@ impl<'a> Mod<'a> {
     }
 }
 
+/// Splits a mod line into its template and numeric values.
+fn parse_mod_values(line: &str, range: Option<f32>) -> (String, Vec<ModValue>) {
+    let mut template = String::with_capacity(line.len());
+    let mut values = Vec::new();
+
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let at_word_start = match template.chars().next_back() {
+            Some(prev) => prev.is_whitespace() || prev == '(',
+            None => true,
+        };
+
+        // Ranges can be prefixed with a sign, e.g. `+(10-20)`.
+        let (sign, unsigned) = match rest.strip_prefix(['+', '-']) {
+            Some(unsigned) if at_word_start && unsigned.starts_with('(') => {
+                (if c == '-' { -1.0 } else { 1.0 }, unsigned)
+            }
+            _ => (1.0, rest),
+        };
+
+        if let Some((value, len)) = parse_mod_range(unsigned, range.unwrap_or(0.5)) {
+            values.push(ModValue {
+                value: sign * value.value,
+                range: value.range.map(|(min, max)| (sign * min, sign * max)),
+            });
+            template.push('#');
+            rest = &unsigned[len..];
+        } else if let Some((value, len)) = parse_mod_number(rest, at_word_start) {
+            values.push(ModValue { value, range: None });
+            template.push('#');
+            rest = &rest[len..];
+        } else {
+            template.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    (template, values)
+}
+
+/// Parses a range in the form of `(min-max)`, returns the value and length of the range.
+fn parse_mod_range(s: &str, position: f32) -> Option<(ModValue, usize)> {
+    let inner = s.strip_prefix('(')?;
+    let (min, min_len) = parse_mod_number(inner, true)?;
+    let inner = inner[min_len..].strip_prefix('-')?;
+    let (max, max_len) = parse_mod_number(inner, true)?;
+    inner[max_len..].strip_prefix(')')?;
+
+    let decimals = [&s[1..1 + min_len], &inner[..max_len]]
+        .iter()
+        .filter_map(|n| n.split_once('.'))
+        .map(|(_, d)| d.len() as i32)
+        .max()
+        .unwrap_or(0);
+    let precision = 10f32.powi(decimals);
+    let value = ((min + (max - min) * position) * precision).round() / precision;
+
+    let len = 1 + min_len + 1 + max_len + 1;
+    Some((
+        ModValue {
+            value,
+            range: Some((min, max)),
+        },
+        len,
+    ))
+}
+
+/// Parses a decimal number at the start of `s`, returns the number and its length.
+///
+/// A leading sign is only accepted if `signed` is set.
+fn parse_mod_number(s: &str, signed: bool) -> Option<(f32, usize)> {
+    let bytes = s.as_bytes();
+
+    let mut len = 0;
+    if signed && matches!(bytes.first(), Some(b'+' | b'-')) {
+        len += 1;
+    }
+
+    let digits = bytes[len..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    len += digits;
+
+    if bytes.get(len) == Some(&b'.') {
+        let decimals = bytes[len + 1..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if decimals > 0 {
+            len += 1 + decimals;
+        }
+    }
+
+    let value = s[..len].trim_start_matches('+').parse().ok()?;
+    Some((value, len))
+}
+
 /// Iterator which supports mods split over multiple lines.
 struct ModLines<'a> {
     lines: &'a str,
@@ -456,6 +814,42 @@ fn fixup_item_name(mut name: &str) -> &str {
     return name[..end].trim();
 }
 
+/// Finds the known unique in an item name, e.g. `Endgame - Foulborn Mageblood [123]`.
+///
+/// Creators add prefixes and suffixes separated by `- ` and unique names can contain
+/// dashes themselves, every dash separated part of the name is matched, longest first.
+fn find_unique(name: &str) -> Option<(&'static poe_data::uniques::Unique, bool)> {
+    let name = &name[..name.find('[').unwrap_or(name.len())];
+
+    let separators = name.match_indices("- ").map(|(i, _)| i).collect::<Vec<_>>();
+    let starts = std::iter::once(0)
+        .chain(separators.iter().map(|i| i + 2))
+        .collect::<Vec<_>>();
+    let ends = separators
+        .iter()
+        .copied()
+        .chain(std::iter::once(name.len()))
+        .collect::<Vec<_>>();
+
+    for len in (1..=starts.len()).rev() {
+        for (&start, &end) in starts.iter().zip(&ends[len - 1..]) {
+            let (candidate, foulborn) = strip_foulborn(name[start..end].trim());
+            if let Some(unique) = poe_data::uniques::by_name(candidate) {
+                return Some((unique, foulborn));
+            }
+        }
+    }
+
+    None
+}
+
+fn strip_foulborn(name: &str) -> (&str, bool) {
+    match name.strip_prefix(FOULBORN) {
+        Some(name) => (name, true),
+        None => (name, false),
+    }
+}
+
 fn extract_magic_base(base: &str, num_mods: usize) -> &str {
     if num_mods == 0 {
         return base;
@@ -608,6 +1002,25 @@ Implicits: 0
         assert_eq!(item.explicits().count(), 1);
     }
 
+    #[test]
+    fn magic_known_base() {
+        let item = Item::parse(
+            r#"Rarity: MAGIC
+Fingerless Silk Gloves of the Fox
+Item Level: 84
+LevelReq: 70
+Implicits: 1
+14% increased Spell Damage
++22 to Dexterity
++40 to maximum Life
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.base, "Fingerless Silk Gloves");
+        assert_eq!(item.explicits().count(), 2);
+    }
+
     #[test]
     fn magic_synthesised_jewel() {
         let item = Item::parse(
@@ -668,12 +1081,78 @@ Extra gore"#,
 
         assert_eq!(item.item_level, 0);
         assert_eq!(item.name, Some("Endgame - Carcass-Jack [123]"));
+        assert_eq!(
+            item.variants().collect::<Vec<_>>(),
+            ["Pre 3.0.0", "Pre 3.5.0", "Current"]
+        );
+        assert_eq!(item.selected_variant(), Some("Current"));
+        assert_eq!(
+            item.unique(),
+            Some(UniqueItem {
+                name: "Carcass-Jack",
+                variant: Some("Current"),
+                relic: false,
+                foulborn: false,
+                known: false,
+            })
+        );
         assert_eq!(item.fixed_item_name(), Some("Carcass-Jack"));
         assert_eq!(item.enchants().count(), 0);
         assert_eq!(item.implicits().count(), 0);
         assert_eq!(item.explicits().count(), 6);
     }
 
+    #[test]
+    fn unique_creator_names() {
+        let unique = |name: &str, expected: (&str, bool, bool)| {
+            let item = format!("Rarity: UNIQUE\n{name}\nHeavy Belt\nImplicits: 0");
+            let item = Item::parse(&item).unwrap();
+            let unique = item.unique().unwrap();
+            assert_eq!((unique.name, unique.foulborn, unique.known), expected);
+        };
+
+        unique("Mageblood", ("Mageblood", false, true));
+        unique("Endgame - Mageblood", ("Mageblood", false, true));
+        unique("Mageblood - Swap", ("Mageblood", false, true));
+        unique(
+            "Foo - Foulborn Mageblood - Bar [1]",
+            ("Mageblood", true, true),
+        );
+        unique("Foo - Bar", ("Bar", false, false));
+    }
+
+    #[test]
+    fn unique_foulborn_relic() {
+        let item = Item::parse(
+            r#"Rarity: RELIC
+Foulborn Thread of Hope
+Crimson Jewel
+Variant: Small Ring
+Variant: Medium Ring
+Variant: Large Ring
+Variant: Very Large Ring
+Variant: Massive Ring
+Selected Variant: 3
+Implicits: 0
+{variant:3}Only affects Passives in Large Ring
+Passives in Radius can be Allocated without being connected to your tree"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.variants().count(), 5);
+        assert_eq!(
+            item.unique(),
+            Some(UniqueItem {
+                name: "Thread of Hope",
+                variant: Some("Large Ring"),
+                relic: true,
+                foulborn: true,
+                known: true,
+            })
+        );
+        assert_eq!(item.explicits().count(), 2);
+    }
+
     #[test]
     fn rare_implicits_fractured_crafted() {
         let item = Item::parse(
@@ -940,6 +1419,91 @@ Implicits: 0
         assert_eq!(chaos_res.tag, Some("crucible"));
     }
 
+    #[test]
+    fn mod_tiers() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Foo Bar
+Titan Greaves
+Implicits: 0
++150 to maximum Life
+{crafted}{range:1}+(80-89) to maximum Life
+{fractured}+35% to Chaos Resistance
+Unknown mod"#,
+        )
+        .unwrap();
+
+        let tiers = item
+            .explicits()
+            .map(|m| m.tier(item.base))
+            .collect::<Vec<_>>();
+        let life = tiers[0].unwrap();
+        assert_eq!(life.tier, 1);
+        assert_eq!(life.name, "Vigorous");
+        assert_eq!(life.generation_type, GenerationType::Prefix);
+        assert!((life.roll - 5.0 / 14.0).abs() < 0.001);
+        assert_eq!(tiers[1], None);
+        assert_eq!(tiers[2].unwrap().tier, 1);
+        assert_eq!(tiers[2].unwrap().roll, 1.0);
+        assert_eq!(tiers[3], None);
+
+        let quality = item.roll_quality().unwrap();
+        assert!((quality - (5.0 / 14.0 + 1.0) / 2.0).abs() < 0.001);
+
+        // Life tiers above `Vigorous` only roll on body armours and shields.
+        let life = item.explicits().next().unwrap();
+        assert_eq!(life.tier("Glorious Plate").unwrap().tier, 3);
+        assert_eq!(life.tier("Unknown Base"), None);
+    }
+
+    #[test]
+    fn mod_values() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Foo Bar
+Triumphant Lamellar
+Implicits: 1
+{tags:defences}{range:0.5}+(10-20)% to Fire Resistance
+{crafted}{tags:life}{range:1}+(80-89) to maximum Life
+{fractured}+35% to Chaos Resistance
+Adds 1 to 3 Lightning Damage
+{variant:1,2}{range:0}(-10--5)% reduced Mana Cost
+{range:0.25}(0.2-0.6)% of Physical Attack Damage Leeched as Life"#,
+        )
+        .unwrap();
+
+        let mods = item.mods().collect::<Vec<_>>();
+        assert_eq!(mods.len(), 6);
+
+        assert_eq!(mods[0].template(), "#% to Fire Resistance");
+        assert_eq!(mods[0].tags().collect::<Vec<_>>(), vec!["defences"]);
+        assert_eq!(
+            mods[0].values(),
+            vec![ModValue {
+                value: 15.0,
+                range: Some((10.0, 20.0))
+            }]
+        );
+
+        assert!(mods[1].crafted);
+        assert_eq!(mods[1].template(), "# to maximum Life");
+        assert_eq!(mods[1].values()[0].value, 89.0);
+
+        assert!(mods[2].fractured);
+        assert_eq!(mods[2].values()[0].value, 35.0);
+        assert_eq!(mods[2].values()[0].range, None);
+
+        assert_eq!(mods[3].template(), "Adds # to # Lightning Damage");
+        let values = mods[3].values();
+        assert_eq!((values[0].value, values[1].value), (1.0, 3.0));
+
+        assert_eq!(mods[4].variants().collect::<Vec<_>>(), vec!["1", "2"]);
+        assert_eq!(mods[4].template(), "#% reduced Mana Cost");
+        assert_eq!(mods[4].values()[0].value, -10.0);
+
+        assert_eq!(mods[5].values()[0].value, 0.3);
+    }
+
     #[test]
     fn mod_lines() {
         let lines = ModLines::new("foo\nbar\nfirst you've\nsecond\nbaz").collect::<Vec<_>>();
@@ -951,4 +1515,47 @@ Implicits: 0
         let lines = ModLines::new("first you've").collect::<Vec<_>>();
         assert_eq!(lines, vec!["first you've"]);
     }
+
+    #[test]
+    fn sockets() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Foo Bar
+Vaal Regalia
+Sockets: R-G-B-B W A
+Implicits: 0
++(80-89) to maximum Life"#,
+        )
+        .unwrap();
+
+        use SocketColor::*;
+        assert_eq!(
+            item.sockets(),
+            vec![
+                SocketGroup {
+                    sockets: vec![Red, Green, Blue, Blue]
+                },
+                SocketGroup {
+                    sockets: vec![White]
+                },
+                SocketGroup {
+                    sockets: vec![Abyss]
+                },
+            ]
+        );
+        assert_eq!(item.max_links(), 4);
+        assert_eq!(item.explicits().count(), 1);
+
+        let item = Item::parse("Rarity: NORMAL\nIron Ring\nImplicits: 0").unwrap();
+        assert!(item.sockets().is_empty());
+        assert_eq!(item.max_links(), 0);
+
+        let item =
+            Item::parse("Rarity: RARE\nTest\nStygian Vise\nSockets: A\nImplicits: 0").unwrap();
+        assert_eq!(item.max_links(), 0);
+
+        let item =
+            Item::parse("Rarity: RARE\nTest\nLeather Belt\nSockets: R-A-A\nImplicits: 0").unwrap();
+        assert_eq!(item.max_links(), 1);
+    }
 }
@@ -1,23 +1,30 @@
 use std::str::FromStr;
 
+pub mod api;
+pub mod character;
 mod config;
+pub mod diff;
 mod error;
 mod gems;
 mod items;
+pub mod lint;
 mod passives;
 mod serde;
 mod stats;
+pub mod trade;
 mod utils;
 
 use shared::{Ascendancy, AscendancyOrClass, Class};
 
+pub use poe_data::mods::GenerationType;
+
 pub use self::config::{Config, ConfigValue};
 pub use self::error::{Error, Result};
-pub use self::items::{Influence, Item, Mod, Rarity};
+pub use self::items::{Influence, Item, Mod, ModTier, ModValue, Rarity, SocketColor, SocketGroup};
 pub use self::passives::Keystone;
 pub use self::serde::SerdePathOfBuilding;
 pub use self::stats::Stat;
-pub use self::utils::decompress;
+pub use self::utils::{compress, decompress};
 
 pub trait PathOfBuilding {
     fn level(&self) -> u8;
@@ -35,6 +42,8 @@ pub trait PathOfBuilding {
     fn skill_sets(&self) -> Vec<SkillSet>;
 
     fn item_by_id(&self, id: u16) -> Option<&str>;
+    /// Ids of all items, in ascending order, see [`PathOfBuilding::item_by_id`].
+    fn item_ids(&self) -> Vec<u16>;
     fn item_sets(&self) -> Vec<ItemSet>;
 
     fn tree_specs(&self) -> Vec<TreeSpec>;
@@ -115,6 +124,8 @@ pub struct ItemSet<'a> {
 pub struct Gear<'a> {
     pub weapon1: Option<&'a str>,
     pub weapon2: Option<&'a str>,
+    pub weapon1_swap: Option<&'a str>,
+    pub weapon2_swap: Option<&'a str>,
     pub helmet: Option<&'a str>,
     pub body_armour: Option<&'a str>,
     pub gloves: Option<&'a str>,
@@ -131,6 +142,32 @@ pub struct Gear<'a> {
     pub sockets: Vec<&'a str>,
 }
 
+impl<'a> Gear<'a> {
+    /// Iterates over all named equipment slots, excluding jewel and abyss sockets.
+    pub fn slots(&self) -> impl Iterator<Item = (&'static str, Option<&'a str>)> {
+        [
+            ("Weapon 1", self.weapon1),
+            ("Weapon 2", self.weapon2),
+            ("Weapon 1 Swap", self.weapon1_swap),
+            ("Weapon 2 Swap", self.weapon2_swap),
+            ("Helmet", self.helmet),
+            ("Body Armour", self.body_armour),
+            ("Gloves", self.gloves),
+            ("Boots", self.boots),
+            ("Amulet", self.amulet),
+            ("Ring 1", self.ring1),
+            ("Ring 2", self.ring2),
+            ("Belt", self.belt),
+            ("Flask 1", self.flask1),
+            ("Flask 2", self.flask2),
+            ("Flask 3", self.flask3),
+            ("Flask 4", self.flask4),
+            ("Flask 5", self.flask5),
+        ]
+        .into_iter()
+    }
+}
+
 pub trait PathOfBuildingExt: PathOfBuilding {
     fn ascendancy_or_class(&self) -> AscendancyOrClass {
         self.ascendancy()
@@ -0,0 +1,279 @@
+//! Common mistakes in builds.
+//!
+//! Lints only look at the selected skill and item set and at the stats
+//! calculated by PoB, they never fail, missing data just skips the check.
+
+use std::fmt;
+
+use crate::gems;
+use crate::{Keystone, PathOfBuilding, PathOfBuildingExt, Skill, Stat};
+
+/// Resistances are capped at 75% unless the maximum is increased.
+const RESISTANCE_CAP: f32 = 75.0;
+/// Attacks missing more often than this are worth a warning.
+const MIN_HIT_CHANCE: f32 = 90.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning<'a> {
+    /// A support gem which can not support any active skill in its group.
+    UnsupportedGem {
+        support: &'a str,
+        skill: &'a str,
+    },
+    /// The socket group of the main skill is disabled.
+    MainSkillDisabled,
+    UncappedResistance {
+        stat: Stat,
+        value: f32,
+    },
+    LowHitChance(f32),
+    /// Negative chaos resistance without Chaos Inoculation.
+    NegativeChaosResistance(f32),
+    /// Number of empty flask slots in the selected item set.
+    MissingFlasks(usize),
+    OutdatedTree {
+        title: Option<&'a str>,
+        version: &'a str,
+    },
+}
+
+impl fmt::Display for Warning<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedGem { support, skill } => {
+                write!(f, "{support} does not support {skill}")
+            }
+            Self::MainSkillDisabled => write!(f, "The main skill is disabled"),
+            Self::UncappedResistance { stat, value } => {
+                let name = match stat {
+                    Stat::FireResistance => "Fire",
+                    Stat::ColdResistance => "Cold",
+                    Stat::LightningResistance => "Lightning",
+                    _ => "Elemental",
+                };
+                write!(f, "{name} resistance is not capped ({value}%)")
+            }
+            Self::LowHitChance(value) => write!(f, "Low chance to hit ({value}%)"),
+            Self::NegativeChaosResistance(value) => {
+                write!(f, "Negative chaos resistance ({value}%)")
+            }
+            Self::MissingFlasks(1) => write!(f, "1 flask slot is empty"),
+            Self::MissingFlasks(count) => write!(f, "{count} flask slots are empty"),
+            Self::OutdatedTree {
+                title: Some(title),
+                version,
+            } => write!(f, "Tree '{title}' is from version {version}"),
+            Self::OutdatedTree {
+                title: None,
+                version,
+            } => write!(f, "Tree is from version {version}"),
+        }
+    }
+}
+
+/// Runs all lints on the build.
+///
+/// Tree specs are only checked for outdated versions if `latest_tree_version`
+/// is given, in the same format as PoB exports it, e.g. `3_23` or `3.23`.
+pub fn lint<'a>(
+    pob: &'a impl PathOfBuilding,
+    latest_tree_version: Option<&str>,
+) -> Vec<Warning<'a>> {
+    let mut warnings = Vec::new();
+
+    lint_skills(pob, &mut warnings);
+    lint_stats(pob, &mut warnings);
+    lint_flasks(pob, &mut warnings);
+    if let Some(latest) = latest_tree_version.and_then(parse_version) {
+        lint_trees(pob, latest, &mut warnings);
+    }
+
+    warnings
+}
+
+fn lint_skills<'a>(pob: &'a impl PathOfBuilding, warnings: &mut Vec<Warning<'a>>) {
+    let Some(skill_set) = pob.skill_sets().into_iter().find(|ss| ss.is_selected) else {
+        return;
+    };
+
+    for skill in &skill_set.skills {
+        if skill.is_selected && !skill.is_enabled {
+            warnings.push(Warning::MainSkillDisabled);
+        }
+        if skill.is_enabled {
+            lint_supports(skill, warnings);
+        }
+    }
+}
+
+fn lint_supports<'a>(skill: &Skill<'a>, warnings: &mut Vec<Warning<'a>>) {
+    let actives = skill
+        .gems
+        .iter()
+        .filter(|gem| gem.is_active && gem.is_enabled)
+        .map(|gem| (gem, gems::active_skill_types(gem)))
+        .collect::<Vec<_>>();
+
+    // Without knowing all skill types a support may still support an unknown skill.
+    let Some(&(main, _)) = actives.first() else {
+        return;
+    };
+    let known = actives
+        .iter()
+        .map(|&(_, types)| Some(types).filter(|types| !types.is_empty()))
+        .collect::<Option<Vec<_>>>();
+    let Some(known) = known else {
+        return;
+    };
+
+    for support in skill.gems.iter().filter(|g| g.is_support && g.is_enabled) {
+        let Some(requirement) = gems::support_requirement(support) else {
+            continue;
+        };
+
+        let supported = |types: &&[&str]| types.iter().any(|t| requirement.contains(t));
+        if !known.iter().any(supported) {
+            warnings.push(Warning::UnsupportedGem {
+                support: support.name,
+                skill: main.name,
+            });
+        }
+    }
+}
+
+fn lint_stats<'a>(pob: &'a impl PathOfBuilding, warnings: &mut Vec<Warning<'a>>) {
+    for stat in [
+        Stat::FireResistance,
+        Stat::ColdResistance,
+        Stat::LightningResistance,
+    ] {
+        if let Some(value) = pob.stat_parse::<f32>(stat) {
+            if value < RESISTANCE_CAP {
+                warnings.push(Warning::UncappedResistance { stat, value });
+            }
+        }
+    }
+
+    if let Some(value) = pob.stat_parse::<f32>(Stat::HitChance) {
+        if value < MIN_HIT_CHANCE {
+            warnings.push(Warning::LowHitChance(value));
+        }
+    }
+
+    if let Some(value) = pob.stat_parse::<f32>(Stat::ChaosResistance) {
+        if value < 0.0 && !pob.has_keystone(Keystone::ChaosInoculation) {
+            warnings.push(Warning::NegativeChaosResistance(value));
+        }
+    }
+}
+
+fn lint_flasks<'a>(pob: &'a impl PathOfBuilding, warnings: &mut Vec<Warning<'a>>) {
+    let Some(item_set) = pob.item_sets().into_iter().find(|is| is.is_selected) else {
+        return;
+    };
+
+    let gear = &item_set.gear;
+    let missing = [
+        gear.flask1,
+        gear.flask2,
+        gear.flask3,
+        gear.flask4,
+        gear.flask5,
+    ]
+    .iter()
+    .filter(|flask| flask.is_none())
+    .count();
+
+    if missing > 0 {
+        warnings.push(Warning::MissingFlasks(missing));
+    }
+}
+
+fn lint_trees<'a>(
+    pob: &'a impl PathOfBuilding,
+    latest: (u32, u32),
+    warnings: &mut Vec<Warning<'a>>,
+) {
+    for spec in pob.tree_specs() {
+        let Some(version) = spec.version else {
+            continue;
+        };
+
+        if parse_version(version).is_some_and(|version| version < latest) {
+            warnings.push(Warning::OutdatedTree {
+                title: spec.title,
+                version,
+            });
+        }
+    }
+}
+
+/// Parses a tree version like `3_23` or `3.23` into a comparable tuple.
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once(['_', '.'])?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SerdePathOfBuilding;
+
+    static V316_POISON_OCC: &str = include_str!("../test/316_poison_occ.xml");
+
+    #[test]
+    fn lint_clean() {
+        let pob = SerdePathOfBuilding::from_xml(V316_POISON_OCC).unwrap();
+        assert_eq!(lint(&pob, None), vec![]);
+        assert_eq!(
+            lint(&pob, Some("3_16")),
+            vec![Warning::OutdatedTree {
+                title: Some("Uber Lab"),
+                version: "3_9"
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_warnings() {
+        let pob = V316_POISON_OCC
+            .replace(
+                r#"enabled="true" slot="Body Armour""#,
+                r#"enabled="false" slot="Body Armour""#,
+            )
+            .replace(
+                r#"skillId="SupportCastOnDamageTaken" qualityId="Default" gemId="Metadata/Items/Gems/SupportGemCastOnDamageTaken""#,
+                r#"skillId="SupportMeleePhysicalDamage" qualityId="Default" gemId="Metadata/Items/Gems/SupportGemMeleePhysicalDamage""#,
+            )
+            .replace(
+                r#"<PlayerStat stat="FireResist" value="75"/>"#,
+                r#"<PlayerStat stat="FireResist" value="60"/>"#,
+            )
+            .replace(
+                r#"<PlayerStat stat="ChaosResist" value="27"/>"#,
+                r#"<PlayerStat stat="ChaosResist" value="-30"/>"#,
+            )
+            .replace(
+                r#"<PlayerStat stat="HitChance" value="97"/>"#,
+                r#"<PlayerStat stat="HitChance" value="80"/>"#,
+            );
+        let pob = SerdePathOfBuilding::from_xml(&pob).unwrap();
+
+        assert_eq!(
+            lint(&pob, None),
+            vec![
+                Warning::MainSkillDisabled,
+                Warning::UnsupportedGem {
+                    support: "Cast when Damage Taken",
+                    skill: "Steelskin"
+                },
+                Warning::UncappedResistance {
+                    stat: Stat::FireResistance,
+                    value: 60.0
+                },
+                Warning::LowHitChance(80.0),
+                Warning::NegativeChaosResistance(-30.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, HashMap};
+
+use shared::{Ascendancy, AscendancyOrClass, Class};
+
+use crate::character::{Character, CharacterItem};
+use crate::serde::model::*;
+use crate::{Error, Result};
+
+/// Converts a character of the official API into the build model.
+pub(crate) fn to_model(character: &Character) -> Result<PathOfBuilding> {
+    let (class, ascendancy) = match character.class.parse::<AscendancyOrClass>() {
+        Ok(AscendancyOrClass::Ascendancy(ascendancy)) => (ascendancy.class(), Some(ascendancy)),
+        Ok(AscendancyOrClass::Class(class)) => (class, None),
+        Err(_) => return Err(Error::UnknownClass(character.class.clone())),
+    };
+
+    let mut items = HashMap::new();
+    let mut gear = Gear::default();
+    let mut skills = Vec::new();
+
+    let equipment = character.equipment.iter().map(|item| (item, item.slot()));
+    let jewels = character.jewels.iter().map(|item| (item, None));
+    for (item, slot) in equipment.chain(jewels) {
+        let Some(content) = item.to_pob_item() else {
+            continue;
+        };
+
+        let id = items.len() as u16 + 1;
+        items.insert(
+            id,
+            Item {
+                id,
+                content: ItemContent { content },
+            },
+        );
+
+        let Some(slot) = slot else {
+            // Jewels are not placed in the tree, socket positions require tree data.
+            continue;
+        };
+        if let Some(gear_slot) = gear.slot_mut(&slot) {
+            *gear_slot = Some(id);
+        }
+        skills.extend(to_skills(item, &slot));
+    }
+
+    // The largest socket group is most likely the main skill.
+    let main_socket_group = skills
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, skill)| (skill.gems.len(), std::cmp::Reverse(*index)))
+        .map_or(1, |(index, _)| index as u8 + 1);
+
+    let mut nodes = character.passives.hashes.clone();
+    nodes.extend(&character.passives.hashes_ex);
+    let mut mastery_effects = character
+        .passives
+        .mastery_effects
+        .iter()
+        .filter_map(|(node, effect)| Some((node.parse().ok()?, *effect)))
+        .collect::<Vec<_>>();
+    mastery_effects.sort_unstable();
+
+    let notes = match &character.league {
+        Some(league) => format!("Imported from {} ({league})", character.name),
+        None => format!("Imported from {}", character.name),
+    };
+
+    Ok(PathOfBuilding {
+        build: Build {
+            level: character.level,
+            class_name: class,
+            ascend_class_name: ascendancy,
+            stats: Vec::new(),
+            main_socket_group,
+        },
+        skills: Skills {
+            active_skill_set: None,
+            skill_sets: Vec::new(),
+            skills,
+        },
+        tree: Tree {
+            active_spec: 1,
+            specs: vec![Spec {
+                title: None,
+                class_id: Some(class_id(class)),
+                ascend_class_id: Some(ascendancy.map_or(0, ascendancy_id)),
+                secondary_ascend_class_id: None,
+                nodes,
+                mastery_effects,
+                url: None,
+                sockets: Sockets::default(),
+                overrides: Overrides::default(),
+                version: None,
+            }],
+        },
+        items: Items {
+            active_item_set: Some(1),
+            items,
+            item_sets: vec![ItemSet {
+                id: 1,
+                title: None,
+                gear,
+            }],
+        },
+        notes,
+        config: Config::default(),
+    })
+}
+
+/// Creates a skill for every linked group of gems socketed in the item.
+fn to_skills(item: &CharacterItem, slot: &str) -> Vec<Skill> {
+    let mut groups = BTreeMap::<u8, Vec<Gem>>::new();
+
+    for gem in item.socketed_items.iter().filter(|gem| gem.is_gem()) {
+        let group = gem
+            .socket
+            .and_then(|socket| item.socket_group(socket))
+            .unwrap_or_default();
+
+        let name = gem
+            .base_type
+            .clone()
+            .unwrap_or_else(|| gem.type_line.clone());
+        // PoB resolves the gem by its id, gems unknown to us are still matched by name.
+        let gem_id = poe_data::gems::by_name(&name).map(|gem| gem.id.to_owned());
+
+        groups.entry(group).or_default().push(Gem {
+            name,
+            skill_id: None,
+            gem_id,
+            quality_id: None,
+            enabled: true,
+            level: gem.property("Level").unwrap_or(1),
+            quality: gem.property("Quality").unwrap_or(0),
+        });
+    }
+
+    groups
+        .into_values()
+        .map(|gems| Skill {
+            main_active_skill: Some(1),
+            enabled: true,
+            label: None,
+            slot: Some(slot.to_owned()),
+            gems,
+        })
+        .collect()
+}
+
+/// Class ids as used by the passive tree.
+fn class_id(class: Class) -> u8 {
+    match class {
+        Class::Scion => 0,
+        Class::Marauder => 1,
+        Class::Ranger => 2,
+        Class::Witch => 3,
+        Class::Duelist => 4,
+        Class::Templar => 5,
+        Class::Shadow => 6,
+    }
+}
+
+/// Ascendancy ids as used by the passive tree, these are relative to the class.
+fn ascendancy_id(ascendancy: Ascendancy) -> u8 {
+    match ascendancy {
+        Ascendancy::Ascendant => 1,
+        Ascendancy::Juggernaut => 1,
+        Ascendancy::Berserker => 2,
+        Ascendancy::Chieftain => 3,
+        Ascendancy::Raider => 1,
+        Ascendancy::Deadeye => 2,
+        Ascendancy::Pathfinder => 3,
+        Ascendancy::Occultist => 1,
+        Ascendancy::Elementalist => 2,
+        Ascendancy::Necromancer => 3,
+        Ascendancy::Slayer => 1,
+        Ascendancy::Gladiator => 2,
+        Ascendancy::Champion => 3,
+        Ascendancy::Inquisitor => 1,
+        Ascendancy::Hierophant => 2,
+        Ascendancy::Guardian => 3,
+        Ascendancy::Assassin => 1,
+        Ascendancy::Trickster => 2,
+        Ascendancy::Saboteur => 3,
+    }
+}
@@ -1,5 +1,7 @@
+mod character;
 mod model;
 mod pob;
 mod utils;
+mod writer;
 
 pub use self::pob::SerdePathOfBuilding;
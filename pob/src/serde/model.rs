@@ -33,7 +33,7 @@ pub(crate) struct Build {
     pub class_name: Class,
     #[serde(default, deserialize_with = "deserialize_ascendancy")]
     pub ascend_class_name: Option<Ascendancy>,
-    #[serde(rename = "$value")]
+    #[serde(default, rename = "$value")]
     pub stats: Vec<StatType>,
     pub main_socket_group: u8,
 }
@@ -409,7 +409,37 @@ pub(crate) struct Gear {
     pub flask3: Option<u16>,
     pub flask4: Option<u16>,
     pub flask5: Option<u16>,
-    pub sockets: Vec<u16>,
+    pub sockets: Vec<Slot>,
+}
+
+impl Gear {
+    /// Returns the item of an equipment slot by its PoB slot name, e.g. `Weapon 1 Swap`.
+    ///
+    /// Jewel sockets are not equipment slots.
+    pub fn slot_mut(&mut self, name: &str) -> Option<&mut Option<u16>> {
+        let slot = match name {
+            "Weapon 1" => &mut self.weapon1,
+            "Weapon 2" => &mut self.weapon2,
+            "Weapon 1 Swap" => &mut self.weapon1_swap,
+            "Weapon 2 Swap" => &mut self.weapon2_swap,
+            "Helmet" => &mut self.helmet,
+            "Body Armour" => &mut self.body_armour,
+            "Gloves" => &mut self.gloves,
+            "Boots" => &mut self.boots,
+            "Amulet" => &mut self.amulet,
+            "Ring 1" => &mut self.ring1,
+            "Ring 2" => &mut self.ring2,
+            "Belt" => &mut self.belt,
+            "Flask 1" => &mut self.flask1,
+            "Flask 2" => &mut self.flask2,
+            "Flask 3" => &mut self.flask3,
+            "Flask 4" => &mut self.flask4,
+            "Flask 5" => &mut self.flask5,
+            _ => return None,
+        };
+
+        Some(slot)
+    }
 }
 
 impl<'de> de::Deserialize<'de> for Gear {
@@ -449,25 +479,9 @@ impl<'de> de::Deserialize<'de> for Gear {
                         continue;
                     }
 
-                    match slot.name.as_str() {
-                        "Weapon 1" => result.weapon1 = Some(slot.item_id),
-                        "Weapon 2" => result.weapon2 = Some(slot.item_id),
-                        "Weapon 1 Swap" => result.weapon1_swap = Some(slot.item_id),
-                        "Weapon 2 Swap" => result.weapon2_swap = Some(slot.item_id),
-                        "Helmet" => result.helmet = Some(slot.item_id),
-                        "Body Armour" => result.body_armour = Some(slot.item_id),
-                        "Gloves" => result.gloves = Some(slot.item_id),
-                        "Boots" => result.boots = Some(slot.item_id),
-                        "Amulet" => result.amulet = Some(slot.item_id),
-                        "Ring 1" => result.ring1 = Some(slot.item_id),
-                        "Ring 2" => result.ring2 = Some(slot.item_id),
-                        "Belt" => result.belt = Some(slot.item_id),
-                        "Flask 1" => result.flask1 = Some(slot.item_id),
-                        "Flask 2" => result.flask2 = Some(slot.item_id),
-                        "Flask 3" => result.flask3 = Some(slot.item_id),
-                        "Flask 4" => result.flask4 = Some(slot.item_id),
-                        "Flask 5" => result.flask5 = Some(slot.item_id),
-                        _ => result.sockets.push(slot.item_id),
+                    match result.slot_mut(&slot.name) {
+                        Some(item) => *item = Some(slot.item_id),
+                        None => result.sockets.push(slot),
                     }
                 }
 
@@ -6,27 +6,18 @@ use crate::{Config, ConfigValue, Error, Keystone, Result, Stat};
 #[derive(Debug)]
 pub struct SerdePathOfBuilding {
     pob: PathOfBuilding,
+    /// The original XML, only retained for builds which are written back,
+    /// see [`SerdePathOfBuilding::from_xml_retained`].
+    xml: Option<String>,
     // TODO: quick access list (indices) for active items (?)
 }
 
 impl SerdePathOfBuilding {
     pub fn from_xml(s: &str) -> Result<Self> {
-        let mut xd = quick_xml::de::Deserializer::from_reader(s.as_bytes());
-
-        #[cfg(any(feature = "better-errors", test))]
-        let pob = match serde_path_to_error::deserialize(&mut xd) {
-            Ok(pob) => pob,
-            Err(err) => {
-                let path = err.path().to_string();
-                return Err(Error::ParseXml(path, err.into_inner()));
-            }
-        };
-
-        #[cfg(not(any(feature = "better-errors", test)))]
-        let pob = serde::Deserialize::deserialize(&mut xd)
-            .map_err(|e| Error::ParseXml("Unknown".to_owned(), e))?;
-
-        Ok(Self { pob })
+        Ok(Self {
+            pob: parse_xml(s)?,
+            xml: None,
+        })
     }
 
     pub fn from_export(data: &str) -> Result<Self> {
@@ -34,6 +25,70 @@ impl SerdePathOfBuilding {
         Self::from_xml(&data)
     }
 
+    /// Parses the build and keeps the original XML, to write the build back without losing
+    /// anything which is not understood by the parser, see [`Self::to_xml`].
+    pub fn from_xml_retained(xml: String) -> Result<Self> {
+        Ok(Self {
+            pob: parse_xml(&xml)?,
+            xml: Some(xml),
+        })
+    }
+
+    /// Like [`Self::from_export`], but keeps the original XML, see [`Self::from_xml_retained`].
+    pub fn from_export_retained(data: &str) -> Result<Self> {
+        Self::from_xml_retained(crate::utils::decompress(data)?)
+    }
+
+    /// Converts a character of the official Path of Exile API into a build.
+    ///
+    /// Items, skills and passives are converted, jewels are added to the items
+    /// but not socketed into the tree.
+    pub fn from_character(character: &crate::character::Character) -> Result<Self> {
+        let pob = crate::serde::character::to_model(character)?;
+        Ok(Self { pob, xml: None })
+    }
+
+    /// Serializes the build back into Path of Building XML.
+    ///
+    /// Builds parsed with [`Self::from_xml_retained`] retain everything of the original XML,
+    /// including parts which are not understood by the parser. All other builds
+    /// only contain what is understood by the parser.
+    pub fn to_xml(&self) -> Result<String> {
+        match self.xml {
+            Some(ref xml) => crate::serde::writer::rewrite_xml(xml, &self.pob),
+            None => crate::serde::writer::to_xml(&self.pob),
+        }
+        .map_err(Error::WriteXml)
+    }
+
+    /// Serializes the build into an export code which can be imported into Path of Building.
+    pub fn to_export(&self) -> Result<String> {
+        crate::utils::compress(&self.to_xml()?)
+    }
+
+    /// Replaces the notes of the build.
+    pub fn set_notes(&mut self, notes: impl Into<String>) {
+        self.pob.notes = notes.into();
+    }
+
+    /// Selects the tree spec at `index` (as returned by [`crate::PathOfBuilding::tree_specs`])
+    /// as the active tree spec.
+    ///
+    /// Returns `false` if there is no tree spec at that index.
+    pub fn set_active_tree_spec(&mut self, index: usize) -> bool {
+        if index >= self.pob.tree.specs.len() {
+            return false;
+        }
+        // PoB tree specs start at 1.
+        match u8::try_from(index + 1) {
+            Ok(active_spec) => {
+                self.pob.tree.active_spec = active_spec;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
     fn main_skill(&self) -> Option<&Skill> {
         let mut index = self.pob.build.main_socket_group as usize;
         if index < 1 {
@@ -90,6 +145,25 @@ impl SerdePathOfBuilding {
     }
 }
 
+fn parse_xml(s: &str) -> Result<PathOfBuilding> {
+    let mut xd = quick_xml::de::Deserializer::from_reader(s.as_bytes());
+
+    #[cfg(any(feature = "better-errors", test))]
+    let pob = match serde_path_to_error::deserialize(&mut xd) {
+        Ok(pob) => pob,
+        Err(err) => {
+            let path = err.path().to_string();
+            return Err(Error::ParseXml(path, err.into_inner()));
+        }
+    };
+
+    #[cfg(not(any(feature = "better-errors", test)))]
+    let pob = serde::Deserialize::deserialize(&mut xd)
+        .map_err(|e| Error::ParseXml("Unknown".to_owned(), e))?;
+
+    Ok(pob)
+}
+
 impl crate::PathOfBuilding for SerdePathOfBuilding {
     fn level(&self) -> u8 {
         self.pob.build.level
@@ -201,6 +275,12 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
             .map(|item| item.content.content.as_str())
     }
 
+    fn item_ids(&self) -> Vec<u16> {
+        let mut ids = self.pob.items.items.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
     fn item_sets(&self) -> Vec<crate::ItemSet> {
         let item = |id| {
             self.pob
@@ -219,6 +299,8 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
                 let gear = crate::Gear {
                     weapon1: gear.weapon1.and_then(item),
                     weapon2: gear.weapon2.and_then(item),
+                    weapon1_swap: gear.weapon1_swap.and_then(item),
+                    weapon2_swap: gear.weapon2_swap.and_then(item),
                     helmet: gear.helmet.and_then(item),
                     body_armour: gear.body_armour.and_then(item),
                     gloves: gear.gloves.and_then(item),
@@ -232,7 +314,11 @@ impl crate::PathOfBuilding for SerdePathOfBuilding {
                     flask3: gear.flask3.and_then(item),
                     flask4: gear.flask4.and_then(item),
                     flask5: gear.flask5.and_then(item),
-                    sockets: gear.sockets.iter().filter_map(|&id| item(id)).collect(),
+                    sockets: gear
+                        .sockets
+                        .iter()
+                        .filter_map(|slot| item(slot.item_id))
+                        .collect(),
                 };
 
                 crate::ItemSet {
@@ -445,6 +531,11 @@ mod tests {
         assert_eq!(None, pob.item_sets()[0].title);
         assert_eq!(Some("Perfect Gear"), pob.item_sets()[1].title);
 
+        let item_ids = pob.item_ids();
+        assert_eq!(32, item_ids.len());
+        assert_eq!(Some(&32), item_ids.last());
+        assert!(item_ids.iter().all(|&id| pob.item_by_id(id).is_some()));
+
         // TODO: test configs
     }
 
@@ -515,4 +606,183 @@ mod tests {
         assert_eq!(wise.node_id, 50197);
         assert_eq!(wise.effect, "+1\n\t\t\t\t\tLimited to 1");
     }
+
+    #[test]
+    fn roundtrip_xml() {
+        for xml in [
+            V316_EMPTY,
+            V316_POISON_OCC,
+            V318_SKILLSET,
+            V319_MASTERY_EFFECTS,
+            V320_IMPENDING_DOOM,
+            V322_OVERRIDES,
+        ] {
+            let pob = SerdePathOfBuilding::from_xml_retained(xml.to_owned()).unwrap();
+            let written = pob.to_xml().unwrap();
+            let pob2 = SerdePathOfBuilding::from_xml(&written).unwrap();
+
+            // An unmodified build is written back exactly as it was read.
+            assert_eq!(written, xml);
+            assert_eq!(pob.level(), pob2.level());
+            assert_eq!(pob.ascendancy_or_class(), pob2.ascendancy_or_class());
+            assert_eq!(pob.notes(), pob2.notes());
+            assert_eq!(pob.main_skill_name(), pob2.main_skill_name());
+            assert_eq!(pob.stat(Stat::Life), pob2.stat(Stat::Life));
+            assert_eq!(
+                format!("{:?}", pob.skill_sets()),
+                format!("{:?}", pob2.skill_sets())
+            );
+            assert_eq!(
+                format!("{:?}", pob.item_sets()),
+                format!("{:?}", pob2.item_sets())
+            );
+            assert_eq!(
+                format!("{:?}", pob.tree_specs()),
+                format!("{:?}", pob2.tree_specs())
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrip_export() {
+        let pob = SerdePathOfBuilding::from_xml_retained(V316_POISON_OCC.to_owned()).unwrap();
+        let export = pob.to_export().unwrap();
+        let pob2 = SerdePathOfBuilding::from_export_retained(&export).unwrap();
+
+        assert_eq!(pob.to_xml().unwrap(), pob2.to_xml().unwrap());
+    }
+
+    #[test]
+    fn edit_notes_and_active_spec() {
+        let mut pob = SerdePathOfBuilding::from_xml_retained(V316_POISON_OCC.to_owned()).unwrap();
+        assert!(pob.tree_specs()[1].active);
+
+        pob.set_notes("");
+        assert!(pob.set_active_tree_spec(0));
+        assert!(!pob.set_active_tree_spec(100));
+
+        let xml = pob.to_xml().unwrap();
+        let pob = SerdePathOfBuilding::from_export(&pob.to_export().unwrap()).unwrap();
+        assert_eq!(pob.notes(), "");
+        assert!(pob.tree_specs()[0].active);
+        assert!(!pob.tree_specs()[1].active);
+
+        // Everything else is retained.
+        assert!(xml.contains(r#"<Tree activeSpec="1">"#));
+        assert!(xml.contains(r#"bandit="None""#));
+        assert!(xml.contains(r#"pantheonMajorGod="TheBrineKing""#));
+        assert!(xml.contains(r#"includeInFullDPS="true""#));
+        assert!(xml.contains("<Calcs"));
+        assert!(xml.contains("<TreeView"));
+        // Only the notes are removed.
+        let notes_start = V316_POISON_OCC.find("\t<Notes>").unwrap();
+        let notes_end = V316_POISON_OCC.find("</Notes>\n").unwrap() + "</Notes>\n".len();
+        let written_notes_start = xml.find("\t<Notes").unwrap();
+        assert_eq!(
+            xml[..written_notes_start],
+            V316_POISON_OCC[..notes_start].replace(r#"activeSpec="2""#, r#"activeSpec="1""#)
+        );
+        assert!(!xml.contains("BUILD SPECIFIC ADVICE"));
+        assert!(xml.ends_with(&V316_POISON_OCC[notes_end..]));
+    }
+
+    #[test]
+    fn add_notes() {
+        let mut pob = SerdePathOfBuilding::from_xml_retained(V322_OVERRIDES.to_owned()).unwrap();
+        pob.set_notes("Hello <World> & Friends");
+
+        let xml = pob.to_xml().unwrap();
+        assert!(xml.contains("<Notes>Hello &lt;World&gt; &amp; Friends</Notes>"));
+        let pob = SerdePathOfBuilding::from_xml(&xml).unwrap();
+        assert_eq!(pob.notes(), "Hello <World> & Friends");
+    }
+
+    #[test]
+    fn from_character() {
+        let character: crate::character::Character = serde_json::from_str(CHARACTER).unwrap();
+        let pob = SerdePathOfBuilding::from_character(&character).unwrap();
+        let pob = SerdePathOfBuilding::from_xml(&pob.to_xml().unwrap()).unwrap();
+
+        assert_eq!(92, pob.level());
+        assert_eq!(Class::Witch, pob.class());
+        assert_eq!(Some(Ascendancy::Necromancer), pob.ascendancy());
+        assert_eq!(Some("Raise Spectre"), pob.main_skill_name());
+        assert!(pob.has_tree_node(1234));
+        assert!(pob.has_tree_node(5678));
+
+        let specs = pob.tree_specs();
+        assert_eq!(specs[0].class_id, Some(3));
+        assert_eq!(specs[0].ascendancy_id, Some(3));
+        assert_eq!(specs[0].mastery_effects, &[(4444, 1)]);
+
+        let item_sets = pob.item_sets();
+        let helmet = item_sets[0].gear.helmet.unwrap();
+        assert_eq!(
+            helmet,
+            "Rarity: RARE\nHavoc Crown\nHubris Circlet\nItem Level: 84\nQuality: 20\n\
+             Sockets: B-B-B R\nImplicits: 1\n{crafted}Minions have 10% increased Movement Speed\n\
+             +90 to maximum Energy Shield\nCorrupted"
+        );
+
+        let skills = &pob.skill_sets()[0].skills;
+        assert_eq!(skills.len(), 2);
+        assert_eq!(skills[0].slot, Some("Helmet"));
+        assert_eq!(skills[0].gems.len(), 3);
+        assert_eq!(skills[0].gems[0].level, 21);
+        assert_eq!(skills[0].gems[0].quality, 20);
+        assert_eq!(
+            skills[0].gems[0].gem_id,
+            Some("Metadata/Items/Gems/SkillGemRaiseSpectre")
+        );
+        assert_eq!(
+            skills[0].gems[1].gem_id,
+            Some("Metadata/Items/Gems/SupportGemMinionDamage")
+        );
+        assert_eq!(skills[1].gems.len(), 1);
+    }
+
+    static CHARACTER: &str = r#"{
+        "name": "SpectreBoi",
+        "class": "Necromancer",
+        "level": 92,
+        "league": "Standard",
+        "equipment": [{
+            "name": "<<set:MS>><<set:M>><<set:S>>Havoc Crown",
+            "typeLine": "Hubris Circlet",
+            "baseType": "Hubris Circlet",
+            "frameType": 2,
+            "ilvl": 84,
+            "inventoryId": "Helm",
+            "corrupted": true,
+            "properties": [{ "name": "Quality", "values": [["+20%", 1]] }],
+            "sockets": [
+                { "group": 0, "sColour": "B" },
+                { "group": 0, "sColour": "B" },
+                { "group": 0, "sColour": "B" },
+                { "group": 1, "sColour": "R" }
+            ],
+            "socketedItems": [
+                {
+                    "typeLine": "Raise Spectre",
+                    "baseType": "Raise Spectre",
+                    "support": false,
+                    "socket": 0,
+                    "properties": [
+                        { "name": "Level", "values": [["21 (Max)", 0]] },
+                        { "name": "Quality", "values": [["+20%", 1]] }
+                    ]
+                },
+                { "typeLine": "Minion Damage Support", "support": true, "socket": 1 },
+                { "typeLine": "Spell Echo Support", "support": true, "socket": 2 },
+                { "typeLine": "Flesh Offering", "support": false, "socket": 3 }
+            ],
+            "enchantMods": ["Minions have 10% increased Movement Speed"],
+            "explicitMods": ["+90 to maximum Energy Shield"]
+        }],
+        "passives": {
+            "hashes": [1234, 4444],
+            "hashes_ex": [5678],
+            "mastery_effects": { "4444": 1 }
+        }
+    }"#;
 }
@@ -0,0 +1,424 @@
+use std::fmt::Write as _;
+
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+
+use crate::serde::model::*;
+
+type XmlWriter = Writer<Vec<u8>>;
+type Result = quick_xml::Result<()>;
+
+/// Rewrites the original Path of Building XML of a build.
+///
+/// The XML is copied verbatim, only the parts which can be edited through
+/// the model (the notes and the active tree spec) are replaced when they changed.
+/// Everything the model does not know about (e.g. the tree view, config,
+/// pantheon or bandit choice) is retained.
+pub(crate) fn rewrite_xml(xml: &str, pob: &PathOfBuilding) -> quick_xml::Result<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut w = Writer::new(Vec::new());
+    let mut buf = Vec::new();
+
+    let mut depth = 0usize;
+    let mut has_notes = false;
+
+    loop {
+        let event = reader.read_event(&mut buf)?;
+        match event {
+            Event::Start(ref e) if depth == 1 && e.name() == b"Notes" => {
+                has_notes = true;
+                w.write_event(&event)?;
+
+                // Notes only contain text, collect it until the closing tag.
+                let mut content = Vec::new();
+                let mut text_buf = Vec::new();
+                loop {
+                    match reader.read_event(&mut text_buf)? {
+                        Event::End(_) => break,
+                        Event::Eof => return Err(quick_xml::Error::UnexpectedEof("Notes".into())),
+                        event => content.push(event.into_owned()),
+                    }
+                    text_buf.clear();
+                }
+
+                if notes_text(&content)? == pob.notes {
+                    for event in &content {
+                        w.write_event(event)?;
+                    }
+                } else {
+                    w.write_event(Event::Text(BytesText::from_plain_str(&pob.notes)))?;
+                }
+                end(&mut w, "Notes")?;
+            }
+            Event::Empty(ref e) if depth == 1 && e.name() == b"Notes" => {
+                has_notes = true;
+                if pob.notes.is_empty() {
+                    w.write_event(&event)?;
+                } else {
+                    text_element(&mut w, "Notes", &pob.notes)?;
+                }
+            }
+            Event::Start(ref e) if depth == 1 && e.name() == b"Tree" => {
+                depth += 1;
+                w.write_event(Event::Start(rewrite_tree(e, &pob.tree)?))?;
+            }
+            Event::Start(_) => {
+                depth += 1;
+                w.write_event(&event)?;
+            }
+            Event::End(_) => {
+                if depth == 1 && !has_notes && !pob.notes.is_empty() {
+                    text_element(&mut w, "Notes", &pob.notes)?;
+                }
+                depth = depth.saturating_sub(1);
+                w.write_event(&event)?;
+            }
+            Event::Eof => break,
+            event => w.write_event(&event)?,
+        }
+        buf.clear();
+    }
+
+    // The reader only ever receives valid UTF-8.
+    Ok(String::from_utf8(w.into_inner()).expect("valid utf-8"))
+}
+
+/// The text of the notes, the same way the deserializer reads it.
+fn notes_text(content: &[Event]) -> quick_xml::Result<String> {
+    let mut text = String::new();
+    for event in content {
+        match event {
+            Event::Text(e) => text.push_str(&String::from_utf8_lossy(&e.unescaped()?)),
+            Event::CData(e) => text.push_str(&String::from_utf8_lossy(e)),
+            _ => {}
+        }
+    }
+    Ok(text.trim().to_owned())
+}
+
+/// Replaces the active spec of the `Tree` element, if it changed.
+fn rewrite_tree(e: &BytesStart, tree: &Tree) -> quick_xml::Result<BytesStart<'static>> {
+    let active_spec = tree.active_spec.to_string();
+
+    let mut changed = false;
+    let mut attributes = Vec::new();
+    for attr in e.attributes() {
+        let mut attr = attr?;
+        if attr.key == b"activeSpec" && attr.value.as_ref() != active_spec.as_bytes() {
+            attr.value = active_spec.as_bytes().to_vec().into();
+            changed = true;
+        }
+        attributes.push(attr);
+    }
+
+    if !changed {
+        return Ok(e.to_owned());
+    }
+
+    let mut elem = BytesStart::owned_name(e.name().to_vec());
+    for attr in attributes {
+        elem.push_attribute(attr);
+    }
+    Ok(elem)
+}
+
+/// Serializes a parsed build into Path of Building XML.
+///
+/// Used for builds which do not originate from PoB XML (e.g. characters),
+/// only data which is part of the model is written.
+/// The generated XML parses back into an equivalent model.
+pub(crate) fn to_xml(pob: &PathOfBuilding) -> quick_xml::Result<String> {
+    let mut w = Writer::new_with_indent(Vec::new(), b'\t', 1);
+
+    w.write_event(Event::Decl(BytesDecl::new(b"1.0", Some(b"UTF-8"), None)))?;
+    start(&mut w, BytesStart::borrowed_name(b"PathOfBuilding"))?;
+    write_build(&mut w, &pob.build)?;
+    write_skills(&mut w, &pob.skills)?;
+    write_tree(&mut w, &pob.tree)?;
+    write_items(&mut w, &pob.items)?;
+    text_element(&mut w, "Notes", &pob.notes)?;
+    write_config(&mut w, &pob.config)?;
+    end(&mut w, "PathOfBuilding")?;
+
+    // The writer only ever receives valid UTF-8.
+    Ok(String::from_utf8(w.into_inner()).expect("valid utf-8"))
+}
+
+fn write_build(w: &mut XmlWriter, build: &Build) -> Result {
+    let mut elem = BytesStart::borrowed_name(b"Build");
+    elem.push_attribute(("level", build.level.to_string().as_str()));
+    elem.push_attribute(("className", build.class_name.as_str()));
+    elem.push_attribute((
+        "ascendClassName",
+        build.ascend_class_name.map_or("None", |a| a.as_str()),
+    ));
+    elem.push_attribute((
+        "mainSocketGroup",
+        build.main_socket_group.to_string().as_str(),
+    ));
+    start(w, elem)?;
+
+    for stat in &build.stats {
+        let (name, stat) = match stat {
+            StatType::PlayerStat(stat) => ("PlayerStat", stat),
+            StatType::FullDpsSkill(stat) => ("FullDPSSkill", stat),
+            StatType::MinionStat(stat) => ("MinionStat", stat),
+            StatType::Unknown => continue,
+        };
+        let mut elem = BytesStart::borrowed_name(name.as_bytes());
+        elem.push_attribute(("stat", stat.name.as_str()));
+        elem.push_attribute(("value", stat.value.as_str()));
+        empty(w, elem)?;
+    }
+
+    end(w, "Build")
+}
+
+fn write_skills(w: &mut XmlWriter, skills: &Skills) -> Result {
+    let mut elem = BytesStart::borrowed_name(b"Skills");
+    if let Some(active_skill_set) = skills.active_skill_set {
+        elem.push_attribute(("activeSkillSet", active_skill_set.to_string().as_str()));
+    }
+    start(w, elem)?;
+
+    for skill_set in &skills.skill_sets {
+        let mut elem = BytesStart::borrowed_name(b"SkillSet");
+        elem.push_attribute(("id", skill_set.id.to_string().as_str()));
+        if let Some(title) = &skill_set.title {
+            elem.push_attribute(("title", title.as_str()));
+        }
+        start(w, elem)?;
+        for skill in &skill_set.skills {
+            write_skill(w, skill)?;
+        }
+        end(w, "SkillSet")?;
+    }
+
+    for skill in &skills.skills {
+        write_skill(w, skill)?;
+    }
+
+    end(w, "Skills")
+}
+
+fn write_skill(w: &mut XmlWriter, skill: &Skill) -> Result {
+    let mut elem = BytesStart::borrowed_name(b"Skill");
+    elem.push_attribute(("mainActiveSkill", or_nil(skill.main_active_skill).as_str()));
+    elem.push_attribute(("enabled", bool_str(skill.enabled)));
+    if let Some(label) = &skill.label {
+        elem.push_attribute(("label", label.as_str()));
+    }
+    if let Some(slot) = &skill.slot {
+        elem.push_attribute(("slot", slot.as_str()));
+    }
+    start(w, elem)?;
+
+    for gem in &skill.gems {
+        let mut elem = BytesStart::borrowed_name(b"Gem");
+        elem.push_attribute(("nameSpec", gem.name.as_str()));
+        if let Some(skill_id) = &gem.skill_id {
+            elem.push_attribute(("skillId", skill_id.as_str()));
+        }
+        if let Some(gem_id) = &gem.gem_id {
+            elem.push_attribute(("gemId", gem_id.as_str()));
+        }
+        if let Some(quality_id) = &gem.quality_id {
+            elem.push_attribute(("qualityId", quality_id.as_str()));
+        }
+        elem.push_attribute(("enabled", bool_str(gem.enabled)));
+        elem.push_attribute(("level", gem.level.to_string().as_str()));
+        elem.push_attribute(("quality", gem.quality.to_string().as_str()));
+        empty(w, elem)?;
+    }
+
+    end(w, "Skill")
+}
+
+fn write_tree(w: &mut XmlWriter, tree: &Tree) -> Result {
+    let mut elem = BytesStart::borrowed_name(b"Tree");
+    elem.push_attribute(("activeSpec", tree.active_spec.to_string().as_str()));
+    start(w, elem)?;
+
+    for spec in &tree.specs {
+        let mut elem = BytesStart::borrowed_name(b"Spec");
+        if let Some(title) = &spec.title {
+            elem.push_attribute(("title", title.as_str()));
+        }
+        if let Some(version) = &spec.version {
+            elem.push_attribute(("treeVersion", version.as_str()));
+        }
+        elem.push_attribute(("classId", or_nil(spec.class_id).as_str()));
+        elem.push_attribute(("ascendClassId", or_nil(spec.ascend_class_id).as_str()));
+        elem.push_attribute((
+            "secondaryAscendClassId",
+            or_nil(spec.secondary_ascend_class_id).as_str(),
+        ));
+        // An empty attribute does not parse as a comma separated list.
+        if !spec.nodes.is_empty() {
+            let nodes = spec
+                .nodes
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            elem.push_attribute(("nodes", nodes.as_str()));
+        }
+        if !spec.mastery_effects.is_empty() {
+            let mut mastery_effects = String::new();
+            for (i, (node, effect)) in spec.mastery_effects.iter().enumerate() {
+                if i > 0 {
+                    mastery_effects.push(',');
+                }
+                let _ = write!(mastery_effects, "{{{node},{effect}}}");
+            }
+            elem.push_attribute(("masteryEffects", mastery_effects.as_str()));
+        }
+        start(w, elem)?;
+
+        if let Some(url) = &spec.url {
+            text_element(w, "URL", url)?;
+        }
+
+        start(w, BytesStart::borrowed_name(b"Sockets"))?;
+        for socket in &spec.sockets.sockets {
+            let mut elem = BytesStart::borrowed_name(b"Socket");
+            elem.push_attribute(("nodeId", socket.node_id.to_string().as_str()));
+            elem.push_attribute(("itemId", socket.item_id.to_string().as_str()));
+            empty(w, elem)?;
+        }
+        end(w, "Sockets")?;
+
+        start(w, BytesStart::borrowed_name(b"Overrides"))?;
+        for o in &spec.overrides.overrides {
+            let mut elem = BytesStart::borrowed_name(b"Override");
+            elem.push_attribute(("dn", o.name.as_str()));
+            elem.push_attribute(("nodeId", o.node_id.to_string().as_str()));
+            start(w, elem)?;
+            w.write_event(Event::Text(BytesText::from_plain_str(&o.effect)))?;
+            end(w, "Override")?;
+        }
+        end(w, "Overrides")?;
+
+        end(w, "Spec")?;
+    }
+
+    end(w, "Tree")
+}
+
+fn write_items(w: &mut XmlWriter, items: &Items) -> Result {
+    let mut elem = BytesStart::borrowed_name(b"Items");
+    if let Some(active_item_set) = items.active_item_set {
+        elem.push_attribute(("activeItemSet", active_item_set.to_string().as_str()));
+    }
+    start(w, elem)?;
+
+    // Items are stored in a map, sort them to get a stable output.
+    let mut sorted = items.items.values().collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|item| item.id);
+    for item in sorted {
+        let mut elem = BytesStart::borrowed_name(b"Item");
+        elem.push_attribute(("id", item.id.to_string().as_str()));
+        start(w, elem)?;
+        w.write_event(Event::Text(BytesText::from_plain_str(
+            &item.content.content,
+        )))?;
+        end(w, "Item")?;
+    }
+
+    for set in &items.item_sets {
+        let mut elem = BytesStart::borrowed_name(b"ItemSet");
+        elem.push_attribute(("id", set.id.to_string().as_str()));
+        if let Some(title) = &set.title {
+            elem.push_attribute(("title", title.as_str()));
+        }
+        start(w, elem)?;
+
+        let gear = &set.gear;
+        let slots = [
+            ("Weapon 1", gear.weapon1),
+            ("Weapon 2", gear.weapon2),
+            ("Weapon 1 Swap", gear.weapon1_swap),
+            ("Weapon 2 Swap", gear.weapon2_swap),
+            ("Helmet", gear.helmet),
+            ("Body Armour", gear.body_armour),
+            ("Gloves", gear.gloves),
+            ("Boots", gear.boots),
+            ("Amulet", gear.amulet),
+            ("Ring 1", gear.ring1),
+            ("Ring 2", gear.ring2),
+            ("Belt", gear.belt),
+            ("Flask 1", gear.flask1),
+            ("Flask 2", gear.flask2),
+            ("Flask 3", gear.flask3),
+            ("Flask 4", gear.flask4),
+            ("Flask 5", gear.flask5),
+        ];
+        let slots = slots
+            .into_iter()
+            .filter_map(|(name, item_id)| Some((name, item_id?)))
+            .chain(gear.sockets.iter().map(|s| (s.name.as_str(), s.item_id)));
+        for (name, item_id) in slots {
+            let mut elem = BytesStart::borrowed_name(b"Slot");
+            elem.push_attribute(("name", name));
+            elem.push_attribute(("itemId", item_id.to_string().as_str()));
+            empty(w, elem)?;
+        }
+
+        end(w, "ItemSet")?;
+    }
+
+    end(w, "Items")
+}
+
+fn write_config(w: &mut XmlWriter, config: &Config) -> Result {
+    start(w, BytesStart::borrowed_name(b"Config"))?;
+
+    for input in &config.input {
+        let mut elem = BytesStart::borrowed_name(b"Input");
+        elem.push_attribute(("name", input.name.as_str()));
+        if let Some(value) = &input.string {
+            elem.push_attribute(("string", value.as_str()));
+        }
+        if let Some(value) = input.boolean {
+            elem.push_attribute(("boolean", bool_str(value)));
+        }
+        if let Some(value) = input.number {
+            elem.push_attribute(("number", value.to_string().as_str()));
+        }
+        empty(w, elem)?;
+    }
+
+    end(w, "Config")
+}
+
+fn start(w: &mut XmlWriter, elem: BytesStart) -> Result {
+    w.write_event(Event::Start(elem))
+}
+
+fn empty(w: &mut XmlWriter, elem: BytesStart) -> Result {
+    w.write_event(Event::Empty(elem))
+}
+
+fn end(w: &mut XmlWriter, name: &str) -> Result {
+    w.write_event(Event::End(BytesEnd::borrowed(name.as_bytes())))
+}
+
+fn text_element(w: &mut XmlWriter, name: &str, text: &str) -> Result {
+    start(w, BytesStart::borrowed_name(name.as_bytes()))?;
+    w.write_event(Event::Text(BytesText::from_plain_str(text)))?;
+    end(w, name)
+}
+
+fn or_nil(value: Option<u8>) -> String {
+    value.map_or_else(|| "nil".to_owned(), |v| v.to_string())
+}
+
+fn bool_str(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Stat {
     Armour,
@@ -41,7 +41,47 @@ pub enum Stat {
 }
 
 impl Stat {
-    fn name(&self) -> &'static str {
+    /// All known stats, excluding [`Stat::Custom`].
+    pub const ALL: &'static [Stat] = &[
+        Self::Armour,
+        Self::AttackDodgeChance,
+        Self::AverageDamage,
+        Self::BlockChance,
+        Self::ChaosResistance,
+        Self::CombinedDps,
+        Self::ColdResistance,
+        Self::CritChance,
+        Self::CritMultiplier,
+        Self::Dexterity,
+        Self::EnduranceChargesMax,
+        Self::EnergyShield,
+        Self::EnergyShieldInc,
+        Self::Evasion,
+        Self::FireResistance,
+        Self::FullDps,
+        Self::Intelligence,
+        Self::Life,
+        Self::LifeInc,
+        Self::LifeUnreserved,
+        Self::LifeUnreservedPercent,
+        Self::LightningResistance,
+        Self::HitChance,
+        Self::HitRate,
+        Self::Mana,
+        Self::ManaInc,
+        Self::ManaUnreserved,
+        Self::MeleeEvadeChance,
+        Self::PhysicalDamageReduction,
+        Self::Speed,
+        Self::SpellBlockChance,
+        Self::SpellDodgeChance,
+        Self::SpellSuppressionChance,
+        Self::Strength,
+        Self::TotalEhp,
+        Self::Ward,
+    ];
+
+    pub fn name(&self) -> &'static str {
         match self {
             Self::Armour => "Armour",
             Self::AttackDodgeChance => "AttackDodgeChance",
@@ -0,0 +1,261 @@
+//! Search queries for the official trade site.
+//!
+//! Queries are in the format expected by the trade site search API,
+//! searching for items similar to an item of a build.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{Influence, Item, Rarity};
+
+/// Filters require at least this fraction of the rolled value.
+const MIN_ROLL: f32 = 0.9;
+
+#[derive(Debug, Serialize)]
+pub struct Search<'a> {
+    pub query: Query<'a>,
+    pub sort: Sort,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Query<'a> {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    /// Base type of the item.
+    #[serde(rename = "type")]
+    pub base: &'a str,
+    pub stats: Vec<StatGroup>,
+    pub filters: BTreeMap<&'static str, Filters>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Status {
+    pub option: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatGroup {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub filters: Vec<StatFilter>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatFilter {
+    /// Trade stat id, e.g. `explicit.stat_3299347043`.
+    pub id: String,
+    pub value: StatValue,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatValue {
+    pub min: f32,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Filters {
+    pub filters: BTreeMap<&'static str, FilterOption>,
+}
+
+/// A single option of a filter, e.g. `{"option": "true"}`.
+#[derive(Debug, Serialize)]
+pub struct FilterOption {
+    pub option: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Sort {
+    pub price: &'static str,
+}
+
+/// Creates a search for items similar to `item`.
+///
+/// Uniques are searched by name, relic and Foulborn uniques only match
+/// their relic and Foulborn versions. Other items are searched by base type,
+/// influences and explicit mods with a minimum value derived from the current rolls.
+/// Crafted mods are not searched for, they can be crafted on the bought item.
+pub fn search<'a>(item: &Item<'a>) -> Search<'a> {
+    let mut filters = BTreeMap::<&'static str, Filters>::new();
+    let mut add_filter = |group: &'static str, filter: &'static str, option: &'static str| {
+        filters
+            .entry(group)
+            .or_default()
+            .filters
+            .insert(filter, FilterOption { option });
+    };
+
+    let unique = item.unique();
+
+    let rarity = match item.rarity {
+        Rarity::Unique => "unique",
+        Rarity::Relic => "uniquefoil",
+        Rarity::Rare => "rare",
+        Rarity::Magic => "magic",
+        Rarity::Normal => "normal",
+    };
+    add_filter("type_filters", "rarity", rarity);
+
+    for influence in [item.influence1, item.influence2].into_iter().flatten() {
+        if let Some(filter) = influence_filter(influence) {
+            add_filter("misc_filters", filter, "true");
+        }
+    }
+
+    if unique.is_some_and(|unique| unique.foulborn) {
+        add_filter("misc_filters", "mutated", "true");
+    }
+
+    let stats = match item.rarity {
+        Rarity::Rare | Rarity::Magic => stat_filters(item),
+        _ => Vec::new(),
+    };
+
+    Search {
+        query: Query {
+            status: Status { option: "online" },
+            name: unique.map(|unique| unique.name),
+            base: item.base,
+            stats: vec![StatGroup {
+                kind: "and",
+                filters: stats,
+            }],
+            filters,
+        },
+        sort: Sort { price: "asc" },
+    }
+}
+
+fn stat_filters(item: &Item) -> Vec<StatFilter> {
+    let mut filters = Vec::new();
+
+    for m in item.explicits().filter(|m| !m.crafted) {
+        let Some(stat_id) = poe_data::trade::stat_id(&m.template()) else {
+            continue;
+        };
+
+        // Mods with multiple values, like added damage, are searched by their average.
+        let values = m.values();
+        if values.is_empty() {
+            continue;
+        }
+        let value = values.iter().map(|v| v.value).sum::<f32>() / values.len() as f32;
+
+        let kind = match m.fractured {
+            true => "fractured",
+            false => "explicit",
+        };
+
+        filters.push(StatFilter {
+            id: format!("{kind}.{stat_id}"),
+            value: StatValue {
+                min: (value * MIN_ROLL).floor(),
+            },
+        });
+    }
+
+    filters
+}
+
+fn influence_filter(influence: Influence) -> Option<&'static str> {
+    let filter = match influence {
+        Influence::Shaper => "shaper_item",
+        Influence::Elder => "elder_item",
+        Influence::Crusader => "crusader_item",
+        Influence::Hunter => "hunter_item",
+        Influence::Redeemer => "redeemer_item",
+        Influence::Warlord => "warlord_item",
+        Influence::Synthesis => "synthesised_item",
+        Influence::Fracture => "fractured_item",
+        // Eldritch influences are implicits, not item properties.
+        Influence::SearingExarch | Influence::EaterOfWorlds => return None,
+    };
+
+    Some(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_rare() {
+        let item = Item::parse(
+            r#"Rarity: RARE
+Doom Stride
+Titan Greaves
+Hunter Item
+Implicits: 0
++150 to maximum Life
+{crafted}+(80-89) to maximum Life
+{fractured}+35% to Chaos Resistance
+Adds 10 to 20 Physical Damage to Attacks
+Unknown mod"#,
+        )
+        .unwrap();
+
+        let search = serde_json::to_value(search(&item)).unwrap();
+        assert_eq!(
+            search,
+            serde_json::json!({
+                "query": {
+                    "status": {"option": "online"},
+                    "type": "Titan Greaves",
+                    "stats": [{
+                        "type": "and",
+                        "filters": [
+                            {"id": "explicit.stat_3299347043", "value": {"min": 135.0}},
+                            {"id": "fractured.stat_2923486259", "value": {"min": 31.0}},
+                            {"id": "explicit.stat_3032590688", "value": {"min": 13.0}},
+                        ],
+                    }],
+                    "filters": {
+                        "misc_filters": {"filters": {"hunter_item": {"option": "true"}}},
+                        "type_filters": {"filters": {"rarity": {"option": "rare"}}},
+                    },
+                },
+                "sort": {"price": "asc"},
+            })
+        );
+    }
+
+    #[test]
+    fn search_unique() {
+        let item = Item::parse(
+            r#"Rarity: UNIQUE
+Endgame - Mageblood
+Heavy Belt
+Implicits: 1
++30 to Strength
++50 to Dexterity"#,
+        )
+        .unwrap();
+
+        let search = search(&item);
+        assert_eq!(search.query.name, Some("Mageblood"));
+        assert_eq!(search.query.base, "Heavy Belt");
+        assert!(search.query.stats[0].filters.is_empty());
+    }
+
+    #[test]
+    fn search_foulborn_relic() {
+        let item = Item::parse(
+            r#"Rarity: RELIC
+Foulborn Mageblood
+Heavy Belt
+Implicits: 0"#,
+        )
+        .unwrap();
+
+        let search = serde_json::to_value(search(&item)).unwrap();
+        assert_eq!(search["query"]["name"], "Mageblood");
+        assert_eq!(
+            search["query"]["filters"],
+            serde_json::json!({
+                "misc_filters": {"filters": {"mutated": {"option": "true"}}},
+                "type_filters": {"filters": {"rarity": {"option": "uniquefoil"}}},
+            })
+        );
+    }
+}
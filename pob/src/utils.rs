@@ -1,6 +1,6 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
-use flate2::bufread::ZlibDecoder;
+use flate2::{bufread::ZlibDecoder, write::ZlibEncoder, Compression};
 
 use crate::{Error, Result};
 
@@ -9,10 +9,28 @@ pub fn decompress(data: &str) -> Result<String> {
     deflate(&data)
 }
 
+/// Compresses and encodes Path of Building XML into an export code.
+///
+/// The inverse of [`decompress`].
+pub fn compress(data: &str) -> Result<String> {
+    let data = zlib(data.as_bytes())?;
+    Ok(encode(&data))
+}
+
 fn decode(data: &str) -> Result<Vec<u8>> {
     base64::decode_config(data.trim(), base64::URL_SAFE).map_err(Error::Base64Decode)
 }
 
+fn encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE)
+}
+
+fn zlib(inp: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(inp).map_err(Error::Compress)?;
+    encoder.finish().map_err(Error::Compress)
+}
+
 fn deflate(inp: &[u8]) -> Result<String> {
     let mut deflater = ZlibDecoder::new(inp);
     let mut buf = Vec::new();
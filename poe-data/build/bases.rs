@@ -0,0 +1,82 @@
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Base {
+    name: String,
+    item_class: String,
+    slot: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    implicits: Vec<String>,
+    requirements: Requirements,
+    #[serde(default)]
+    sockets: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct Requirements {
+    level: u8,
+    str: u16,
+    dex: u16,
+    int: u16,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("base_items.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Base> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+
+    writeln!(output, "use super::{{Base, Requirements, Slot}};")?;
+
+    for base in data {
+        let slot = match base.slot.as_str() {
+            "helmet" => "Slot::Helmet",
+            "body_armour" => "Slot::BodyArmour",
+            "gloves" => "Slot::Gloves",
+            "boots" => "Slot::Boots",
+            "belt" => "Slot::Belt",
+            "amulet" => "Slot::Amulet",
+            "ring" => "Slot::Ring",
+            "one_hand_weapon" => "Slot::OneHandWeapon",
+            "two_hand_weapon" => "Slot::TwoHandWeapon",
+            "shield" => "Slot::Shield",
+            "quiver" => "Slot::Quiver",
+            "flask" => "Slot::Flask",
+            "jewel" => "Slot::Jewel",
+            _ => anyhow::bail!("invalid slot '{}' for base '{}'", base.slot, base.name),
+        };
+
+        let Requirements {
+            level,
+            str,
+            dex,
+            int,
+        } = base.requirements;
+        let requirements =
+            format!("Requirements {{ level: {level}, str: {str}, dex: {dex}, int: {int} }}");
+
+        let name = base.name;
+        let item_class = base.item_class;
+        let tags = base.tags;
+        let implicits = base.implicits;
+        let sockets = base.sockets;
+        let value = format!(
+            "Base {{ name: {name:?}, item_class: {item_class:?}, slot: {slot}, tags: &{tags:?}, implicits: &{implicits:?}, requirements: {requirements}, sockets: {sockets} }}"
+        );
+        map.entry(name, &value);
+    }
+
+    writeln!(
+        output,
+        "pub static BASES: phf::Map<&'static str, Base> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
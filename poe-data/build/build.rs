@@ -1,10 +1,20 @@
 use std::{env, fs::File, path::Path};
 
+mod bases;
 mod gems;
+mod mods;
+mod trade;
+mod uniques;
 
 pub fn main() -> anyhow::Result<()> {
-    let gems_path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("gems.rs");
-    gems::generate(&mut File::create(gems_path)?)?;
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    gems::generate(&mut File::create(out_dir.join("gems.rs"))?)?;
+    bases::generate(&mut File::create(out_dir.join("bases.rs"))?)?;
+    uniques::generate(&mut File::create(out_dir.join("uniques.rs"))?)?;
+    mods::generate(&mut File::create(out_dir.join("mods.rs"))?)?;
+    trade::generate(&mut File::create(out_dir.join("trade.rs"))?)?;
 
     Ok(())
 }
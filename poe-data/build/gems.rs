@@ -12,6 +12,10 @@ struct Gem {
     color: String,
     #[serde(default)]
     vendors: Vec<Vendor>,
+    #[serde(default)]
+    skill_types: Vec<String>,
+    #[serde(default)]
+    support_types: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -29,6 +33,7 @@ pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
     let data: Vec<Gem> = serde_json::from_reader(data)?;
 
     let mut map = phf_codegen::Map::new();
+    let mut by_name = phf_codegen::Map::new();
 
     writeln!(output, "use super::{{Gem, Vendor}};")?;
     writeln!(output, "use shared::{{Color, ClassSet}};")?;
@@ -64,11 +69,17 @@ pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
         }
         write!(vendors, "]")?;
 
+        let id = gem.id;
         let name = gem.name;
         let level = gem.level;
-        let value =
-            format!("Gem {{ name: {name:?}, color: {color}, level: {level}, vendors: {vendors} }}");
-        map.entry(gem.id, &value);
+        let skill_types = gem.skill_types;
+        let support_types = gem.support_types;
+        let value = format!(
+            "Gem {{ id: {id:?}, name: {name:?}, color: {color}, level: {level}, vendors: {vendors}, \
+             skill_types: &{skill_types:?}, support_types: &{support_types:?} }}"
+        );
+        by_name.entry(name, &format!("{id:?}"));
+        map.entry(id, &value);
     }
 
     writeln!(
@@ -76,6 +87,11 @@ pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
         "pub static GEMS: phf::Map<&'static str, Gem> = {};",
         map.build()
     )?;
+    writeln!(
+        output,
+        "pub static GEMS_BY_NAME: phf::Map<&'static str, &'static str> = {};",
+        by_name.build()
+    )?;
 
     Ok(())
 }
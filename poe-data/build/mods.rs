@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Mod {
+    id: String,
+    name: String,
+    group: String,
+    generation_type: String,
+    required_level: u8,
+    domain: String,
+    #[serde(default)]
+    spawn_weights: Vec<SpawnWeight>,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpawnWeight {
+    tag: String,
+    weight: u32,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("mods.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Mod> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+    let mut by_group = BTreeMap::<&str, Vec<(u8, &str)>>::new();
+    let mut by_text = BTreeMap::<String, Vec<(u8, &str)>>::new();
+
+    writeln!(output, "use super::{{Domain, GenerationType, Mod}};")?;
+
+    for m in &data {
+        let generation_type = match m.generation_type.as_str() {
+            "prefix" => "GenerationType::Prefix",
+            "suffix" => "GenerationType::Suffix",
+            _ => anyhow::bail!(
+                "invalid generation type '{}' for mod '{}'",
+                m.generation_type,
+                m.id
+            ),
+        };
+
+        let domain = match m.domain.as_str() {
+            "item" => "Domain::Item",
+            "flask" => "Domain::Flask",
+            "abyss_jewel" => "Domain::AbyssJewel",
+            "misc" => "Domain::Misc",
+            _ => anyhow::bail!("invalid domain '{}' for mod '{}'", m.domain, m.id),
+        };
+
+        let spawn_weights = m
+            .spawn_weights
+            .iter()
+            .map(|w| format!("({:?}, {})", w.tag, w.weight))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let (text, stats) = parse_text(&m.text);
+        let stats = stats
+            .iter()
+            .map(|(min, max)| format!("({min:?}, {max:?})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let value = format!(
+            "Mod {{ id: {:?}, name: {:?}, group: {:?}, generation_type: {generation_type}, required_level: {}, domain: {domain}, spawn_weights: &[{spawn_weights}], text: {text:?}, stats: &[{stats}] }}",
+            m.id, m.name, m.group, m.required_level
+        );
+        map.entry(m.id.as_str(), &value);
+
+        by_group
+            .entry(&m.group)
+            .or_default()
+            .push((m.required_level, &m.id));
+        by_text
+            .entry(text)
+            .or_default()
+            .push((m.required_level, &m.id));
+    }
+
+    writeln!(
+        output,
+        "pub static MODS: phf::Map<&'static str, Mod> = {};",
+        map.build()
+    )?;
+
+    // Both indices list the highest level mods first, the order tiers are ranked in.
+    write_index(output, "MODS_BY_GROUP", by_group)?;
+    write_index(output, "MODS_BY_TEXT", by_text)?;
+
+    Ok(())
+}
+
+fn write_index<K: AsRef<str>>(
+    output: &mut dyn std::io::Write,
+    name: &str,
+    mut index: BTreeMap<K, Vec<(u8, &str)>>,
+) -> anyhow::Result<()> {
+    let mut map = phf_codegen::Map::new();
+    for (key, mods) in &mut index {
+        mods.sort_by(|a, b| b.cmp(a));
+        let ids = mods.iter().map(|(_, id)| id).collect::<Vec<_>>();
+        map.entry(key.as_ref(), &format!("&{ids:?}"));
+    }
+    writeln!(
+        output,
+        "pub static {name}: phf::Map<&'static str, &'static [&'static str]> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
+
+/// Replaces every value and range in the mod text with `#`, the same way `pob::Mod::template` does.
+///
+/// Signs are part of the value, `+(3-9) to maximum Life` becomes `# to maximum Life`.
+fn parse_text(text: &str) -> (String, Vec<(f32, f32)>) {
+    let mut template = String::with_capacity(text.len());
+    let mut stats = Vec::new();
+
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let at_word_start = match template.chars().next_back() {
+            Some(prev) => prev.is_whitespace() || prev == '(',
+            None => true,
+        };
+
+        let (sign, unsigned) = match rest.strip_prefix(['+', '-']) {
+            Some(unsigned) if at_word_start => (if c == '-' { -1.0 } else { 1.0 }, unsigned),
+            _ => (1.0, rest),
+        };
+
+        if let Some((min, max, len)) = parse_range(unsigned) {
+            stats.push((sign * min, sign * max));
+            template.push('#');
+            rest = &unsigned[len..];
+        } else if let Some((value, len)) = parse_number(unsigned).filter(|_| at_word_start) {
+            stats.push((sign * value, sign * value));
+            template.push('#');
+            rest = &unsigned[len..];
+        } else {
+            template.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    (template, stats)
+}
+
+fn parse_range(s: &str) -> Option<(f32, f32, usize)> {
+    let inner = s.strip_prefix('(')?;
+    let (min, min_len) = parse_number(inner)?;
+    let inner = inner[min_len..].strip_prefix('-')?;
+    let (max, max_len) = parse_number(inner)?;
+    inner[max_len..].strip_prefix(')')?;
+
+    Some((min, max, 1 + min_len + 1 + max_len + 1))
+}
+
+fn parse_number(s: &str) -> Option<(f32, usize)> {
+    let len = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let number = s[..len].trim_end_matches('.');
+    let value = number.parse().ok()?;
+    Some((value, number.len()))
+}
@@ -0,0 +1,29 @@
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Stat {
+    id: String,
+    text: String,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("trade_stats.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Stat> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+    for stat in &data {
+        map.entry(stat.text.as_str(), &format!("{:?}", stat.id));
+    }
+
+    writeln!(
+        output,
+        "pub static STATS: phf::Map<&'static str, &'static str> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use std::{fs::File, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Unique {
+    name: String,
+    base: String,
+    #[serde(default)]
+    variants: Vec<String>,
+}
+
+pub fn generate(output: &mut dyn std::io::Write) -> anyhow::Result<()> {
+    let path = Path::new("data").join("uniques.json");
+
+    let data = File::open(path)?;
+    let data: Vec<Unique> = serde_json::from_reader(data)?;
+
+    let mut map = phf_codegen::Map::new();
+
+    writeln!(output, "use super::Unique;")?;
+
+    for unique in data {
+        let name = unique.name;
+        let base = unique.base;
+        let variants = unique.variants;
+        let value = format!("Unique {{ name: {name:?}, base: {base:?}, variants: &{variants:?} }}");
+        map.entry(name, &value);
+    }
+
+    writeln!(
+        output,
+        "pub static UNIQUES: phf::Map<&'static str, Unique> = {};",
+        map.build()
+    )?;
+
+    Ok(())
+}
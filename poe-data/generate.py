@@ -0,0 +1,348 @@
+#!/usr/bin/env python3
+"""Generates the item data files in `data/` from a RePoE and a Path of Building checkout
+and the stats of the trade site.
+
+Usage:
+
+    curl -A "pobb.in" https://www.pathofexile.com/api/trade/data/stats > stats.json
+    ./generate.py <repoe>/data <pob>/src stats.json
+
+* `base_items.json` and `mods.json` are generated from the RePoE export
+  (https://github.com/repoe-fork/repoe), using `base_items.json`, `mods.json`
+  and `stat_translations.json`.
+* `uniques.json` is generated from the unique item lists of Path of Building
+  (https://github.com/PathOfBuildingCommunity/PathOfBuilding), `Data/Uniques/*.lua`.
+* The skill types of the gems in `gems.json` are generated from the RePoE export,
+  using `gems.json`. Other fields of the gems are kept.
+* `trade_stats.json` is generated from the explicit stats of the trade site,
+  `/api/trade/data/stats`.
+
+The output is sorted and written one entry per line, regenerating from the same
+inputs produces the same files.
+"""
+
+import json
+import re
+import sys
+from pathlib import Path
+
+OUTPUT = Path(__file__).parent / "data"
+
+SLOTS = {
+    "Amulet": "amulet",
+    "Ring": "ring",
+    "Belt": "belt",
+    "Helmet": "helmet",
+    "Body Armour": "body_armour",
+    "Gloves": "gloves",
+    "Boots": "boots",
+    "Shield": "shield",
+    "Quiver": "quiver",
+    "Life Flask": "flask",
+    "Mana Flask": "flask",
+    "Hybrid Flask": "flask",
+    "Utility Flask": "flask",
+    "Jewel": "jewel",
+    "Abyss Jewel": "jewel",
+    "Claw": "one_hand_weapon",
+    "Dagger": "one_hand_weapon",
+    "Rune Dagger": "one_hand_weapon",
+    "One Hand Sword": "one_hand_weapon",
+    "Thrusting One Hand Sword": "one_hand_weapon",
+    "One Hand Axe": "one_hand_weapon",
+    "One Hand Mace": "one_hand_weapon",
+    "Sceptre": "one_hand_weapon",
+    "Wand": "one_hand_weapon",
+    "Bow": "two_hand_weapon",
+    "Staff": "two_hand_weapon",
+    "Warstaff": "two_hand_weapon",
+    "Two Hand Sword": "two_hand_weapon",
+    "Two Hand Axe": "two_hand_weapon",
+    "Two Hand Mace": "two_hand_weapon",
+}
+
+# Maximum number of sockets per slot, bases of other slots have no sockets.
+SOCKETS = {
+    "body_armour": 6,
+    "two_hand_weapon": 6,
+    "helmet": 4,
+    "gloves": 4,
+    "boots": 4,
+    "one_hand_weapon": 3,
+    "shield": 3,
+}
+
+# Domains of mods which can roll on the bases in `SLOTS`.
+MOD_DOMAINS = {"item", "flask", "abyss_jewel", "misc"}
+
+
+def load(path):
+    with open(path, encoding="utf-8") as f:
+        return json.load(f)
+
+
+def write(name, entries):
+    with open(OUTPUT / name, "w", encoding="utf-8") as f:
+        f.write("[\n")
+        f.write(",\n".join(json.dumps(entry, ensure_ascii=False) for entry in entries))
+        f.write("\n]\n")
+
+
+class Translations:
+    """Renders stats of mods into the text shown on items, e.g. `+(3-9) to maximum Life`."""
+
+    def __init__(self, translations):
+        self.by_stat = {}
+        for translation in translations:
+            for stat in translation["ids"]:
+                self.by_stat.setdefault(stat, translation)
+
+    def render(self, stats):
+        values = {stat["id"]: (stat["min"], stat["max"]) for stat in stats}
+        lines = []
+        seen = set()
+        for stat in stats:
+            translation = self.by_stat.get(stat["id"])
+            if translation is None or id(translation) in seen:
+                continue
+            seen.add(id(translation))
+
+            ranges = [values.get(stat, (0, 0)) for stat in translation["ids"]]
+            line = self._render(translation["English"], ranges)
+            if line:
+                lines.append(line)
+        return lines
+
+    def _render(self, variants, ranges):
+        for variant in variants:
+            if not all(_matches(c, r[1]) for c, r in zip(variant["condition"], ranges)):
+                continue
+
+            string = variant["string"]
+            for index, (fmt, handlers, (lo, hi)) in enumerate(
+                zip(variant["format"], variant["index_handlers"], ranges)
+            ):
+                if fmt == "ignore":
+                    continue
+                lo, hi = _handle(handlers, lo), _handle(handlers, hi)
+                value = _number(lo) if lo == hi else f"({_number(lo)}-{_number(hi)})"
+                if fmt.startswith("+") and lo >= 0:
+                    value = "+" + value
+                string = string.replace(f"{{{index}}}", value)
+            return string
+        return None
+
+
+def _matches(condition, value):
+    if condition.get("negated"):
+        return not _matches({k: v for k, v in condition.items() if k != "negated"}, value)
+    return condition.get("min", value) <= value <= condition.get("max", value)
+
+
+HANDLERS = {
+    "negate": lambda v: -v,
+    "negate_and_double": lambda v: -2 * v,
+    "double": lambda v: 2 * v,
+    "divide_by_two_0dp": lambda v: int(v / 2),
+    "divide_by_ten_0dp": lambda v: int(v / 10),
+    "divide_by_one_hundred": lambda v: v / 100,
+    "divide_by_one_hundred_2dp": lambda v: round(v / 100, 2),
+    "per_minute_to_per_second": lambda v: round(v / 60, 1),
+    "per_minute_to_per_second_0dp": lambda v: int(v / 60),
+    "per_minute_to_per_second_1dp": lambda v: round(v / 60, 1),
+    "per_minute_to_per_second_2dp": lambda v: round(v / 60, 2),
+    "milliseconds_to_seconds": lambda v: v / 1000,
+    "milliseconds_to_seconds_0dp": lambda v: int(v / 1000),
+    "milliseconds_to_seconds_1dp": lambda v: round(v / 1000, 1),
+    "milliseconds_to_seconds_2dp": lambda v: round(v / 1000, 2),
+    "deciseconds_to_seconds": lambda v: v / 10,
+    "60%_of_value": lambda v: v * 0.6,
+}
+
+
+def _handle(handlers, value):
+    for handler in handlers:
+        value = HANDLERS.get(handler, lambda v: v)(value)
+    return value
+
+
+def _number(value):
+    if float(value).is_integer():
+        return str(int(value))
+    return f"{value:g}"
+
+
+def generate_bases(repoe, translations, mods):
+    bases = []
+    for base in load(repoe / "base_items.json").values():
+        slot = SLOTS.get(base["item_class"])
+        if slot is None or base["release_state"] != "released":
+            continue
+
+        implicits = []
+        for implicit in base["implicits"]:
+            if implicit in mods:
+                implicits.extend(translations.render(mods[implicit]["stats"]))
+
+        requirements = base.get("requirements") or {}
+        sockets = SOCKETS.get(slot, 0)
+        if base["name"] == "Unset Ring":
+            sockets = 1
+
+        bases.append(
+            {
+                "name": base["name"],
+                "item_class": base["item_class"],
+                "slot": slot,
+                "tags": base["tags"],
+                "implicits": implicits,
+                "requirements": {
+                    "level": requirements.get("level", 1),
+                    "str": requirements.get("strength", 0),
+                    "dex": requirements.get("dexterity", 0),
+                    "int": requirements.get("intelligence", 0),
+                },
+                "sockets": sockets,
+            }
+        )
+
+    bases.sort(key=lambda b: (b["slot"], b["item_class"], b["requirements"]["level"], b["name"]))
+    return bases
+
+
+def generate_mods(translations, mods):
+    result = []
+    for mod_id, mod in mods.items():
+        if mod["domain"] not in MOD_DOMAINS or mod["generation_type"] not in ("prefix", "suffix"):
+            continue
+        # Mods without spawn weights are only added by crafting, essences and similar.
+        if not any(weight["weight"] > 0 for weight in mod["spawn_weights"]):
+            continue
+        if not mod["groups"] or not mod["stats"]:
+            continue
+
+        text = translations.render(mod["stats"])
+        if not text:
+            continue
+
+        result.append(
+            {
+                "id": mod_id,
+                "name": mod["name"],
+                "group": mod["groups"][0],
+                "generation_type": mod["generation_type"],
+                "required_level": mod["required_level"],
+                "domain": mod["domain"],
+                "spawn_weights": mod["spawn_weights"],
+                "text": "\n".join(text),
+            }
+        )
+
+    result.sort(key=lambda m: (m["group"], m["generation_type"], m["required_level"], m["id"]))
+    return result
+
+
+UNIQUE = re.compile(r"\[\[\n(.*?)\]\]", re.DOTALL)
+
+
+def generate_uniques(pob):
+    uniques = {}
+    for path in sorted((pob / "Data" / "Uniques").glob("*.lua")):
+        for block in UNIQUE.findall(path.read_text(encoding="utf-8")):
+            lines = [line.strip() for line in block.splitlines() if line.strip()]
+            if len(lines) < 2:
+                continue
+
+            name, base = lines[0], lines[1]
+            variants = [
+                line.removeprefix("Variant:").strip()
+                for line in lines[2:]
+                if line.startswith("Variant:")
+            ]
+            uniques.setdefault(name, {"name": name, "base": base, "variants": variants})
+
+    return sorted(uniques.values(), key=lambda u: u["name"])
+
+
+# Operators in the allowed types of a support, supports using them are not modelled.
+TYPE_OPERATORS = {"AND", "OR", "NOT"}
+
+
+def generate_gems(repoe):
+    by_gem_id = {}
+    for gem in load(repoe / "gems.json").values():
+        base_item = gem.get("base_item")
+        if base_item:
+            by_gem_id.setdefault(base_item["id"], gem)
+
+    gems = load(OUTPUT / "gems.json")
+    for gem in gems:
+        gem.pop("skill_types", None)
+        gem.pop("support_types", None)
+
+        data = by_gem_id.get(gem["id"])
+        if data is None:
+            continue
+
+        active_skill = data.get("active_skill")
+        if active_skill and active_skill.get("types"):
+            gem["skill_types"] = sorted(active_skill["types"])
+
+        support_gem = data.get("support_gem")
+        if support_gem:
+            allowed = support_gem.get("allowed_types") or []
+            if allowed and not TYPE_OPERATORS.intersection(allowed):
+                gem["support_types"] = sorted(allowed)
+
+    return gems
+
+
+# Trade texts of local mods are suffixed, the suffix is not part of the item text.
+LOCAL = " (Local)"
+
+
+def generate_trade_stats(stats):
+    explicit = [
+        entry
+        for group in stats["result"]
+        for entry in group["entries"]
+        if entry["type"] == "explicit"
+    ]
+
+    # Item texts are templated without signs, e.g. `# to maximum Life` for `+# to maximum Life`.
+    def text(entry):
+        return re.sub(r"[+-]#", "#", entry["text"]).removesuffix(LOCAL)
+
+    # Global stats are preferred, local stats are used if there is no global stat with the same text.
+    result = {}
+    for local in (False, True):
+        for entry in explicit:
+            if entry["text"].endswith(LOCAL) == local:
+                stat_id = entry["id"].removeprefix("explicit.")
+                result.setdefault(text(entry), {"id": stat_id, "text": text(entry)})
+
+    return sorted(result.values(), key=lambda s: s["text"])
+
+
+def main():
+    if len(sys.argv) != 4:
+        print(__doc__, file=sys.stderr)
+        sys.exit(1)
+
+    repoe, pob, stats = Path(sys.argv[1]), Path(sys.argv[2]), Path(sys.argv[3])
+
+    translations = Translations(load(repoe / "stat_translations.json"))
+    mods = load(repoe / "mods.json")
+
+    write("base_items.json", generate_bases(repoe, translations, mods))
+    write("mods.json", generate_mods(translations, mods))
+    write("uniques.json", generate_uniques(pob))
+    write("trade_stats.json", generate_trade_stats(load(stats)))
+
+    # Unlike the other files `gems.json` is written compact on a single line.
+    with open(OUTPUT / "gems.json", "w", encoding="utf-8") as f:
+        json.dump(generate_gems(repoe), f, separators=(",", ":"))
+
+
+if __name__ == "__main__":
+    main()
@@ -1,11 +1,21 @@
+pub mod planner;
+
 pub mod gems {
     use shared::{Class, ClassSet, Color};
 
+    #[derive(Debug)]
     pub struct Gem {
+        pub id: &'static str,
         pub name: &'static str,
         pub color: Color,
         pub level: u8,
         pub vendors: &'static [Vendor],
+        /// Skill types of an active skill gem, e.g. `Attack`.
+        pub skill_types: &'static [&'static str],
+        /// Skill types of which a skill needs at least one to be supported by a support gem.
+        ///
+        /// Empty if the requirement is not known.
+        pub support_types: &'static [&'static str],
     }
 
     impl Gem {
@@ -16,6 +26,7 @@ pub mod gems {
         }
     }
 
+    #[derive(Debug)]
     pub struct Vendor {
         pub act: u8,
         pub npc: &'static str,
@@ -27,7 +38,334 @@ pub mod gems {
         data::GEMS.get(id)
     }
 
+    /// Finds a gem by the name shown in game, e.g. `Added Fire Damage Support`.
+    pub fn by_name(name: &str) -> Option<&'static Gem> {
+        data::GEMS_BY_NAME.get(name).and_then(|id| by_id(id))
+    }
+
     mod data {
         include!(concat!(env!("OUT_DIR"), "/gems.rs"));
     }
 }
+
+pub mod bases {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Slot {
+        Helmet,
+        BodyArmour,
+        Gloves,
+        Boots,
+        Belt,
+        Amulet,
+        Ring,
+        OneHandWeapon,
+        TwoHandWeapon,
+        Shield,
+        Quiver,
+        Flask,
+        Jewel,
+    }
+
+    #[derive(Debug)]
+    pub struct Base {
+        pub name: &'static str,
+        pub item_class: &'static str,
+        pub slot: Slot,
+        /// Spawn tags, e.g. `ring` or `armour`, see [`crate::mods::Mod::spawns_on`].
+        pub tags: &'static [&'static str],
+        pub implicits: &'static [&'static str],
+        pub requirements: Requirements,
+        /// Maximum amount of sockets.
+        pub sockets: u8,
+    }
+
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct Requirements {
+        pub level: u8,
+        pub str: u16,
+        pub dex: u16,
+        pub int: u16,
+    }
+
+    pub fn by_name(name: &str) -> Option<&'static Base> {
+        data::BASES.get(name)
+    }
+
+    /// Finds the base of a magic item by its name, e.g. `Athlete's Coral Ring of the Whelpling`.
+    ///
+    /// Affix names are not known, the longest base name contained in the name wins.
+    pub fn find_magic_base(name: &str) -> Option<&'static Base> {
+        let words = name.split_whitespace().collect::<Vec<_>>();
+
+        for len in (1..=words.len()).rev() {
+            let base = words
+                .windows(len)
+                .find_map(|window| by_name(&window.join(" ")));
+
+            if base.is_some() {
+                return base;
+            }
+        }
+
+        None
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/bases.rs"));
+    }
+}
+
+pub mod uniques {
+    use crate::bases::{self, Base};
+
+    #[derive(Debug)]
+    pub struct Unique {
+        pub name: &'static str,
+        pub base: &'static str,
+        /// Variant names as used by PoB, empty if the unique has no variants.
+        pub variants: &'static [&'static str],
+    }
+
+    impl Unique {
+        pub fn base(&self) -> Option<&'static Base> {
+            bases::by_name(self.base)
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<&'static Unique> {
+        data::UNIQUES.get(name)
+    }
+
+    pub fn all() -> impl Iterator<Item = &'static Unique> {
+        data::UNIQUES.values()
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/uniques.rs"));
+    }
+}
+
+pub mod mods {
+    use crate::bases::{Base, Slot};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GenerationType {
+        Prefix,
+        Suffix,
+    }
+
+    /// Kind of items a mod can roll on.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Domain {
+        Item,
+        Flask,
+        AbyssJewel,
+        /// Jewels, except abyss jewels.
+        Misc,
+    }
+
+    impl Domain {
+        fn of(base: &Base) -> Self {
+            match base.slot {
+                Slot::Flask => Self::Flask,
+                Slot::Jewel if base.item_class == "Abyss Jewel" => Self::AbyssJewel,
+                Slot::Jewel => Self::Misc,
+                _ => Self::Item,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Mod {
+        /// Mod id, as used by PoB for crafted `Prefix` and `Suffix` lines.
+        pub id: &'static str,
+        /// Affix name, e.g. `Hale` or `of the Whelpling`.
+        pub name: &'static str,
+        pub group: &'static str,
+        pub generation_type: GenerationType,
+        /// Minimum item level to roll the mod.
+        pub required_level: u8,
+        pub domain: Domain,
+        /// Spawn weights by base tag, the first tag of the base in the list applies.
+        pub spawn_weights: &'static [(&'static str, u32)],
+        /// Mod text with every value replaced by `#`, see `pob::Mod::template`.
+        pub text: &'static str,
+        /// Minimum and maximum for every value of the text.
+        pub stats: &'static [(f32, f32)],
+    }
+
+    impl Mod {
+        /// Whether the rolled values are within the ranges of the mod.
+        pub fn matches(&self, values: &[f32]) -> bool {
+            self.stats.len() == values.len()
+                && self
+                    .stats
+                    .iter()
+                    .zip(values)
+                    .all(|(&(min, max), &value)| min <= value && value <= max)
+        }
+
+        /// Whether the mod can roll on the base.
+        pub fn spawns_on(&self, base: &Base) -> bool {
+            let weight = self
+                .spawn_weights
+                .iter()
+                .find(|(tag, _)| base.tags.contains(tag))
+                .map(|&(_, weight)| weight);
+
+            self.domain == Domain::of(base) && weight.unwrap_or(0) > 0
+        }
+
+        /// Tier of the mod on the base, `1` is the best tier.
+        ///
+        /// Tiers are ranked within the group and generation type,
+        /// only counting mods which can roll on the base.
+        pub fn tier(&self, base: &Base) -> Option<u8> {
+            if !self.spawns_on(base) {
+                return None;
+            }
+
+            let position = by_group(self.group)
+                .filter(|m| m.generation_type == self.generation_type && m.spawns_on(base))
+                .position(|m| m.id == self.id)?;
+
+            u8::try_from(position + 1).ok()
+        }
+    }
+
+    pub fn by_id(id: &str) -> Option<&'static Mod> {
+        data::MODS.get(id)
+    }
+
+    /// All mods of the group, highest level first.
+    pub fn by_group(group: &str) -> impl Iterator<Item = &'static Mod> {
+        data::MODS_BY_GROUP
+            .get(group)
+            .copied()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| by_id(id))
+    }
+
+    /// All mods with the templated text, highest level first.
+    pub fn by_text(text: &str) -> impl Iterator<Item = &'static Mod> {
+        data::MODS_BY_TEXT
+            .get(text)
+            .copied()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| by_id(id))
+    }
+
+    /// Finds the mod which rolled the `values` for the templated text on the base.
+    pub fn find(base: &Base, text: &str, values: &[f32]) -> Option<&'static Mod> {
+        by_text(text).find(|m| m.spawns_on(base) && m.matches(values))
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/mods.rs"));
+    }
+}
+
+pub mod trade {
+    /// Trade site stat id of a templated mod text, see [`crate::mods::Mod::text`].
+    ///
+    /// The id does not include the mod type, e.g. `stat_3299347043`
+    /// needs to be prefixed with `explicit.` or `implicit.`.
+    pub fn stat_id(text: &str) -> Option<&'static str> {
+        data::STATS.get(text).copied()
+    }
+
+    mod data {
+        include!(concat!(env!("OUT_DIR"), "/trade.rs"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gem_by_name() {
+        let gem = gems::by_name("Added Fire Damage Support").unwrap();
+        assert_eq!(gem.id, "Metadata/Items/Gems/SupportGemAddedFireDamage");
+        assert!(gems::by_name("Not A Gem").is_none());
+    }
+
+    #[test]
+    fn find_magic_base() {
+        let base = bases::find_magic_base("Athlete's Coral Ring of the Whelpling").unwrap();
+        assert_eq!(base.name, "Coral Ring");
+        assert_eq!(base.slot, bases::Slot::Ring);
+        assert_eq!(base.implicits, ["+(20-30) to maximum Life"]);
+
+        let base = bases::find_magic_base("Endless Grand Life Flask of Warding").unwrap();
+        assert_eq!(base.name, "Grand Life Flask");
+
+        assert!(bases::find_magic_base("Athlete's Ring").is_none());
+    }
+
+    #[test]
+    fn uniques_have_bases() {
+        for unique in uniques::all() {
+            assert!(unique.base().is_some(), "{} has no base", unique.name);
+        }
+
+        let thread_of_hope = uniques::by_name("Thread of Hope").unwrap();
+        assert_eq!(thread_of_hope.base().unwrap().slot, bases::Slot::Jewel);
+        assert_eq!(thread_of_hope.variants.len(), 5);
+    }
+
+    #[test]
+    fn mod_tiers() {
+        let boots = bases::by_name("Titan Greaves").unwrap();
+        let helmet = bases::by_name("Royal Burgonet").unwrap();
+        let body_armour = bases::by_name("Glorious Plate").unwrap();
+
+        let life = mods::by_id("IncreasedLife0").unwrap();
+        assert_eq!(life.text, "# to maximum Life");
+        assert_eq!(life.stats, [(3.0, 9.0)]);
+        assert_eq!(life.tier(body_armour), Some(13));
+        assert_eq!(life.tier(boots), Some(11));
+
+        let life = mods::find(boots, "# to maximum Life", &[150.0]).unwrap();
+        assert_eq!(life.name, "Vigorous");
+        assert_eq!(life.tier(boots), Some(1));
+        assert_eq!(life.tier(helmet), Some(2));
+        assert_eq!(life.tier(body_armour), Some(3));
+        assert_eq!(
+            mods::by_text("# to maximum Life").next().unwrap().name,
+            "Prime"
+        );
+
+        assert!(mods::find(boots, "# to maximum Life", &[180.0]).is_none());
+        assert!(mods::find(body_armour, "# to maximum Life", &[180.0]).is_some());
+
+        let ring = bases::by_name("Coral Ring").unwrap();
+        let added = mods::find(
+            ring,
+            "Adds # to # Physical Damage to Attacks",
+            &[10.0, 20.0],
+        );
+        let added = added.unwrap();
+        assert_eq!(added.name, "Tempered");
+        assert_eq!(added.generation_type, mods::GenerationType::Prefix);
+        assert!(mods::find(
+            boots,
+            "Adds # to # Physical Damage to Attacks",
+            &[10.0, 20.0]
+        )
+        .is_none());
+
+        let flask = bases::by_name("Grand Life Flask").unwrap();
+        let curse = mods::by_id("FlaskCurseImmunity1").unwrap();
+        assert!(curse.spawns_on(flask));
+        assert!(!curse.spawns_on(ring));
+        assert_eq!(curse.tier(ring), None);
+
+        assert!(mods::find(boots, "# to maximum Life", &[500.0]).is_none());
+
+        assert_eq!(trade::stat_id(life.text), Some("stat_3299347043"));
+        assert!(mods::find(boots, "Unknown", &[]).is_none());
+    }
+}
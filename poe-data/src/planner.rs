@@ -0,0 +1,148 @@
+//! Leveling shopping list for the gems of a build.
+//!
+//! Every gem is bought from the first vendor which sells it to the class.
+
+use shared::Class;
+
+use crate::gems::{self, Gem, Vendor};
+
+/// Sells all gems to all classes after "Fallen from Grace" in act 6.
+pub const LILLY_ROTH: &str = "Lilly Roth";
+
+#[derive(Debug, Default)]
+pub struct ShoppingList {
+    /// Gems the class can buy, grouped by act and quest, ordered by act.
+    pub quests: Vec<Quest>,
+    /// Gems the class can not buy from any vendor.
+    pub unavailable: Vec<Purchase>,
+}
+
+#[derive(Debug)]
+pub struct Quest {
+    pub act: u8,
+    pub quest: &'static str,
+    pub npc: &'static str,
+    pub gems: Vec<Purchase>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Purchase {
+    pub gem: &'static Gem,
+    /// First vendor selling the gem to the class.
+    pub vendor: Option<&'static Vendor>,
+}
+
+impl Purchase {
+    /// Whether the gem is only sold to the class by Lilly Roth.
+    pub fn needs_lilly_roth(&self) -> bool {
+        self.vendor.is_some_and(|vendor| vendor.npc == LILLY_ROTH)
+    }
+
+    /// Whether the gem is sold to other classes, but not to the class of the build.
+    ///
+    /// These gems have to be bought with another character.
+    pub fn needs_other_character(&self) -> bool {
+        self.vendor.is_none() && !self.gem.vendors.is_empty()
+    }
+}
+
+/// Creates the shopping list for gems of a build.
+///
+/// Duplicate and unknown gem ids are ignored.
+pub fn shopping_list<'a>(gem_ids: impl IntoIterator<Item = &'a str>, class: Class) -> ShoppingList {
+    let mut purchases = Vec::<Purchase>::new();
+    for id in gem_ids {
+        let Some(gem) = gems::by_id(id) else {
+            continue;
+        };
+        if purchases.iter().any(|p| p.gem.id == gem.id) {
+            continue;
+        }
+
+        purchases.push(Purchase {
+            gem,
+            // Vendors are sorted by act.
+            vendor: gem.vendors(class).next(),
+        });
+    }
+    purchases.sort_by_key(|p| (p.gem.level, p.gem.name));
+
+    let mut list = ShoppingList::default();
+    for purchase in purchases {
+        let Some(vendor) = purchase.vendor else {
+            list.unavailable.push(purchase);
+            continue;
+        };
+
+        let quest = list
+            .quests
+            .iter_mut()
+            .find(|q| (q.act, q.quest, q.npc) == (vendor.act, vendor.quest, vendor.npc));
+        match quest {
+            Some(quest) => quest.gems.push(purchase),
+            None => list.quests.push(Quest {
+                act: vendor.act,
+                quest: vendor.quest,
+                npc: vendor.npc,
+                gems: vec![purchase],
+            }),
+        }
+    }
+    list.quests.sort_by_key(|q| (q.act, q.quest));
+
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREBALL: &str = "Metadata/Items/Gems/SkillGemFireball";
+    const ADDED_FIRE: &str = "Metadata/Items/Gems/SupportGemAddedFireDamage";
+    const ALCHEMISTS_MARK: &str = "Metadata/Items/Gems/SkillGemAlchemistsMark";
+    const PORTAL: &str = "Metadata/Items/Gems/SkillGemPortal";
+    const AMBUSH: &str = "Metadata/Items/Gems/SkillGemAmbush";
+
+    fn names(purchases: &[Purchase]) -> Vec<&str> {
+        purchases.iter().map(|p| p.gem.name).collect()
+    }
+
+    #[test]
+    fn shopping_list_by_class() {
+        let gems = [FIREBALL, ADDED_FIRE, FIREBALL, "Unknown"];
+
+        let witch = shopping_list(gems, Class::Witch);
+        assert_eq!(witch.quests.len(), 2);
+        assert_eq!(witch.quests[0].act, 1);
+        assert_eq!(witch.quests[0].npc, "Nessa");
+        assert_eq!(names(&witch.quests[0].gems), ["Fireball"]);
+        assert_eq!(witch.quests[1].act, 3);
+        assert_eq!(names(&witch.quests[1].gems), ["Added Fire Damage Support"]);
+        assert!(witch.unavailable.is_empty());
+
+        let marauder = shopping_list(gems, Class::Marauder);
+        assert_eq!(marauder.quests.len(), 2);
+        assert!(marauder.quests.iter().all(|q| q.act == 1));
+    }
+
+    #[test]
+    fn shopping_list_lilly_roth() {
+        let list = shopping_list([AMBUSH, FIREBALL], Class::Witch);
+
+        let quest = list.quests.last().unwrap();
+        assert_eq!(quest.npc, LILLY_ROTH);
+        assert_eq!(names(&quest.gems), ["Ambush"]);
+        assert!(quest.gems[0].needs_lilly_roth());
+        assert!(!list.quests[0].gems[0].needs_lilly_roth());
+    }
+
+    #[test]
+    fn shopping_list_unavailable() {
+        let list = shopping_list([ALCHEMISTS_MARK, PORTAL], Class::Witch);
+
+        assert!(list.quests.is_empty());
+        assert_eq!(names(&list.unavailable), ["Portal", "Alchemist's Mark"]);
+        assert!(list.unavailable[1].needs_other_character());
+        assert!(!list.unavailable[0].needs_other_character());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -6,6 +6,135 @@ use std::path::Path;
 
 use poe_api::api::SkillTreeData;
 use poe_api::SkillTreeNode;
+use serde::Deserialize;
+
+/// Angles of the nodes on orbits with 16 nodes, these are not evenly spaced.
+const ORBIT_ANGLES_16: [f32; 16] = [
+    0.0, 30.0, 45.0, 60.0, 90.0, 120.0, 135.0, 150.0, 180.0, 210.0, 225.0, 240.0, 270.0, 300.0,
+    315.0, 330.0,
+];
+/// Angles of the nodes on orbits with 40 nodes, only used by trees which also have 16 node orbits.
+const ORBIT_ANGLES_40: [f32; 40] = [
+    0.0, 10.0, 20.0, 30.0, 40.0, 45.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0, 130.0,
+    135.0, 140.0, 150.0, 160.0, 170.0, 180.0, 190.0, 200.0, 210.0, 220.0, 225.0, 230.0, 240.0,
+    250.0, 260.0, 270.0, 280.0, 290.0, 300.0, 310.0, 315.0, 320.0, 330.0, 340.0, 350.0,
+];
+
+/// Layout and connections of the tree, read separately since [`SkillTreeData`] omits them.
+#[derive(Deserialize)]
+struct Layout {
+    classes: Vec<LayoutClass>,
+    nodes: HashMap<String, LayoutNode>,
+    groups: HashMap<u32, LayoutGroup>,
+    constants: LayoutConstants,
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutNode {
+    #[serde(default)]
+    group: Option<u32>,
+    #[serde(default)]
+    orbit: usize,
+    #[serde(default)]
+    orbit_index: usize,
+    #[serde(default)]
+    out: Vec<String>,
+    #[serde(default, rename = "in")]
+    in_: Vec<String>,
+    #[serde(default)]
+    class_start_index: Option<u8>,
+    #[serde(default)]
+    is_jewel_socket: bool,
+    #[serde(default)]
+    is_ascendancy_start: bool,
+    #[serde(default)]
+    ascendancy_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LayoutClass {
+    ascendancies: Vec<LayoutAscendancy>,
+}
+
+#[derive(Deserialize)]
+struct LayoutAscendancy {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct LayoutGroup {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutConstants {
+    skills_per_orbit: Vec<usize>,
+    orbit_radii: Vec<f32>,
+}
+
+impl Layout {
+    fn node(&self, id: u32) -> Option<&LayoutNode> {
+        self.nodes.get(&id.to_string())
+    }
+
+    /// Position of a node, relative to the center of the tree.
+    fn position(&self, node: &LayoutNode) -> (f32, f32) {
+        let Some(group) = node.group.and_then(|group| self.groups.get(&group)) else {
+            return (0.0, 0.0);
+        };
+
+        let radius = self.orbit_radius(node.orbit);
+        let skills = self.constants.skills_per_orbit[node.orbit];
+        let has_16 = self.constants.skills_per_orbit.contains(&16);
+        let angle = match skills {
+            16 => ORBIT_ANGLES_16[node.orbit_index],
+            40 if has_16 => ORBIT_ANGLES_40[node.orbit_index],
+            _ => 360.0 * node.orbit_index as f32 / skills as f32,
+        }
+        .to_radians();
+
+        (
+            group.x + radius * angle.sin(),
+            group.y - radius * angle.cos(),
+        )
+    }
+
+    fn orbit_radius(&self, orbit: usize) -> f32 {
+        self.constants
+            .orbit_radii
+            .get(orbit)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Nodes connected to `id`, in either direction.
+    ///
+    /// The ascendancies are connected to the class start nodes,
+    /// these connections can never be allocated and are omitted.
+    fn neighbours(&self, id: u32, node: &LayoutNode) -> Vec<u32> {
+        let mut neighbours = node
+            .out
+            .iter()
+            .chain(&node.in_)
+            .filter_map(|other| other.parse::<u32>().ok())
+            .filter(|&other| other != id)
+            .filter(|&other| {
+                self.node(other)
+                    .is_some_and(|other| other.ascendancy_name == node.ascendancy_name)
+            })
+            .collect::<Vec<_>>();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+}
 
 fn get_trees() -> impl Iterator<Item = String> {
     env::vars().filter_map(|(name, _)| {
@@ -19,19 +148,20 @@ pub fn main() -> anyhow::Result<()> {
         let data_path = Path::new(".").join("data").join(format!("{version}.json"));
 
         let data = std::fs::read_to_string(data_path)?;
+        let layout: Layout = serde_json::from_str(&data)?;
         let data: SkillTreeData = serde_json::from_str(&data)?;
 
         let dest_path = Path::new(&env::var_os("OUT_DIR").unwrap())
             .join(format!("tree{}.rs", version.replace('.', "_")));
         let mut output = File::create(dest_path)?;
 
-        generate(&data, &mut output)?;
+        generate(&data, &layout, &mut output)?;
     }
 
     Ok(())
 }
 
-fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()> {
+fn generate(data: &SkillTreeData, layout: &Layout, output: &mut dyn Write) -> anyhow::Result<()> {
     let mut map = phf_codegen::Map::new();
 
     let alternate_ascendancies = data
@@ -49,13 +179,18 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
     };
 
     writeln!(output, "#[allow(unused_imports)]")?;
-    writeln!(output, "use crate::{{Kind, MasteryEffect, Node}};")?;
+    writeln!(output, "use crate::{{Bounds, Kind, MasteryEffect, Node}};")?;
 
+    let no_layout = LayoutNode::default();
     for node in data.nodes.values() {
+        let l = layout.node(node.skill).unwrap_or(&no_layout);
+
         let kind = if node.is_mastery {
             "Kind::Mastery"
         } else if node.is_keystone {
             "Kind::Keystone"
+        } else if l.is_jewel_socket {
+            "Kind::JewelSocket"
         } else if is_alt_ascendancy_notable(node) {
             "Kind::AlternateAscendancyNotable"
         } else if node.is_notable {
@@ -91,13 +226,28 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
             // files on the cdn are lowercase (directly extracted from the bundle)
                 .map(|icon| icon.to_lowercase());
 
+        let ascendancy = &node.ascendancy_name;
+        let class_start = l.class_start_index;
+        let is_ascendancy_start = l.is_ascendancy_start;
+        let group = l.group.unwrap_or_default();
+        let orbit = l.orbit;
+        let (x, y) = layout.position(l);
+        let neighbours = layout.neighbours(node.skill, l);
+
         let n = format!(
             r#"Node {{ 
                 kind: {kind}, 
                 name: "{}", 
                 stats: &{:?}, 
                 mastery_effects: &[{mastery_effects}], 
-                icon: {icon:?} 
+                icon: {icon:?},
+                ascendancy: {ascendancy:?},
+                class_start: {class_start:?},
+                is_ascendancy_start: {is_ascendancy_start},
+                group: {group},
+                orbit: {orbit},
+                position: ({x:?}, {y:?}),
+                neighbours: &{neighbours:?},
             }}"#,
             node.name, node.stats
         );
@@ -111,5 +261,50 @@ fn generate(data: &SkillTreeData, output: &mut dyn Write) -> anyhow::Result<()>
         map.build()
     )?;
 
+    let mut groups = phf_codegen::Map::new();
+    for (id, group) in &layout.groups {
+        groups.entry(*id, &format!("({:?}, {:?})", group.x, group.y));
+    }
+    writeln!(
+        output,
+        "pub static GROUPS: phf::Map<u32, (f32, f32)> = {};",
+        groups.build()
+    )?;
+
+    let ascendancies = layout
+        .classes
+        .iter()
+        .map(|class| {
+            let names = class.ascendancies.iter().map(|a| &a.id).collect::<Vec<_>>();
+            format!("&{names:?}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        output,
+        "pub static ASCENDANCIES: &[&[&str]] = &[{ascendancies}];"
+    )?;
+
+    let alternate_ascendancies = data
+        .alternate_ascendancies
+        .iter()
+        .map(|a| &a.id)
+        .collect::<Vec<_>>();
+    writeln!(
+        output,
+        "pub static ALTERNATE_ASCENDANCIES: &[&str] = &{alternate_ascendancies:?};"
+    )?;
+
+    writeln!(
+        output,
+        "pub static ORBIT_RADII: &[f32] = &{:?};",
+        layout.constants.orbit_radii
+    )?;
+    writeln!(
+        output,
+        "pub static BOUNDS: Bounds = Bounds {{ min_x: {:?}, min_y: {:?}, max_x: {:?}, max_y: {:?} }};",
+        layout.min_x, layout.min_y, layout.max_x, layout.max_y
+    )?;
+
     Ok(())
 }
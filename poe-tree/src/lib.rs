@@ -5,11 +5,23 @@ pub enum Kind {
     Node,
     Notable,
     Mastery,
+    JewelSocket,
     // Special
     AlternateAscendancyNotable,
 }
 
 impl Kind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keystone => "Keystone",
+            Self::Node => "Node",
+            Self::Notable => "Notable",
+            Self::Mastery => "Mastery",
+            Self::JewelSocket => "JewelSocket",
+            Self::AlternateAscendancyNotable => "AlternateAscendancyNotable",
+        }
+    }
+
     pub fn is_keystone(&self) -> bool {
         matches!(self, Self::Keystone)
     }
@@ -22,6 +34,10 @@ impl Kind {
         matches!(self, Self::Mastery)
     }
 
+    pub fn is_jewel_socket(&self) -> bool {
+        matches!(self, Self::JewelSocket)
+    }
+
     pub fn is_alternate_ascendancy_notable(&self) -> bool {
         matches!(self, Self::AlternateAscendancyNotable)
     }
@@ -34,6 +50,35 @@ pub struct Node {
     pub stats: &'static [&'static str],
     pub mastery_effects: &'static [MasteryEffect],
     pub icon: Option<&'static str>,
+    /// Name of the ascendancy the node belongs to.
+    pub ascendancy: Option<&'static str>,
+    /// Class index, as used by PoB, if the node is a class start node.
+    pub class_start: Option<u8>,
+    pub is_ascendancy_start: bool,
+    pub group: u32,
+    pub orbit: u8,
+    /// Position of the node in tree coordinates, see [`Version::bounds`].
+    pub position: (f32, f32),
+    /// Nodes connected to this node, connections are undirected.
+    ///
+    /// Connections between class start nodes and ascendancies are omitted.
+    pub neighbours: &'static [u32],
+}
+
+impl Node {
+    /// Whether the node can be allocated with a passive point.
+    pub fn is_allocatable(&self) -> bool {
+        self.class_start.is_none() && !self.is_ascendancy_start
+    }
+}
+
+/// Extent of the tree in tree coordinates.
+#[derive(Debug, Copy, Clone)]
+pub struct Bounds {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
 }
 
 #[derive(Debug)]
@@ -54,8 +99,8 @@ impl std::fmt::Display for ParseVersionError {
 impl std::error::Error for ParseVersionError {}
 
 macro_rules! gen {
-    ($(($version:ident, $file:expr, $module:ident, $feature:expr, $m:pat)),+) => {
-        #[derive(Copy, Clone)]
+    ($(($version:ident, $file:expr, $module:ident, $feature:expr, $name:expr, $m:pat)),+) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
         pub enum Version {
             $(
                 #[cfg(feature = $feature)]
@@ -64,13 +109,25 @@ macro_rules! gen {
         }
 
         impl Version {
+            /// Latest tree version enabled through features.
             pub fn latest() -> Version {
-                $(
-                    if cfg!(feature = $feature) {
-                        return Self::$version;
-                    }
-                 )*
-                unreachable!("no version enabled")
+                const ENABLED: &[Version] = &[
+                    $(
+                        #[cfg(feature = $feature)]
+                        Version::$version,
+                    )*
+                ];
+                *ENABLED.first().expect("no version enabled")
+            }
+
+            /// Version as used by PoB, e.g. `3_23`.
+            pub fn as_str(&self) -> &'static str {
+                match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => $name,
+                    )*
+                }
             }
 
             fn get_node(&self, _id: u32) -> Option<&'static Node> {
@@ -83,6 +140,86 @@ macro_rules! gen {
                     _ => None,
                 }
             }
+
+            /// All nodes of the tree, in no particular order.
+            pub fn nodes(&self) -> impl Iterator<Item = (u32, &'static Node)> {
+                let tree = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => &self::$module::TREE,
+                    )*
+                };
+                tree.entries().map(|(&id, node)| (id, node))
+            }
+
+            /// Center of a node group in tree coordinates.
+            pub fn group_position(&self, group: u32) -> Option<(f32, f32)> {
+                let groups = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => &self::$module::GROUPS,
+                    )*
+                };
+                groups.get(&group).copied()
+            }
+
+            pub fn orbit_radius(&self, orbit: u8) -> f32 {
+                let radii = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::ORBIT_RADII,
+                    )*
+                };
+                radii.get(orbit as usize).copied().unwrap_or_default()
+            }
+
+            /// Name of an ascendancy from the class and ascendancy index used by PoB.
+            ///
+            /// Ascendancies are indexed from `1`, `0` is no ascendancy.
+            pub fn ascendancy(&self, class_id: u8, ascendancy_id: u8) -> Option<&'static str> {
+                let ascendancies = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::ASCENDANCIES,
+                    )*
+                };
+                let index = usize::from(ascendancy_id).checked_sub(1)?;
+                ascendancies.get(usize::from(class_id))?.get(index).copied()
+            }
+
+            /// Name of an alternate ascendancy from the index used by PoB.
+            ///
+            /// Alternate ascendancies are indexed from `1`, `0` is no alternate ascendancy.
+            pub fn alternate_ascendancy(&self, alternate_ascendancy_id: u8) -> Option<&'static str> {
+                let alternate_ascendancies = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::ALTERNATE_ASCENDANCIES,
+                    )*
+                };
+                let index = usize::from(alternate_ascendancy_id).checked_sub(1)?;
+                alternate_ascendancies.get(index).copied()
+            }
+
+            /// Whether `name` is a regular ascendancy and not an alternate ascendancy.
+            pub fn is_ascendancy(&self, name: &str) -> bool {
+                let ascendancies = match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::ASCENDANCIES,
+                    )*
+                };
+                ascendancies.iter().any(|class| class.contains(&name))
+            }
+
+            pub fn bounds(&self) -> Bounds {
+                match *self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        Self::$version => self::$module::BOUNDS,
+                    )*
+                }
+            }
         }
 
         impl std::str::FromStr for Version {
@@ -110,6 +247,10 @@ macro_rules! gen {
     };
 }
 
+pub mod path;
+pub mod svg;
+pub mod validate;
+
 pub fn get_node_opt(version: &str, id: u32) -> Option<&'static Node> {
     version.parse::<Version>().ok().and_then(|v| v.get_node(id))
 }
@@ -119,13 +260,13 @@ pub fn get_node(version: Version, id: u32) -> Option<&'static Node> {
 }
 
 gen! {
-    (V3_23, "/tree3_23.rs", tree3_23, "tree-3_23", "3_23" | "3.23"),
-    (V3_22, "/tree3_22.rs", tree3_22, "tree-3_22", "3_22" | "3.22"),
-    (V3_21, "/tree3_21.rs", tree3_21, "tree-3_21", "3_21" | "3.21"),
-    (V3_20, "/tree3_20.rs", tree3_20, "tree-3_20", "3_20" | "3.20"),
-    (V3_19, "/tree3_19.rs", tree3_19, "tree-3_19", "3_19" | "3.19"),
-    (V3_18, "/tree3_18.rs", tree3_18, "tree-3_18", "3_18" | "3.18"),
-    (V3_17, "/tree3_17.rs", tree3_17, "tree-3_17", "3_17" | "3.17"),
-    (V3_16, "/tree3_16.rs", tree3_16, "tree-3_16", "3_16" | "3.16"),
-    (V3_15, "/tree3_15.rs", tree3_15, "tree-3_15", "3_15" | "3.15")
+    (V3_23, "/tree3_23.rs", tree3_23, "tree-3_23", "3_23", "3_23" | "3.23"),
+    (V3_22, "/tree3_22.rs", tree3_22, "tree-3_22", "3_22", "3_22" | "3.22"),
+    (V3_21, "/tree3_21.rs", tree3_21, "tree-3_21", "3_21", "3_21" | "3.21"),
+    (V3_20, "/tree3_20.rs", tree3_20, "tree-3_20", "3_20", "3_20" | "3.20"),
+    (V3_19, "/tree3_19.rs", tree3_19, "tree-3_19", "3_19", "3_19" | "3.19"),
+    (V3_18, "/tree3_18.rs", tree3_18, "tree-3_18", "3_18", "3_18" | "3.18"),
+    (V3_17, "/tree3_17.rs", tree3_17, "tree-3_17", "3_17", "3_17" | "3.17"),
+    (V3_16, "/tree3_16.rs", tree3_16, "tree-3_16", "3_16", "3_16" | "3.16"),
+    (V3_15, "/tree3_15.rs", tree3_15, "tree-3_15", "3_15", "3_15" | "3.15")
 }
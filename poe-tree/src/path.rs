@@ -0,0 +1,234 @@
+//! Pathing and connectivity on the passive tree.
+//!
+//! Class ids are the ids used by PoB, which match the `classStartIndex`
+//! of the class start nodes. Unknown node ids, e.g. nodes of cluster jewels,
+//! are ignored.
+
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+
+use crate::{get_node, Version};
+
+/// Class start node of a class.
+pub fn class_start(version: Version, class_id: u8) -> Option<u32> {
+    version
+        .nodes()
+        .find(|(_, node)| node.class_start == Some(class_id))
+        .map(|(id, _)| id)
+}
+
+/// Shortest path between two nodes, including both nodes.
+///
+/// Paths never cross class start nodes, except when starting from one.
+pub fn shortest_path(version: Version, from: u32, to: u32) -> Option<Vec<u32>> {
+    bfs(version, [from], |id| id == to)
+}
+
+/// Allocated nodes which are not connected to the class start or an ascendancy start.
+pub fn disconnected_nodes(version: Version, class_id: u8, allocated: &[u32]) -> Vec<u32> {
+    let allocated = allocated
+        .iter()
+        .filter_map(|&id| Some((id, get_node(version, id)?)))
+        .collect::<HashMap<_, _>>();
+
+    let roots = class_start(version, class_id).into_iter().chain(
+        version
+            .nodes()
+            .filter(|(_, node)| node.is_ascendancy_start)
+            .map(|(id, _)| id),
+    );
+
+    let mut connected = HashSet::new();
+    let mut queue = roots.collect::<VecDeque<_>>();
+    while let Some(id) = queue.pop_front() {
+        if !connected.insert(id) {
+            continue;
+        }
+        let Some(node) = get_node(version, id) else {
+            continue;
+        };
+        queue.extend(
+            node.neighbours
+                .iter()
+                .filter(|neighbour| allocated.contains_key(neighbour)),
+        );
+    }
+
+    // Masteries are not connected to other nodes, they only require a node of their group.
+    let groups = connected
+        .iter()
+        .filter_map(|&id| get_node(version, id))
+        .filter(|node| node.is_allocatable())
+        .map(|node| node.group)
+        .collect::<HashSet<_>>();
+
+    let mut disconnected = allocated
+        .iter()
+        .filter(|(_, node)| node.is_allocatable())
+        .filter(|(id, node)| match node.kind.is_mastery() {
+            true => !groups.contains(&node.group),
+            false => !connected.contains(*id),
+        })
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    disconnected.sort_unstable();
+
+    disconnected
+}
+
+/// Whether all allocated nodes are connected to the class start or an ascendancy start.
+pub fn is_connected(version: Version, class_id: u8, allocated: &[u32]) -> bool {
+    disconnected_nodes(version, class_id, allocated).is_empty()
+}
+
+/// Number of passive points required to allocate `target`,
+/// including the target itself.
+///
+/// Returns `Some(0)` if the node is already allocated and `None`
+/// if the node can not be reached.
+pub fn points_to_reach(
+    version: Version,
+    class_id: u8,
+    allocated: &[u32],
+    target: u32,
+) -> Option<usize> {
+    if allocated.contains(&target) {
+        return Some(0);
+    }
+    let node = get_node(version, target)?;
+    if !node.is_allocatable() {
+        return None;
+    }
+
+    // Ascendancy start nodes are free, nodes of an ascendancy can only be reached from there.
+    let sources = match node.ascendancy {
+        Some(ascendancy) => version
+            .nodes()
+            .filter(|(_, n)| n.is_ascendancy_start && n.ascendancy == Some(ascendancy))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>(),
+        None => class_start(version, class_id).into_iter().collect(),
+    };
+    let sources = sources.into_iter().chain(allocated.iter().copied());
+
+    match node.kind.is_mastery() {
+        true => {
+            let is_target = |id| {
+                get_node(version, id).is_some_and(|n| n.group == node.group && !n.kind.is_mastery())
+            };
+            let path = bfs(version, sources, is_target)?;
+            Some(path.len())
+        }
+        false => {
+            let path = bfs(version, sources, |id| id == target)?;
+            Some(path.len() - 1)
+        }
+    }
+}
+
+/// Breadth first search from any of the `sources` to the first node matching `is_target`.
+///
+/// Returns the path from the source to the target, including both.
+fn bfs(
+    version: Version,
+    sources: impl IntoIterator<Item = u32>,
+    is_target: impl Fn(u32) -> bool,
+) -> Option<Vec<u32>> {
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if parents.insert(source, None).is_none() {
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        if is_target(id) {
+            let mut path = vec![id];
+            while let Some(&Some(parent)) = parents.get(path.last()?) {
+                path.push(parent);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let Some(node) = get_node(version, id) else {
+            continue;
+        };
+        // Only the sources may be class start nodes.
+        if node.class_start.is_some() && parents[&id].is_some() {
+            continue;
+        }
+
+        for &neighbour in node.neighbours {
+            if let Entry::Vacant(entry) = parents.entry(neighbour) {
+                entry.insert(Some(id));
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARAUDER: u8 = 1;
+    const MARAUDER_START: u32 = 47175;
+    const WARRIORS_BLOOD: u32 = 24383;
+    const BERSERKER_NODE: u32 = 5865;
+
+    fn version() -> Version {
+        "3.15".parse().unwrap()
+    }
+
+    #[test]
+    fn path_from_class_start() {
+        assert_eq!(class_start(version(), MARAUDER), Some(MARAUDER_START));
+
+        let path = shortest_path(version(), MARAUDER_START, WARRIORS_BLOOD).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.first(), Some(&MARAUDER_START));
+        assert_eq!(path.last(), Some(&WARRIORS_BLOOD));
+        assert!(is_connected(version(), MARAUDER, &path[1..]));
+
+        assert_eq!(
+            points_to_reach(version(), MARAUDER, &[], WARRIORS_BLOOD),
+            Some(5)
+        );
+        assert_eq!(
+            points_to_reach(version(), MARAUDER, &path[1..3], WARRIORS_BLOOD),
+            Some(3)
+        );
+        assert_eq!(
+            points_to_reach(version(), MARAUDER, &path[1..], WARRIORS_BLOOD),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn disconnected() {
+        let path = shortest_path(version(), MARAUDER_START, WARRIORS_BLOOD).unwrap();
+
+        let mut expected = path[2..].to_vec();
+        expected.sort_unstable();
+        assert_eq!(
+            disconnected_nodes(version(), MARAUDER, &path[2..]),
+            expected
+        );
+        // Unknown nodes, e.g. cluster jewel nodes, are ignored.
+        assert!(is_connected(version(), MARAUDER, &[path[1], 65536]));
+    }
+
+    #[test]
+    fn ascendancy() {
+        assert_eq!(
+            points_to_reach(version(), MARAUDER, &[], BERSERKER_NODE),
+            Some(1)
+        );
+        assert!(is_connected(version(), MARAUDER, &[BERSERKER_NODE]));
+        // Ascendancies are not connected to the class start.
+        assert!(shortest_path(version(), MARAUDER_START, BERSERKER_NODE).is_none());
+    }
+}
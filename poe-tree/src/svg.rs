@@ -0,0 +1,270 @@
+//! Renders the passive tree as SVG.
+//!
+//! The output does not depend on any scripts or external assets,
+//! it can be inlined into a page or rasterized into an image.
+//!
+//! Every node is rendered as a `circle` with the id `n<node id>`,
+//! nodes can be highlighted by adding the `highlight` class.
+//!
+//! The name, kind and stats of a node are attached as `data-name`, `data-kind`
+//! and `data-stats` attributes, stats are separated by newlines. Tooltips can be
+//! shown without the tree data of the version.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{get_node, Kind, Node, Version};
+
+/// Space around the tree, in tree coordinates.
+const PADDING: f32 = 250.0;
+
+const EDGE: &str = "#334155";
+const EDGE_ALLOCATED: &str = "#d97706";
+const NODE: &str = "#1e293b";
+const NODE_STROKE: &str = "#64748b";
+const NODE_ALLOCATED: &str = "#fbbf24";
+const MASTERY_ALLOCATED: &str = "#a78bfa";
+const JEWEL: &str = "#38bdf8";
+
+/// Allocated nodes of a tree spec.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Spec<'a> {
+    /// Class index, as used by PoB.
+    pub class_id: Option<u8>,
+    /// Ascendancy index, as used by PoB, see [`Version::ascendancy`].
+    pub ascendancy_id: Option<u8>,
+    /// Alternate ascendancy index, as used by PoB, see [`Version::alternate_ascendancy`].
+    pub alternate_ascendancy_id: Option<u8>,
+    pub nodes: &'a [u32],
+    /// Jewel sockets which contain a jewel.
+    pub sockets: &'a [u32],
+}
+
+/// Renders the tree with all allocated nodes of the `spec`.
+///
+/// Only the selected ascendancies and ascendancies with allocated nodes are rendered.
+pub fn render(version: Version, spec: &Spec) -> String {
+    let ascendancy = spec
+        .class_id
+        .zip(spec.ascendancy_id)
+        .and_then(|(class_id, ascendancy_id)| version.ascendancy(class_id, ascendancy_id));
+    let alternate_ascendancy = spec
+        .alternate_ascendancy_id
+        .and_then(|id| version.alternate_ascendancy(id));
+
+    let ascendancies = spec
+        .nodes
+        .iter()
+        .filter_map(|&id| get_node(version, id)?.ascendancy)
+        .chain(ascendancy)
+        .chain(alternate_ascendancy)
+        .collect::<HashSet<_>>();
+
+    // Start nodes are never part of the allocated nodes, but are always connected.
+    let allocated = version
+        .nodes()
+        .filter(|(_, node)| {
+            let is_class_start = spec.class_id.is_some() && node.class_start == spec.class_id;
+            let is_ascendancy_start = node.is_ascendancy_start
+                && node.ascendancy.is_some_and(|a| ascendancies.contains(a));
+            is_class_start || is_ascendancy_start
+        })
+        .map(|(id, _)| id)
+        .chain(spec.nodes.iter().copied())
+        .collect::<HashSet<_>>();
+
+    let is_visible = |node: &Node| match node.ascendancy {
+        Some(ascendancy) => ascendancies.contains(ascendancy),
+        None => true,
+    };
+    let mut nodes = version
+        .nodes()
+        .filter(|(_, node)| is_visible(node))
+        .collect::<Vec<_>>();
+    // Output should be stable, the tree is a hash map.
+    nodes.sort_unstable_by_key(|(id, _)| *id);
+
+    let bounds = version.bounds();
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.0} {:.0} {:.0} {:.0}">"#,
+        bounds.min_x - PADDING,
+        bounds.min_y - PADDING,
+        bounds.max_x - bounds.min_x + 2.0 * PADDING,
+        bounds.max_y - bounds.min_y + 2.0 * PADDING,
+    );
+    let _ = write!(
+        svg,
+        "<style>.highlight{{stroke:#f8fafc;stroke-width:24}}</style>"
+    );
+
+    // Allocated connections are rendered last to be on top.
+    for is_allocated in [false, true] {
+        let (color, width) = match is_allocated {
+            true => (EDGE_ALLOCATED, 24),
+            false => (EDGE, 12),
+        };
+        let _ = write!(
+            svg,
+            r#"<g fill="none" stroke="{color}" stroke-width="{width}">"#
+        );
+        for &(id, node) in &nodes {
+            for &other_id in node.neighbours.iter().filter(|&&other| other > id) {
+                let Some(other) = get_node(version, other_id).filter(|n| is_visible(n)) else {
+                    continue;
+                };
+                if is_allocated == (allocated.contains(&id) && allocated.contains(&other_id)) {
+                    render_edge(&mut svg, version, node, other);
+                }
+            }
+        }
+        svg.push_str("</g>");
+    }
+
+    for (id, node) in nodes {
+        let is_allocated = allocated.contains(&id);
+        let (fill, stroke) = match node.kind {
+            Kind::Mastery if is_allocated => (MASTERY_ALLOCATED, MASTERY_ALLOCATED),
+            Kind::Mastery => ("none", EDGE),
+            Kind::JewelSocket if spec.sockets.contains(&id) => (JEWEL, NODE_ALLOCATED),
+            _ if is_allocated => (NODE_ALLOCATED, NODE_ALLOCATED),
+            _ => (NODE, NODE_STROKE),
+        };
+        let (x, y) = node.position;
+        let _ = write!(
+            svg,
+            r#"<circle id="n{id}" cx="{x:.0}" cy="{y:.0}" r="{}" fill="{fill}" stroke="{stroke}" stroke-width="8" data-name="{}" data-kind="{}" data-stats="{}"/>"#,
+            radius(node),
+            escape(node.name),
+            node.kind.as_str(),
+            escape(&node.stats.join("\n")),
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Connections between nodes on the same orbit follow the orbit.
+fn render_edge(svg: &mut String, version: Version, a: &Node, b: &Node) {
+    let (ax, ay) = a.position;
+    let (bx, by) = b.position;
+
+    let center = version.group_position(a.group);
+    let radius = version.orbit_radius(a.orbit);
+    match center {
+        Some((cx, cy)) if a.group == b.group && a.orbit == b.orbit && radius > 0.0 => {
+            let angle_a = (ax - cx).atan2(cy - ay);
+            let angle_b = (bx - cx).atan2(cy - by);
+            let mut delta = angle_b - angle_a;
+            if delta > std::f32::consts::PI {
+                delta -= std::f32::consts::TAU;
+            } else if delta < -std::f32::consts::PI {
+                delta += std::f32::consts::TAU;
+            }
+            let sweep = u8::from(delta > 0.0);
+            let _ = write!(
+                svg,
+                r#"<path d="M{ax:.0} {ay:.0}A{radius:.0} {radius:.0} 0 0 {sweep} {bx:.0} {by:.0}"/>"#
+            );
+        }
+        _ => {
+            let _ = write!(svg, r#"<path d="M{ax:.0} {ay:.0}L{bx:.0} {by:.0}"/>"#);
+        }
+    }
+}
+
+/// Escapes text for an attribute value, newlines are kept as character references.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+fn radius(node: &Node) -> u32 {
+    if node.class_start.is_some() {
+        return 80;
+    }
+
+    match node.kind {
+        Kind::Keystone => 56,
+        Kind::Mastery => 44,
+        Kind::Notable | Kind::JewelSocket | Kind::AlternateAscendancyNotable => 40,
+        Kind::Node if node.is_ascendancy_start => 24,
+        Kind::Node => 26,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::shortest_path;
+
+    const MARAUDER: u8 = 1;
+    const BERSERKER: u8 = 2;
+    const MARAUDER_START: u32 = 47175;
+    const WARRIORS_BLOOD: u32 = 24383;
+    const BERSERKER_NODE: u32 = 5865;
+    const CHIEFTAIN_NODE: u32 = 24704;
+
+    fn version() -> Version {
+        "3.15".parse().unwrap()
+    }
+
+    #[test]
+    fn render_allocated() {
+        let path = shortest_path(version(), MARAUDER_START, WARRIORS_BLOOD).unwrap();
+        let svg = render(
+            version(),
+            &Spec {
+                class_id: Some(MARAUDER),
+                ascendancy_id: Some(BERSERKER),
+                nodes: &path[1..],
+                ..Default::default()
+            },
+        );
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains(&format!(r#"<circle id="n{WARRIORS_BLOOD}""#)));
+        assert!(svg.contains(&format!(r#"<circle id="n{BERSERKER_NODE}""#)));
+        assert!(!svg.contains(&format!(r#"<circle id="n{CHIEFTAIN_NODE}""#)));
+
+        let node = svg
+            .split("<circle ")
+            .find(|node| node.starts_with(&format!(r#"id="n{WARRIORS_BLOOD}""#)))
+            .unwrap();
+        assert!(node.contains(&format!(r#"fill="{NODE_ALLOCATED}""#)));
+        assert!(node.contains(r#"data-name="Warrior's Blood""#));
+        assert!(node.contains(r#"data-kind="Notable""#));
+    }
+
+    #[test]
+    fn render_node_data() {
+        let svg = render(version(), &Spec::default());
+
+        let node = get_node(version(), WARRIORS_BLOOD).unwrap();
+        let stats = node.stats.join("&#10;");
+        assert!(node.stats.len() > 1);
+        assert!(svg.contains(&format!(r#"data-stats="{stats}""#)));
+    }
+
+    #[test]
+    fn render_allocated_ascendancy() {
+        let svg = render(
+            version(),
+            &Spec {
+                class_id: Some(MARAUDER),
+                nodes: &[BERSERKER_NODE],
+                ..Default::default()
+            },
+        );
+
+        // Ascendancies with allocated nodes are shown even if not selected.
+        assert!(svg.contains(&format!(r#"<circle id="n{BERSERKER_NODE}""#)));
+        assert!(!svg.contains(&format!(r#"<circle id="n{CHIEFTAIN_NODE}""#)));
+    }
+}
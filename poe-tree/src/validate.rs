@@ -0,0 +1,164 @@
+//! Validation of allocated nodes against the tree data.
+//!
+//! Catches corrupted or hand edited tree specs, which the tree data
+//! can not describe or which can not be allocated in game.
+
+use crate::{get_node, Version};
+
+/// Highest character level.
+pub const MAX_LEVEL: u8 = 100;
+/// Passive points rewarded by quests, including the bandit reward for helping no one.
+pub const QUEST_POINTS: usize = 24;
+/// Ascendancy points rewarded by all labyrinths.
+pub const ASCENDANCY_POINTS: usize = 8;
+
+/// Nodes of cluster jewels have ids outside of the tree data, starting at this id.
+const CLUSTER_NODE_START: u32 = 65536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    /// Node does not exist in the tree.
+    UnknownNode(u32),
+    /// Mastery effect is not an effect of the node.
+    InvalidMasteryEffect { node: u32, effect: u32 },
+    /// More passive points allocated than available at the level.
+    TooManyPoints { points: usize, max: usize },
+    /// More ascendancy points allocated than available.
+    TooManyAscendancyPoints { points: usize, max: usize },
+}
+
+/// Maximum number of passive points available at a character level.
+pub fn max_points(level: u8) -> usize {
+    usize::from(level.max(1)) - 1 + QUEST_POINTS
+}
+
+/// Validates allocated `nodes` and `mastery_effects` of a tree spec
+/// for a character of `level`.
+///
+/// Nodes of alternate ascendancies have their own points and are not counted.
+pub fn validate(
+    version: Version,
+    nodes: &[u32],
+    mastery_effects: &[(u32, u32)],
+    level: u8,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let mut points = 0;
+    let mut ascendancy_points = 0;
+    for &id in nodes {
+        if id >= CLUSTER_NODE_START {
+            points += 1;
+            continue;
+        }
+
+        match get_node(version, id) {
+            None => issues.push(Issue::UnknownNode(id)),
+            Some(node) if !node.is_allocatable() => {}
+            Some(node) => match node.ascendancy {
+                Some(ascendancy) if version.is_ascendancy(ascendancy) => ascendancy_points += 1,
+                Some(_) => {}
+                None => points += 1,
+            },
+        }
+    }
+
+    for &(node, effect) in mastery_effects {
+        let is_valid = get_node(version, node)
+            .is_some_and(|node| node.mastery_effects.iter().any(|m| m.effect == effect));
+
+        if !is_valid {
+            issues.push(Issue::InvalidMasteryEffect { node, effect });
+        }
+    }
+
+    let max = max_points(level);
+    if points > max {
+        issues.push(Issue::TooManyPoints { points, max });
+    }
+    if ascendancy_points > ASCENDANCY_POINTS {
+        issues.push(Issue::TooManyAscendancyPoints {
+            points: ascendancy_points,
+            max: ASCENDANCY_POINTS,
+        });
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::shortest_path;
+
+    const MARAUDER_START: u32 = 47175;
+    const WARRIORS_BLOOD: u32 = 24383;
+    const BERSERKER_NODE: u32 = 5865;
+
+    fn version() -> Version {
+        "3.15".parse().unwrap()
+    }
+
+    #[test]
+    fn validate_valid() {
+        let mut nodes = shortest_path(version(), MARAUDER_START, WARRIORS_BLOOD).unwrap();
+        nodes.push(BERSERKER_NODE);
+        nodes.push(CLUSTER_NODE_START + 1);
+
+        assert_eq!(validate(version(), &nodes, &[], 10), vec![]);
+    }
+
+    #[test]
+    fn validate_invalid() {
+        let issues = validate(
+            version(),
+            &[1, MARAUDER_START, WARRIORS_BLOOD],
+            &[(WARRIORS_BLOOD, 1)],
+            1,
+        );
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue::UnknownNode(1),
+                Issue::InvalidMasteryEffect {
+                    node: WARRIORS_BLOOD,
+                    effect: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_points() {
+        assert_eq!(max_points(1), QUEST_POINTS);
+        assert_eq!(max_points(100), 123);
+
+        let nodes = version()
+            .nodes()
+            .filter(|(_, node)| node.is_allocatable() && node.ascendancy.is_none())
+            .map(|(id, _)| id)
+            .take(max_points(90) + 1)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            validate(version(), &nodes, &[], 90),
+            vec![Issue::TooManyPoints {
+                points: max_points(90) + 1,
+                max: max_points(90)
+            }]
+        );
+
+        let nodes = version()
+            .nodes()
+            .filter(|(_, node)| node.is_allocatable() && node.ascendancy == Some("Berserker"))
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            validate(version(), &nodes, &[], 100),
+            vec![Issue::TooManyAscendancyPoints {
+                points: nodes.len(),
+                max: ASCENDANCY_POINTS
+            }]
+        );
+    }
+}
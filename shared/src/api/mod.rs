@@ -0,0 +1,5 @@
+//! Stable types of the public JSON API.
+//!
+//! Types in a versioned module only ever receive backwards compatible changes,
+//! e.g. new optional fields. Breaking changes require a new version.
+pub mod v1;
@@ -0,0 +1,289 @@
+//! Version 1 of the public JSON API, served under `/api/v1/`.
+//!
+//! With the `schema` feature enabled, [`openapi`] generates an OpenAPI document
+//! describing all endpoints from these types.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Summary of a build.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Build {
+    /// Paste id, either `<id>` or `<user>:<id>`.
+    pub id: String,
+    pub title: String,
+    /// Unix timestamp in milliseconds, `0` if unknown.
+    pub last_modified: u64,
+    pub level: u8,
+    pub class: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ascendancy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_skill: Option<String>,
+    /// Highest passive tree version used by the build.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Stats as calculated by Path of Building.
+///
+/// Keys are the Path of Building stat names, e.g. `Life` or `CombinedDPS`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Stats {
+    pub player: BTreeMap<String, f32>,
+    pub minion: BTreeMap<String, f32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkillSet {
+    pub id: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Whether this is the skill set selected in Path of Building.
+    pub active: bool,
+    pub skills: Vec<Skill>,
+}
+
+/// A socket group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Skill {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+    pub enabled: bool,
+    /// Whether this is the main skill of the build.
+    pub main: bool,
+    pub gems: Vec<Gem>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Gem {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_id: Option<String>,
+    pub level: u8,
+    pub quality: u8,
+    pub enabled: bool,
+    pub support: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ItemSet {
+    pub id: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Whether this is the item set selected in Path of Building.
+    pub active: bool,
+    pub slots: Vec<ItemSlot>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ItemSlot {
+    /// Path of Building slot name, e.g. `Body Armour`, jewels use `Jewel`.
+    pub slot: String,
+    pub item: Item,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Item {
+    pub rarity: Rarity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub base: String,
+    pub item_level: u8,
+    pub quality: u8,
+    pub enchants: Vec<String>,
+    pub implicits: Vec<String>,
+    pub explicits: Vec<String>,
+    pub corrupted: bool,
+    /// The item in Path of Building's text format.
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Rarity {
+    Normal,
+    Magic,
+    Rare,
+    Unique,
+    Relic,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TreeSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Whether this is the tree spec selected in Path of Building.
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ascendancy_id: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Allocated passive node ids.
+    pub nodes: Vec<u32>,
+    pub mastery_effects: Vec<MasteryEffect>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MasteryEffect {
+    pub node: u32,
+    pub effect: u32,
+}
+
+/// Where the class of the build can buy the gems of all skill sets while leveling.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GemShoppingList {
+    /// Gems grouped by the first quest rewarding them to the class, ordered by act.
+    pub quests: Vec<GemQuest>,
+    /// Gems which can not be bought by the class.
+    pub unavailable: Vec<GemPurchase>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GemQuest {
+    pub act: u8,
+    pub quest: String,
+    pub npc: String,
+    pub gems: Vec<GemPurchase>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GemPurchase {
+    /// Metadata id of the gem, e.g. `Metadata/Items/Gems/SkillGemFireball`.
+    pub id: String,
+    pub name: String,
+    /// Character level required to use the gem.
+    pub level: u8,
+    /// Whether the gem is only sold to the class by Lilly Roth in act 6.
+    pub lilly_roth: bool,
+    /// Whether the gem is only sold to other classes and has to be bought with another character.
+    pub other_character: bool,
+}
+
+/// Body of every unsuccessful response.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Error {
+    pub code: u16,
+    pub message: String,
+}
+
+/// Generates the OpenAPI document for all `/api/v1/` endpoints.
+#[cfg(feature = "schema")]
+pub fn openapi() -> serde_json::Value {
+    use schemars::gen::SchemaSettings;
+    use serde_json::json;
+
+    let mut gen = SchemaSettings::openapi3().into_generator();
+
+    let endpoint = |summary: &str, schema: schemars::schema::Schema| {
+        json!({
+            "summary": summary,
+            "parameters": [{
+                "name": "id",
+                "in": "path",
+                "required": true,
+                "description": "Paste id, either `<id>` or `<user>:<id>`",
+                "schema": { "type": "string" },
+            }],
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { "application/json": { "schema": schema } },
+                },
+                "default": {
+                    "description": "Error",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Error" },
+                        },
+                    },
+                },
+            },
+        })
+    };
+
+    let paths = json!({
+        "/api/v1/paste/{id}": {
+            "get": endpoint("Build summary", gen.subschema_for::<Build>()),
+        },
+        "/api/v1/paste/{id}/stats": {
+            "get": endpoint("Player and minion stats", gen.subschema_for::<Stats>()),
+        },
+        "/api/v1/paste/{id}/skills": {
+            "get": endpoint("Skill sets", gen.subschema_for::<Vec<SkillSet>>()),
+        },
+        "/api/v1/paste/{id}/items": {
+            "get": endpoint("Item sets", gen.subschema_for::<Vec<ItemSet>>()),
+        },
+        "/api/v1/paste/{id}/tree": {
+            "get": endpoint("Passive tree specs", gen.subschema_for::<Vec<TreeSpec>>()),
+        },
+        "/api/v1/paste/{id}/gems": {
+            "get": endpoint("Gem shopping list", gen.subschema_for::<GemShoppingList>()),
+        },
+    });
+
+    gen.subschema_for::<Error>();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "pobb.in",
+            "version": "1",
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.definitions(),
+        },
+    })
+}
+
+#[cfg(all(test, feature = "schema"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openapi_references_resolve() {
+        let doc = openapi();
+        let doc = serde_json::to_string(&doc).unwrap();
+
+        let schemas = [
+            "Build",
+            "Stats",
+            "SkillSet",
+            "ItemSet",
+            "TreeSpec",
+            "GemShoppingList",
+            "Error",
+        ];
+        for schema in schemas {
+            let reference = format!("\"#/components/schemas/{schema}\"");
+            assert!(doc.contains(&reference), "missing reference to {schema}");
+            assert!(
+                doc.contains(&format!("\"{schema}\":{{")),
+                "missing definition of {schema}"
+            );
+        }
+    }
+}
@@ -108,6 +108,34 @@ impl UserPasteId {
         format!("/u/{}/{}/json", self.user, self.id)
     }
 
+    pub fn to_card_url(&self) -> String {
+        format!("/u/{}/{}/card.png", self.user, self.id)
+    }
+
+    pub fn to_tree_url(&self, index: usize) -> String {
+        format!("/u/{}/{}/trees/{index}", self.user, self.id)
+    }
+
+    pub fn to_item_trade_url(&self, item_id: u16) -> String {
+        format!("/u/{}/{}/items/{item_id}/trade", self.user, self.id)
+    }
+
+    pub fn to_revisions_url(&self) -> String {
+        format!("/u/{}/{}/revisions", self.user, self.id)
+    }
+
+    pub fn to_revision_raw_url(&self, revision: u32) -> String {
+        format!("/u/{}/{}/rev/{revision}", self.user, self.id)
+    }
+
+    pub fn to_revision_json_url(&self, revision: u32) -> String {
+        format!("/u/{}/{}/rev/{revision}/json", self.user, self.id)
+    }
+
+    pub fn to_revision_xml_url(&self, revision: u32) -> String {
+        format!("/u/{}/{}/rev/{revision}/xml", self.user, self.id)
+    }
+
     pub fn to_pob_load_url(&self) -> String {
         // TODO: maybe get rid of this format?
         format!("/pob/{}:{}", self.user, self.id)
@@ -172,6 +200,29 @@ impl PasteId {
         }
     }
 
+    pub fn to_card_url(&self) -> String {
+        match self {
+            Self::Paste(id) => format!("/{id}/card.png"),
+            Self::UserPaste(up) => up.to_card_url(),
+        }
+    }
+
+    /// Rendered passive tree of the tree spec at `index`.
+    pub fn to_tree_url(&self, index: usize) -> String {
+        match self {
+            Self::Paste(id) => format!("/{id}/trees/{index}"),
+            Self::UserPaste(up) => up.to_tree_url(index),
+        }
+    }
+
+    /// Trade site search for the item with the PoB item id `item_id`.
+    pub fn to_item_trade_url(&self, item_id: u16) -> String {
+        match self {
+            Self::Paste(id) => format!("/{id}/items/{item_id}/trade"),
+            Self::UserPaste(up) => up.to_item_trade_url(item_id),
+        }
+    }
+
     pub fn to_pob_load_url(&self) -> String {
         // TODO: maybe this is just `format!("/pob/{}", self)
         match self {
@@ -0,0 +1,245 @@
+//! Recognizes links to builds hosted on other sites.
+//!
+//! Every [`Importer`] knows the hosts it is responsible for and how to turn a
+//! link into the url of the raw export code, which can then be fetched and
+//! stored as a regular paste.
+use std::fmt;
+
+use crate::PasteId;
+
+/// Known pobb.in instances, builds can be copied between instances.
+///
+/// Other instances can be configured at build time with a comma separated list
+/// of hosts in the `POBBIN_INSTANCES` environment variable, which replaces the default.
+pub fn pobbin_instances() -> impl Iterator<Item = &'static str> {
+    option_env!("POBBIN_INSTANCES")
+        .unwrap_or("pobb.in")
+        .split(',')
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+}
+
+/// All importers, in the order they are tried.
+pub const IMPORTERS: &[Importer] = &[
+    Importer::PoeNinja,
+    Importer::Pastebin,
+    Importer::Rentry,
+    Importer::Hastebin,
+    Importer::Pobbin,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Importer {
+    PoeNinja,
+    Pastebin,
+    Rentry,
+    Hastebin,
+    Pobbin,
+}
+
+impl Importer {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PoeNinja => "poe.ninja",
+            Self::Pastebin => "pastebin.com",
+            Self::Rentry => "rentry.co",
+            Self::Hastebin => "hastebin",
+            Self::Pobbin => "pobb.in",
+        }
+    }
+
+    fn has_host(self, host: &str) -> bool {
+        let hosts: &[&str] = match self {
+            Self::PoeNinja => &["poe.ninja"],
+            Self::Pastebin => &["pastebin.com"],
+            Self::Rentry => &["rentry.co", "rentry.org"],
+            Self::Hastebin => &["hastebin.com", "hastebin.skyra.pw"],
+            Self::Pobbin => return pobbin_instances().any(|instance| instance == host),
+        };
+
+        hosts.contains(&host)
+    }
+
+    /// Extracts the id of the build from the path of a link.
+    fn parse_path(self, path: &str) -> Option<String> {
+        let segments = path.split('/').collect::<Vec<_>>();
+
+        let id = match (self, segments.as_slice()) {
+            (Self::PoeNinja, ["pob", id] | ["pob", "raw", id]) => id,
+            (Self::Pastebin, [id] | ["raw", id]) if is_pastebin_id(id) => id,
+            (Self::Rentry, [id] | [id, "raw"]) => id,
+            // Hastebin links may contain a file extension for syntax highlighting.
+            (Self::Hastebin, [id] | ["raw", id]) => id.split('.').next().unwrap_or_default(),
+            (Self::Pobbin, _) => {
+                let id: PasteId = match segments.as_slice() {
+                    [id] | [id, "raw"] => id.parse().ok()?,
+                    ["u", user, id] | ["u", user, id, "raw"] => {
+                        format!("{user}:{id}").parse().ok()?
+                    }
+                    _ => return None,
+                };
+                return Some(id.to_string());
+            }
+            _ => return None,
+        };
+
+        is_valid_id(id).then(|| id.to_string())
+    }
+
+    fn raw_url(self, host: &str, id: &str) -> String {
+        match self {
+            Self::PoeNinja => format!("https://poe.ninja/pob/raw/{id}"),
+            Self::Pastebin => format!("https://pastebin.com/raw/{id}"),
+            Self::Rentry => format!("https://{host}/{id}/raw"),
+            Self::Hastebin => format!("https://{host}/raw/{id}"),
+            Self::Pobbin => {
+                let id = id.parse::<PasteId>().expect("validated when parsing");
+                format!("https://{host}{}", id.to_raw_url())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Importer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A recognized link to a build hosted on another site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub importer: Importer,
+    host: String,
+    id: String,
+}
+
+impl Import {
+    /// Tries all [`IMPORTERS`] on a link, returns `None` if no importer recognizes it.
+    ///
+    /// The scheme is optional, e.g. `pastebin.com/abcd1234` is recognized as well.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let url = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(url);
+
+        let (host, path) = url.split_once('/').unwrap_or((url, ""));
+        let host = host.to_ascii_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let path = path.trim_end_matches('/');
+
+        IMPORTERS
+            .iter()
+            .filter(|importer| importer.has_host(host))
+            .find_map(|&importer| {
+                Some(Self {
+                    importer,
+                    host: host.to_owned(),
+                    id: importer.parse_path(path)?,
+                })
+            })
+    }
+
+    /// Builds which used to be served under a pobb.in paste id from another site.
+    ///
+    /// Pastebin ids can be used in place of a paste id, e.g. `pobb.in/abcd1234`.
+    pub fn from_paste_id(id: &PasteId) -> Option<Self> {
+        match id {
+            PasteId::Paste(id) if is_pastebin_id(id) => Some(Self {
+                importer: Importer::Pastebin,
+                host: "pastebin.com".to_owned(),
+                id: id.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Id of the build on the remote site.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Url of the raw export code.
+    pub fn raw_url(&self) -> String {
+        self.importer.raw_url(&self.host, &self.id)
+    }
+}
+
+/// Pastebin ids are always 8 alphanumeric characters.
+pub fn is_pastebin_id(candidate: &str) -> bool {
+    candidate.len() == 8 && candidate.bytes().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 90
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_url(url: &str) -> Option<String> {
+        Import::parse(url).map(|import| import.raw_url())
+    }
+
+    #[test]
+    fn recognize() {
+        assert_eq!(
+            raw_url("https://poe.ninja/pob/1a2b3"),
+            Some("https://poe.ninja/pob/raw/1a2b3".to_owned())
+        );
+        assert_eq!(
+            raw_url("https://pastebin.com/abcd1234"),
+            Some("https://pastebin.com/raw/abcd1234".to_owned())
+        );
+        assert_eq!(
+            raw_url("pastebin.com/raw/abcd1234/"),
+            Some("https://pastebin.com/raw/abcd1234".to_owned())
+        );
+        assert_eq!(
+            raw_url("https://rentry.org/my-build"),
+            Some("https://rentry.org/my-build/raw".to_owned())
+        );
+        assert_eq!(
+            raw_url("https://hastebin.skyra.pw/ixiqofuxoh.txt"),
+            Some("https://hastebin.skyra.pw/raw/ixiqofuxoh".to_owned())
+        );
+        assert_eq!(
+            raw_url("https://pobb.in/u/someone/my-build?foo=bar"),
+            Some("https://pobb.in/u/someone/my-build/raw".to_owned())
+        );
+        assert_eq!(
+            raw_url("https://www.pobb.in/abcdefg"),
+            Some("https://pobb.in/abcdefg/raw".to_owned())
+        );
+    }
+
+    #[test]
+    fn from_paste_id() {
+        let import = |id: &str| Import::from_paste_id(&id.parse().unwrap());
+
+        assert_eq!(
+            import("abcd1234").map(|import| import.raw_url()),
+            Some("https://pastebin.com/raw/abcd1234".to_owned())
+        );
+        assert_eq!(import("abcdefghijkl"), None);
+        assert_eq!(import("someone:abcd1234"), None);
+    }
+
+    #[test]
+    fn reject() {
+        assert_eq!(Import::parse("https://pastebin.com/short"), None);
+        assert_eq!(Import::parse("https://poe.ninja/builds/league"), None);
+        assert_eq!(Import::parse("https://pobb.in/a"), None);
+        assert_eq!(Import::parse("https://example.com/abcd1234"), None);
+        assert_eq!(Import::parse("https://rentry.co/../etc"), None);
+    }
+}
@@ -1,4 +1,6 @@
+pub mod api;
 pub mod id;
+pub mod import;
 pub mod model;
 mod poe;
 mod user;
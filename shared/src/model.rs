@@ -51,12 +51,357 @@ pub struct PasteSummary {
     pub private: bool,
 }
 
+/// A previous version of a user paste.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasteRevision {
+    /// Revision number, starting at `1` for the oldest revision.
+    pub revision: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Hex encoded sha1 of the paste content.
+    pub sha1: String,
+    pub last_modified: u64,
+}
+
+/// Response to a successful upload.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UploadedPaste {
+    pub id: PasteId,
+    /// Problems with the tree specs of the build, the paste is still created.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<TreeWarning>,
+}
+
+/// A problem with a tree spec, e.g. from a corrupted or hand edited export.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TreeWarning {
+    /// The tree version of the spec is not known, the spec is not validated.
+    UnsupportedVersion {
+        spec: usize,
+        version: String,
+    },
+    UnknownNode {
+        /// Index of the tree spec.
+        spec: usize,
+        node: u32,
+    },
+    InvalidMasteryEffect {
+        spec: usize,
+        node: u32,
+        effect: u32,
+    },
+    TooManyPoints {
+        spec: usize,
+        points: usize,
+        max: usize,
+    },
+    TooManyAscendancyPoints {
+        spec: usize,
+        points: usize,
+        max: usize,
+    },
+}
+
+impl std::fmt::Display for TreeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedVersion { spec, version } => {
+                write!(
+                    f,
+                    "Tree {} uses unsupported tree version {version}",
+                    spec + 1
+                )
+            }
+            Self::UnknownNode { spec, node } => {
+                write!(f, "Tree {} contains unknown node {node}", spec + 1)
+            }
+            Self::InvalidMasteryEffect { spec, node, effect } => write!(
+                f,
+                "Tree {} contains invalid mastery effect {effect} for node {node}",
+                spec + 1
+            ),
+            Self::TooManyPoints { spec, points, max } => write!(
+                f,
+                "Tree {} allocates {points} passive points, at most {max} are available",
+                spec + 1
+            ),
+            Self::TooManyAscendancyPoints { spec, points, max } => write!(
+                f,
+                "Tree {} allocates {points} ascendancy points, at most {max} are available",
+                spec + 1
+            ),
+        }
+    }
+}
+
+impl TreeWarning {
+    /// Whether the spec itself is invalid.
+    ///
+    /// Unsupported versions are only a limitation of the validation, the spec may be valid.
+    pub fn is_invalid(&self) -> bool {
+        !matches!(self, Self::UnsupportedVersion { .. })
+    }
+}
+
 impl PasteSummary {
     pub fn to_url(&self) -> String {
         self.id.to_url()
     }
 }
 
+/// Filter and sort order for a list of pastes.
+///
+/// All filters are optional, a paste has to match all set filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasteFilter {
+    pub ascendancy_or_class: Option<AscendancyOrClass>,
+    /// Case insensitive main skill name.
+    pub main_skill_name: Option<String>,
+    pub version: Option<String>,
+    /// Case insensitive substring of the title.
+    pub title: Option<String>,
+    pub sort: PasteSort,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PasteSort {
+    /// Pinned pastes first, then the most recently modified.
+    #[default]
+    Rank,
+    Newest,
+    Oldest,
+    Title,
+    AscendancyOrClass,
+    MainSkillName,
+    /// Most recent tree version first.
+    Version,
+}
+
+impl PasteSort {
+    pub const ALL: [PasteSort; 7] = [
+        Self::Rank,
+        Self::Newest,
+        Self::Oldest,
+        Self::Title,
+        Self::AscendancyOrClass,
+        Self::MainSkillName,
+        Self::Version,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rank => "rank",
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::Title => "title",
+            Self::AscendancyOrClass => "ascendancy_or_class",
+            Self::MainSkillName => "main_skill_name",
+            Self::Version => "version",
+        }
+    }
+
+    pub fn sort(&self, pastes: &mut [PasteSummary]) {
+        let newest = |a: &PasteSummary, b: &PasteSummary| b.last_modified.cmp(&a.last_modified);
+
+        match self {
+            Self::Rank => pastes.sort_unstable_by(|a, b| b.rank.cmp(&a.rank).then(newest(a, b))),
+            Self::Newest => pastes.sort_unstable_by(newest),
+            Self::Oldest => pastes.sort_unstable_by_key(|p| p.last_modified),
+            Self::Title => pastes.sort_by_cached_key(|p| p.title.to_lowercase()),
+            Self::AscendancyOrClass => pastes.sort_unstable_by(|a, b| {
+                a.ascendancy_or_class
+                    .as_str()
+                    .cmp(b.ascendancy_or_class.as_str())
+                    .then(newest(a, b))
+            }),
+            Self::MainSkillName => pastes.sort_unstable_by(|a, b| {
+                // Pastes without a main skill go last.
+                let key =
+                    |p: &PasteSummary| (p.main_skill_name.is_none(), p.main_skill_name.clone());
+                key(a).cmp(&key(b)).then(newest(a, b))
+            }),
+            Self::Version => pastes.sort_by_cached_key(|p| {
+                let version = p.version.as_deref().map(|v| {
+                    v.split('.')
+                        .map(|part| part.parse::<u32>().unwrap_or(0))
+                        .collect::<Vec<_>>()
+                });
+                (
+                    std::cmp::Reverse(version),
+                    std::cmp::Reverse(p.last_modified),
+                )
+            }),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid sort order, expected one of: rank, newest, oldest, title, ascendancy_or_class, main_skill_name, version")]
+pub struct InvalidPasteSort;
+
+impl std::str::FromStr for PasteSort {
+    type Err = InvalidPasteSort;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or(InvalidPasteSort)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidPasteFilter {
+    #[error(transparent)]
+    AscendancyOrClass(#[from] crate::poe::InvalidAscendancyOrClass),
+    #[error(transparent)]
+    Sort(#[from] InvalidPasteSort),
+}
+
+impl PasteFilter {
+    /// Parses a filter from query parameters, unknown parameters are ignored.
+    pub fn from_query_pairs<K, V>(
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, InvalidPasteFilter>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut filter = Self::default();
+
+        for (key, value) in pairs {
+            let value = value.as_ref().trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            match key.as_ref() {
+                "ascendancy_or_class" => filter.ascendancy_or_class = Some(value.parse()?),
+                "main_skill_name" => filter.main_skill_name = Some(value.to_owned()),
+                "version" => filter.version = Some(value.to_owned()),
+                "title" => filter.title = Some(value.to_owned()),
+                "sort" => filter.sort = value.parse()?,
+                _ => {}
+            }
+        }
+
+        Ok(filter)
+    }
+
+    /// Encodes the filter as a query string, without the leading `?`.
+    pub fn to_query(&self) -> String {
+        let encode = |v: &str| {
+            percent_encoding::utf8_percent_encode(v, percent_encoding::NON_ALPHANUMERIC).to_string()
+        };
+
+        let pairs = [
+            (
+                "ascendancy_or_class",
+                self.ascendancy_or_class.map(|a| a.as_str()),
+            ),
+            ("main_skill_name", self.main_skill_name.as_deref()),
+            ("version", self.version.as_deref()),
+            ("title", self.title.as_deref()),
+            (
+                "sort",
+                (self.sort != PasteSort::Rank).then(|| self.sort.as_str()),
+            ),
+        ];
+
+        pairs
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key}={}", encode(value?))))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Whether the filter neither filters nor changes the default sort order.
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn matches(&self, paste: &PasteSummary) -> bool {
+        fn eq_ignore_case(filter: Option<&str>, value: Option<&str>) -> bool {
+            match (filter, value) {
+                (None, _) => true,
+                (Some(filter), Some(value)) => value.eq_ignore_ascii_case(filter),
+                (Some(_), None) => false,
+            }
+        }
+
+        let ascendancy_or_class = match self.ascendancy_or_class {
+            Some(aoc) => aoc == paste.ascendancy_or_class,
+            None => true,
+        };
+        let title = match self.title {
+            Some(ref title) => paste.title.to_lowercase().contains(&title.to_lowercase()),
+            None => true,
+        };
+
+        ascendancy_or_class
+            && title
+            && eq_ignore_case(
+                self.main_skill_name.as_deref(),
+                paste.main_skill_name.as_deref(),
+            )
+            && eq_ignore_case(self.version.as_deref(), paste.version.as_deref())
+    }
+
+    /// Filters and sorts the pastes in place.
+    pub fn apply(&self, pastes: &mut Vec<PasteSummary>) {
+        pastes.retain(|paste| self.matches(paste));
+        self.sort.sort(pastes);
+    }
+}
+
+/// Permission granted to a personal API token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiTokenScope {
+    /// List private pastes of the token owner.
+    ReadPrivate,
+    /// Create and update pastes of the token owner.
+    Write,
+    /// Delete pastes of the token owner.
+    Delete,
+}
+
+impl ApiTokenScope {
+    pub const ALL: [ApiTokenScope; 3] = [Self::ReadPrivate, Self::Write, Self::Delete];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadPrivate => "read-private",
+            Self::Write => "write",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// A personal API token, the secret token itself is only returned once on creation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub created: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateApiToken {
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreatedApiToken {
+    /// Secret to be used as `Authorization: Bearer <token>`.
+    pub token: String,
+    #[serde(flatten)]
+    pub info: ApiToken,
+}
+
 fn is_false(v: &bool) -> bool {
     !v
 }
@@ -79,6 +424,15 @@ pub mod data {
         /// Additional gem information.
         #[serde(default, skip_serializing_if = "HashMap::is_empty")]
         pub gems: HashMap<String, Gem>,
+        /// Warnings about common mistakes in the build, see `pob::lint`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub warnings: Vec<String>,
+        /// Where to buy the gems of the build while leveling.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub gem_shopping_list: Option<GemShoppingList>,
+        /// Changes between consecutive tree specs, see `pob::diff::tree_progression`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tree_progression: Vec<TreeStep>,
     }
 
     #[derive(Default, Debug, Clone, Deserialize, Serialize)]
@@ -112,6 +466,42 @@ pub mod data {
         pub text: String,
     }
 
+    /// Changes of a tree spec compared to the previous tree spec.
+    #[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct TreeStep {
+        /// Index of the tree spec.
+        pub index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub title: Option<String>,
+        pub points: Points,
+        pub previous_points: Points,
+        #[serde(default)]
+        pub added: TreeChanges,
+        #[serde(default)]
+        pub removed: TreeChanges,
+    }
+
+    /// Passive and ascendancy points spent in a tree spec.
+    #[derive(Default, Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct Points {
+        pub passives: usize,
+        pub ascendancy: usize,
+    }
+
+    #[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+    pub struct TreeChanges {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub keystones: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub notables: Vec<String>,
+        /// Mastery effects formatted as `<mastery>: <stats>`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub mastery_effects: Vec<String>,
+        /// Number of other nodes, masteries are tracked through their effects.
+        #[serde(default)]
+        pub small: usize,
+    }
+
     #[derive(Debug, Clone, Deserialize, Serialize)]
     pub struct Gem {
         pub name: String,
@@ -126,4 +516,154 @@ pub mod data {
         pub npc: String,
         pub quest: String,
     }
+
+    /// Where the class of the build can buy its gems, see `api::v1::GemShoppingList`.
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    pub struct GemShoppingList {
+        pub quests: Vec<GemQuest>,
+        pub unavailable: Vec<GemPurchase>,
+    }
+
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    pub struct GemQuest {
+        pub act: u8,
+        pub quest: String,
+        pub npc: String,
+        pub gems: Vec<GemPurchase>,
+    }
+
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    pub struct GemPurchase {
+        pub id: String,
+        pub name: String,
+        pub level: u8,
+        pub lilly_roth: bool,
+        pub other_character: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ascendancy, Class};
+
+    fn summary(
+        id: &str,
+        title: &str,
+        asc: AscendancyOrClass,
+        version: &str,
+        mtime: u64,
+    ) -> PasteSummary {
+        PasteSummary {
+            id: id.parse().unwrap(),
+            title: title.to_owned(),
+            ascendancy_or_class: asc,
+            version: Some(version.to_owned()),
+            main_skill_name: Some("Cyclone".to_owned()),
+            last_modified: mtime,
+            rank: None,
+            private: false,
+        }
+    }
+
+    fn pastes() -> Vec<PasteSummary> {
+        vec![
+            summary(
+                "aaaaa",
+                "Slayer Cyclone",
+                Ascendancy::Slayer.into(),
+                "3.9",
+                1,
+            ),
+            summary("bbbbb", "Witch Start", Class::Witch.into(), "3.19", 3),
+            summary(
+                "ccccc",
+                "cyclone league start",
+                Ascendancy::Slayer.into(),
+                "3.20",
+                2,
+            ),
+        ]
+    }
+
+    fn ids(pastes: &[PasteSummary]) -> Vec<&str> {
+        pastes.iter().map(|p| p.id.id()).collect()
+    }
+
+    #[test]
+    fn paste_filter_query_roundtrip() {
+        let filter = PasteFilter {
+            ascendancy_or_class: Some(Ascendancy::Slayer.into()),
+            main_skill_name: Some("Cyclone".to_owned()),
+            version: None,
+            title: Some("league start".to_owned()),
+            sort: PasteSort::Version,
+        };
+        let query = filter.to_query();
+        assert_eq!(
+            query,
+            "ascendancy_or_class=Slayer&main_skill_name=Cyclone&title=league%20start&sort=version"
+        );
+
+        let pairs = query.split('&').map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap();
+            let value = percent_encoding::percent_decode_str(value).decode_utf8_lossy();
+            (key.to_owned(), value.into_owned())
+        });
+        assert_eq!(filter, PasteFilter::from_query_pairs(pairs).unwrap());
+
+        assert!(PasteFilter::from_query_pairs([("sort", "foo")]).is_err());
+        assert!(PasteFilter::from_query_pairs([("ascendancy_or_class", "Foo")]).is_err());
+        assert!(PasteFilter::from_query_pairs([("title", "")])
+            .unwrap()
+            .is_default());
+    }
+
+    #[test]
+    fn paste_filter_apply() {
+        let mut p = pastes();
+        PasteFilter::default().apply(&mut p);
+        assert_eq!(ids(&p), ["bbbbb", "ccccc", "aaaaa"]);
+
+        let mut p = pastes();
+        PasteFilter {
+            title: Some("CYCLONE".to_owned()),
+            sort: PasteSort::Oldest,
+            ..Default::default()
+        }
+        .apply(&mut p);
+        assert_eq!(ids(&p), ["aaaaa", "ccccc"]);
+
+        let mut p = pastes();
+        PasteFilter {
+            ascendancy_or_class: Some(Class::Witch.into()),
+            ..Default::default()
+        }
+        .apply(&mut p);
+        assert_eq!(ids(&p), ["bbbbb"]);
+
+        let mut p = pastes();
+        PasteFilter {
+            version: Some("3.19".to_owned()),
+            main_skill_name: Some("cyclone".to_owned()),
+            ..Default::default()
+        }
+        .apply(&mut p);
+        assert_eq!(ids(&p), ["bbbbb"]);
+    }
+
+    #[test]
+    fn paste_sort() {
+        let mut p = pastes();
+        PasteSort::Version.sort(&mut p);
+        assert_eq!(ids(&p), ["ccccc", "bbbbb", "aaaaa"]);
+
+        let mut p = pastes();
+        PasteSort::Title.sort(&mut p);
+        assert_eq!(ids(&p), ["ccccc", "aaaaa", "bbbbb"]);
+
+        let mut p = pastes();
+        PasteSort::AscendancyOrClass.sort(&mut p);
+        assert_eq!(ids(&p), ["ccccc", "aaaaa", "bbbbb"]);
+    }
 }
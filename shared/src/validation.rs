@@ -64,6 +64,15 @@ pub mod user {
         }
     }
 
+    #[must_use]
+    pub fn is_valid_token_name(name: &str) -> Validation {
+        match name.trim().len() {
+            0 => Invalid("Token name is required"),
+            1..=50 => Valid,
+            _ => Invalid("Token name too long"),
+        }
+    }
+
     #[must_use]
     pub fn is_valid_custom_id(id: &str) -> Validation {
         match id.len() {
@@ -104,6 +113,14 @@ pub mod user {
             assert!(is_valid_custom_title("aks jda;klsdäö").is_valid());
         }
 
+        #[test]
+        fn test_token_name() {
+            assert!(!is_valid_token_name("").is_valid());
+            assert!(!is_valid_token_name("  ").is_valid());
+            assert!(is_valid_token_name("CI").is_valid());
+            assert!(!is_valid_token_name(&"a".repeat(51)).is_valid());
+        }
+
         #[test]
         fn test_id_length() {
             for i in 0..200 {
@@ -3,10 +3,19 @@ use std::{borrow::Cow, num::NonZeroU8, rc::Rc, time::Duration};
 use pob::{PathOfBuilding, PathOfBuildingExt, SerdePathOfBuilding};
 use sentry::MetricUnit;
 use serde::{Deserialize, Serialize};
-use shared::{model::PasteMetadata, validation, PasteId, User, UserPasteId};
+use shared::{
+    import::Import,
+    model::{
+        ApiToken, ApiTokenScope, CreateApiToken, CreatedApiToken, PasteFilter, PasteMetadata,
+        UploadedPaste,
+    },
+    validation, Id, PasteId, User, UserPasteId,
+};
 
 use crate::{
-    consts, crypto, poe_api,
+    api_v1::{self, Resource},
+    cache::PasteResources,
+    card, consts, poe_api,
     request_context::RequestContext,
     response,
     route::{self, DeleteEndpoints, GetEndpoints, PostEndpoints},
@@ -54,12 +63,13 @@ pub async fn handle(rctx: &mut RequestContext, route: route::Api) -> response::R
     // Currently this can happen on some API endpoints related to login/auth,
     // these are handled as API endpoints but are user facing, meaning
     // the user would expect a proper error page not just some JSON.
-    let is_user_api = matches!(&route, Get(Login) | Get(Oauht2Poe));
+    let is_user_api = matches!(&route, Get(Login) | Get(LoginCharacter(_)) | Get(Oauht2Poe));
 
     let r = match route {
         // Get
         Get(Oembed) => handle_oembed(rctx).await,
         Get(User(user)) => handle_user(rctx, user).await,
+        Get(Tokens) => handle_list_tokens(rctx).await,
         Get(PobPaste(LenientId(id))) => handle_download_text(rctx, id).await,
         Get(PobUserPaste(user, LenientId(id))) => {
             handle_download_text(rctx, UserPasteId { user, id }.into()).await
@@ -72,17 +82,51 @@ pub async fn handle(rctx: &mut RequestContext, route: route::Api) -> response::R
         Get(UserPasteJson(user, id)) => {
             handle_download_json(rctx, UserPasteId { user, id }.into()).await
         }
+        Get(PasteCard(id)) => card::handle_card(rctx, PasteId::Paste(id)).await,
+        Get(UserPasteCard(user, id)) => {
+            card::handle_card(rctx, UserPasteId { user, id }.into()).await
+        }
         Get(PasteXml(id)) => handle_download_xml(rctx, PasteId::Paste(id)).await,
         Get(UserPasteXml(user, id)) => {
             handle_download_xml(rctx, UserPasteId { user, id }.into()).await
         }
+        Get(PasteTree(id, n)) => handle_tree(rctx, PasteId::Paste(id), n).await,
+        Get(UserPasteTree(user, id, n)) => {
+            handle_tree(rctx, UserPasteId { user, id }.into(), n).await
+        }
+        Get(PasteItemTrade(id, n)) => handle_item_trade(rctx, PasteId::Paste(id), n).await,
+        Get(UserPasteItemTrade(user, id, n)) => {
+            handle_item_trade(rctx, UserPasteId { user, id }.into(), n).await
+        }
+        Get(UserPasteRevisions(user, id)) => handle_revisions(rctx, UserPasteId { user, id }).await,
+        Get(UserPasteRevision(user, id, rev)) => {
+            handle_download_revision_text(rctx, UserPasteId { user, id }, rev).await
+        }
+        Get(UserPasteRevisionJson(user, id, rev)) => {
+            handle_download_revision_json(rctx, UserPasteId { user, id }, rev).await
+        }
+        Get(UserPasteRevisionXml(user, id, rev)) => {
+            handle_download_revision_xml(rctx, UserPasteId { user, id }, rev).await
+        }
+        Get(V1OpenApi) => api_v1::handle_openapi(),
+        Get(V1Build(id)) => api_v1::handle_paste(rctx, id, Resource::Build).await,
+        Get(V1Stats(id)) => api_v1::handle_paste(rctx, id, Resource::Stats).await,
+        Get(V1Skills(id)) => api_v1::handle_paste(rctx, id, Resource::Skills).await,
+        Get(V1Items(id)) => api_v1::handle_paste(rctx, id, Resource::Items).await,
+        Get(V1Tree(id)) => api_v1::handle_paste(rctx, id, Resource::Tree).await,
+        Get(V1Gems(id)) => api_v1::handle_paste(rctx, id, Resource::Gems).await,
         Get(Login) => handle_login(rctx).await,
+        Get(LoginCharacter(name)) => handle_login_character(rctx, name).await,
         Get(Oauht2Poe) => handle_oauth2_poe(rctx).await,
         // Post
         Post(Upload) => handle_upload(rctx).await,
         Post(PobUpload) => handle_pob_upload(rctx).await,
+        Post(PostEndpoints::Import) => handle_import(rctx).await,
+        Post(CreateToken) => handle_create_token(rctx).await,
+        Post(UndeletePaste(id)) => handle_undelete_paste(rctx, id).await,
         // Delete
         Delete(DeletePaste(id)) => handle_delete_paste(rctx, id).await,
+        Delete(DeleteToken(id)) => handle_delete_token(rctx, id).await,
         // Not Found Routes - these should never happen,
         // but they are there because sycamore_router requires them.
         Get(GetEndpoints::NotFound)
@@ -193,14 +237,226 @@ async fn handle_download_xml(rctx: &RequestContext, id: PasteId) -> Result<Respo
         .result()
 }
 
+#[tracing::instrument(skip(rctx))]
+async fn handle_tree(rctx: &RequestContext, id: PasteId, index: u16) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get(&id)
+        .await?
+        .ok_or_else(|| Error::NotFound("paste", id.to_string()))?;
+
+    let pob = SerdePathOfBuilding::from_export(&paste.content)
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+
+    let specs = pob.tree_specs();
+    let spec = specs
+        .get(usize::from(index))
+        .ok_or_else(|| Error::NotFound("tree", index.to_string()))?;
+
+    Response::ok()
+        .meta_paste(id, &paste)
+        .body(app::render_tree(spec))
+        .content_type("image/svg+xml")
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_item_trade(rctx: &RequestContext, id: PasteId, item_id: u16) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get(&id)
+        .await?
+        .ok_or_else(|| Error::NotFound("paste", id.to_string()))?;
+
+    let pob = SerdePathOfBuilding::from_export(&paste.content)
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+
+    let item = pob
+        .item_by_id(item_id)
+        .ok_or_else(|| Error::NotFound("item", item_id.to_string()))?;
+    let item = pob::Item::parse(item).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    Response::ok()
+        .meta_paste(id, &paste)
+        .json(&pob::trade::search(&item))
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_revisions(rctx: &RequestContext, id: UserPasteId) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let revisions = storage.list_revisions(&id).await?;
+
+    let etag = revisions
+        .last()
+        .map(|r| format!("{}-{}", r.revision, r.sha1))
+        .unwrap_or_else(|| "empty".to_owned());
+
+    Response::ok()
+        .json(&revisions)
+        .etag(Etag::strong(&etag))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_download_revision_text(
+    rctx: &RequestContext,
+    id: UserPasteId,
+    revision: u32,
+) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get_revision(&id, revision)
+        .await?
+        .ok_or_else(|| Error::NotFound("revision", format!("{id}@{revision}")))?;
+
+    Response::ok()
+        .meta_paste(id, &paste)
+        .body(paste.content)
+        .content_type("text/plain")
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_download_revision_json(
+    rctx: &RequestContext,
+    id: UserPasteId,
+    revision: u32,
+) -> Result<Response> {
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let (meta, paste) = pastes
+        .get_revision(&id, revision)
+        .await?
+        .ok_or_else(|| Error::NotFound("revision", format!("{id}@{revision}")))?;
+
+    Response::ok()
+        .json(&paste)
+        .meta_paste(id, paste)
+        .content_type("application/json")
+        .etag(Etag::strong(&meta.etag))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_download_revision_xml(
+    rctx: &RequestContext,
+    id: UserPasteId,
+    revision: u32,
+) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get_revision(&id, revision)
+        .await?
+        .ok_or_else(|| Error::NotFound("revision", format!("{id}@{revision}")))?;
+
+    let content = pob::decompress(&paste.content).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+    Response::ok()
+        .meta_paste(id, &paste)
+        .body(content)
+        .content_type("application/xml")
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
 #[tracing::instrument(skip(rctx))]
 async fn handle_delete_paste(rctx: &RequestContext, id: PasteId) -> Result<Response> {
+    let id = owned_user_paste(rctx, id)?;
+
+    // Cached resources of the paste are only known from its content.
     let storage = rctx.inject::<crate::storage::Storage>();
-    storage.delete(&id).await?;
-    crate::cache::on_paste_change(rctx, id);
+    let resources = storage
+        .get(&id.clone().into())
+        .await?
+        .map(|paste| PasteResources::of_export(&paste.content))
+        .unwrap_or_default();
+    // Revision numbers start at 1 again when a paste with the same id is created,
+    // cached revisions of the deleted paste must not be served for it.
+    let revisions = storage
+        .list_revisions(&id)
+        .await?
+        .iter()
+        .map(|r| r.revision)
+        .max()
+        .unwrap_or(0);
+    let resources = resources.with_revisions(revisions);
+
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    pastes.delete(&id).await?;
+    crate::cache::on_paste_change(rctx, id.into(), resources);
     Ok(Response::ok())
 }
 
+#[tracing::instrument(skip(rctx))]
+async fn handle_undelete_paste(rctx: &RequestContext, id: PasteId) -> Result<Response> {
+    let id = owned_user_paste(rctx, id)?;
+
+    let storage = rctx.inject::<crate::storage::Storage>();
+    if !storage
+        .restore(&id, worker::Date::now().as_millis())
+        .await?
+    {
+        return Err(Error::NotFound("deleted paste", id.to_string()));
+    }
+
+    let id = PasteId::from(id);
+    let response = Response::ok().json(&id);
+    // Resources of the paste were purged when it was deleted.
+    crate::cache::on_paste_change(rctx, id, PasteResources::default());
+    Ok(response)
+}
+
+/// Makes sure `id` is a user paste owned by the user the request is authorized for.
+fn owned_user_paste(rctx: &RequestContext, id: PasteId) -> Result<UserPasteId> {
+    let id = match id {
+        PasteId::UserPaste(id) => id,
+        // Anonymous pastes are content addressed and shared by everyone who uploaded
+        // the same build, they are not owned by anyone and cannot be deleted.
+        PasteId::Paste(_) => {
+            tracing::warn!("attempt to delete anonymous paste");
+            return Err(Error::AccessDenied);
+        }
+    };
+
+    let session = rctx.authorized(ApiTokenScope::Delete);
+    validate_access!(session.map(|s| &s.name) == Some(&id.user));
+
+    Ok(id)
+}
+
 #[derive(Deserialize)]
 struct UploadRequest {
     /// Existing id to update a paste.
@@ -225,6 +481,10 @@ struct UploadRequest {
     #[serde(default)]
     private: bool,
 
+    /// Reject the upload instead of warning about invalid tree specs.
+    #[serde(default)]
+    strict: bool,
+
     content: String,
 }
 
@@ -236,13 +496,27 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
     tracing::info!(?data.id, data.as_user, ?data.title, ?data.custom_id, size = content.len(), "upload");
     sentry::add_attachment_plain(content.clone(), "pob.txt");
 
-    let pob = validate_pob(rctx.is_logged_in(), &content)?;
+    let pob = validate_pob(rctx.authorized(ApiTokenScope::Write).is_some(), &content)?;
+    let warnings = crate::pastes::validate_trees(&pob);
+    let invalid = warnings
+        .iter()
+        .filter(|w| w.is_invalid())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if !invalid.is_empty() {
+        tracing::info!(?warnings, "invalid tree specs");
+        sentry::counter(Counters::PobInvalidTree)
+            .inc(1)
+            .tag("strict", if data.strict { "true" } else { "false" });
+    }
+    // Specs of unsupported versions are not validated, they are not rejected either.
+    if data.strict && !invalid.is_empty() {
+        return Err(Error::BadRequest(invalid.join(", ")));
+    }
     let mut metadata = to_metadata(&pob);
 
-    let sha1 = crypto::sha1(&content).await?;
-
     let id = if data.as_user {
-        let session = rctx.session().ok_or_else(|| {
+        let session = rctx.authorized(ApiTokenScope::Write).ok_or_else(|| {
             tracing::warn!("missing user session");
             Error::AccessDenied
         })?;
@@ -263,7 +537,7 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
                 "Custom id does not match paste id"
             );
 
-            id
+            Some(id.unwrap_user())
         } else {
             let id = match data.custom_id {
                 Some(id) => id,
@@ -271,11 +545,10 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
             };
             validate_v!(validation::user::is_valid_custom_id(&id));
 
-            UserPasteId {
+            Some(UserPasteId {
                 user: session.name.clone(),
                 id: id.try_into()?,
-            }
-            .into()
+            })
         }
     } else {
         validate_access!(data.id.is_none());
@@ -284,17 +557,22 @@ async fn handle_upload(rctx: &mut RequestContext) -> Result<Response> {
         // validate!(data.title.is_none(), "Cannot set title");
         // validate!(data.custom_id.is_none(), "Cannot set custom id");
 
-        PasteId::Paste(utils::hash_to_short_id(&sha1))
+        None
     };
 
-    tracing::debug!("--> uploading paste '{}'", id);
-    let storage = rctx.inject::<crate::storage::Storage>();
-    storage.put(&id, &sha1, &content, Some(&metadata)).await?;
-    tracing::debug!("<-- paste uploaded");
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let uploaded = pastes.upload(id, &content, &metadata).await?;
+    let id = uploaded.id;
 
-    let response = Response::ok().json(&id).meta_paste(&id, metadata);
+    let response = Response::ok()
+        .json(&UploadedPaste {
+            id: id.clone(),
+            warnings,
+        })
+        .meta_paste(&id, metadata);
 
-    crate::cache::on_paste_change(rctx, id);
+    let resources = PasteResources::of(&pob).union(uploaded.replaced);
+    crate::cache::on_paste_change(rctx, id, resources);
 
     Ok(response)
 }
@@ -306,28 +584,83 @@ async fn handle_pob_upload(rctx: &mut RequestContext) -> Result<Response> {
     tracing::info!(size = data.len(), "pob upload");
     sentry::add_attachment_plain(data.clone(), "pob.txt");
 
-    let pob = validate_pob(rctx.is_logged_in(), &data)?;
+    let pob = validate_pob(rctx.authorized(ApiTokenScope::Write).is_some(), &data)?;
     let metadata = to_metadata(&pob);
 
-    let sha1 = crypto::sha1(&data).await?;
-    let id = PasteId::Paste(utils::hash_to_short_id(&sha1));
+    // Uploads authenticated with an API token are published to the token owner.
+    let id = if rctx.bearer_token().is_some() {
+        let session = rctx.authorized(ApiTokenScope::Write).ok_or_else(|| {
+            tracing::warn!("api token missing write scope");
+            Error::AccessDenied
+        })?;
 
-    tracing::debug!("--> uploading paste '{}'", id);
-    let storage = rctx.inject::<crate::storage::Storage>();
-    storage.put(&id, &sha1, &data, Some(&metadata)).await?;
-    tracing::debug!("<-- paste uploaing ...");
+        Some(UserPasteId {
+            user: session.name.clone(),
+            id: utils::random_string::<9>()?.try_into()?,
+        })
+    } else {
+        None
+    };
+
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let uploaded = pastes.upload(id, &data, &metadata).await?;
+    let id = uploaded.id;
 
     let response = Response::ok()
         .body(id.to_string())
         .meta_paste(&id, metadata);
 
-    crate::cache::on_paste_change(rctx, id);
+    let resources = PasteResources::of(&pob).union(uploaded.replaced);
+    crate::cache::on_paste_change(rctx, id, resources);
+
+    Ok(response)
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportRequest {
+    url: String,
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_import(rctx: &mut RequestContext) -> Result<Response> {
+    let data = rctx.req_mut().json::<ImportRequest>().await?;
+
+    let Some(import) = Import::parse(&data.url) else {
+        return Err(Error::BadRequest("Unsupported build link".to_owned()));
+    };
+    tracing::info!(importer = %import.importer, id = import.id(), "import");
+
+    let Some(content) = crate::import::fetch(&import).await? else {
+        return Err(Error::NotFound("build", data.url));
+    };
+    let content: Rc<[u8]> = content.into_bytes().into();
+    sentry::add_attachment_plain(content.clone(), "pob.txt");
+
+    let pob = validate_pob(rctx.authorized(ApiTokenScope::Write).is_some(), &content)?;
+    let metadata = to_metadata(&pob);
+
+    sentry::counter(Counters::PobImport)
+        .inc(1)
+        .tag("importer", import.importer.name());
+
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let uploaded = pastes.upload(None, &content, &metadata).await?;
+    let id = uploaded.id;
+
+    let response = Response::ok().json(&id).meta_paste(&id, metadata);
+
+    let resources = PasteResources::of(&pob).union(uploaded.replaced);
+    crate::cache::on_paste_change(rctx, id, resources);
 
     Ok(response)
 }
 
-fn validate_pob(is_logged_in: bool, data: &[u8]) -> Result<SerdePathOfBuilding> {
-    let limit = if is_logged_in {
+/// Parses and validates an uploaded build.
+///
+/// Users authorized to write pastes (a session or a token with the write scope)
+/// may upload larger builds.
+fn validate_pob(can_write: bool, data: &[u8]) -> Result<SerdePathOfBuilding> {
+    let limit = if can_write {
         consts::MAX_UPLOAD_SIZE_LOGGED_IN
     } else {
         consts::MAX_UPLOAD_SIZE
@@ -375,8 +708,16 @@ fn to_metadata(pob: &SerdePathOfBuilding) -> PasteMetadata {
 #[tracing::instrument(skip(rctx))]
 async fn handle_user(rctx: &RequestContext, user: User) -> Result<Response> {
     let pastes = rctx.inject::<crate::pastes::Pastes>();
-    let session = rctx.session();
-    let (meta, pastes) = pastes.list_pastes(session, &user).await?;
+    let session = rctx.authorized(ApiTokenScope::ReadPrivate);
+
+    let filter = PasteFilter::from_query_pairs(rctx.url()?.query_pairs())
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let (meta, pastes) = pastes.list_pastes(session, &user, &filter).await?;
+
+    // Filtered results are not cached, cache invalidation only knows about the plain URL.
+    if !filter.is_default() {
+        return Response::ok().json(&pastes).meta_list(user).result();
+    }
 
     Response::ok()
         .json(&pastes)
@@ -390,18 +731,81 @@ async fn handle_user(rctx: &RequestContext, user: User) -> Result<Response> {
         .result()
 }
 
+#[tracing::instrument(skip(rctx))]
+async fn handle_list_tokens(rctx: &RequestContext) -> Result<Response> {
+    // Tokens can only be managed from a browser session, never with another token.
+    let session = rctx.session().ok_or(Error::AccessDenied)?;
+
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let tokens = storage.list_tokens(&session.name).await?;
+
+    Response::ok().json(&tokens).result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_create_token(rctx: &mut RequestContext) -> Result<Response> {
+    let data = rctx.req_mut().json::<CreateApiToken>().await?;
+    let session = rctx.session().ok_or(Error::AccessDenied)?;
+
+    validate_v!(validation::user::is_valid_token_name(&data.name));
+    validate!(!data.scopes.is_empty(), "At least one scope is required");
+
+    let info = ApiToken {
+        id: utils::random_string::<9>()?,
+        name: data.name.trim().to_owned(),
+        scopes: data.scopes,
+        created: worker::Date::now().as_millis(),
+    };
+
+    let dangerous = rctx.inject::<crate::dangerous::Dangerous>();
+    let token = crate::tokens::sign(&dangerous, &session.name, &info).await?;
+
+    let storage = rctx.inject::<crate::storage::Storage>();
+    storage.put_token(&session.name, &info).await?;
+
+    tracing::info!(id = %info.id, scopes = ?info.scopes, "created api token");
+
+    Response::ok()
+        .json(&CreatedApiToken { token, info })
+        .result()
+}
+
+#[tracing::instrument(skip(rctx))]
+async fn handle_delete_token(rctx: &RequestContext, id: Id) -> Result<Response> {
+    let session = rctx.session().ok_or(Error::AccessDenied)?;
+
+    let storage = rctx.inject::<crate::storage::Storage>();
+    storage.delete_token(&session.name, &id).await?;
+
+    Ok(Response::ok())
+}
+
 #[tracing::instrument(skip(rctx))]
 async fn handle_login(rctx: &RequestContext) -> Result<Response> {
+    redirect_to_login(rctx, None)
+}
+
+/// Logs in with access to the characters of the account,
+/// the character is snapshotted once the user returns from the OAuth flow.
+#[tracing::instrument(skip(rctx))]
+async fn handle_login_character(rctx: &RequestContext, name: String) -> Result<Response> {
+    validate!(is_valid_character_name(&name), "Invalid character name");
+    redirect_to_login(rctx, Some(&name))
+}
+
+fn redirect_to_login(rctx: &RequestContext, character: Option<&str>) -> Result<Response> {
     let req_url = rctx.url()?;
     let host = crate::utils::if_develop!("preview.pobb.in", req_url.host_str().unwrap());
 
-    let state = create_oauth_state(&req_url, rctx.referrer().as_ref())?;
+    let state = create_oauth_state(&req_url, rctx.referrer().as_ref(), character)?;
     let redirect_uri = format!("https://{host}/oauth2/authorization/poe");
-    let login_uri = rctx.inject::<crate::poe_api::Oauth>().get_login_url(
-        &redirect_uri,
-        &state,
-        consts::OAUTH_SCOPE,
-    );
+    let scope = match character {
+        Some(_) => consts::OAUTH_SCOPE_CHARACTERS,
+        None => consts::OAUTH_SCOPE,
+    };
+    let login_uri =
+        rctx.inject::<crate::poe_api::Oauth>()
+            .get_login_url(&redirect_uri, &state, scope);
 
     tracing::info!(%redirect_uri, %state, "redirecting for login");
 
@@ -445,9 +849,8 @@ async fn handle_oauth2_poe(rctx: &RequestContext) -> Result<Response> {
     let oauth = rctx.inject::<crate::poe_api::Oauth>();
     let token = oauth.fetch_token(&grant.code).await?;
 
-    let profile = poe_api::PoeApi::new(token.access_token)
-        .fetch_profile()
-        .await?;
+    let api = poe_api::PoeApi::new(token.access_token);
+    let profile = api.fetch_profile().await?;
 
     sentry::update_username(&profile.name);
 
@@ -460,18 +863,90 @@ async fn handle_oauth2_poe(rctx: &RequestContext) -> Result<Response> {
         .await?;
 
     sentry::counter(Counters::ApiLoginSuccess).inc(1);
-    Response::redirect_temp(redirect_from_oauth_state(&grant.state))
+
+    let redirect = match character_from_oauth_state(&grant.state) {
+        Some(character) => {
+            let id = snapshot_character(rctx, &api, &user.name, character).await?;
+            Cow::Owned(id.to_url())
+        }
+        None => Cow::Borrowed(redirect_from_oauth_state(&grant.state)),
+    };
+
+    Response::redirect_temp(&redirect)
         .delete_state_cookie()
         .new_session(&session)
         .result()
 }
 
-fn create_oauth_state(req_url: &url::Url, referrer: Option<&url::Url>) -> Result<String> {
+/// Stores a character of the logged in user as a new user paste.
+async fn snapshot_character(
+    rctx: &RequestContext,
+    api: &poe_api::PoeApi,
+    user: &User,
+    name: &str,
+) -> Result<PasteId> {
+    let character = api.fetch_character(name).await?;
+
+    let pob = SerdePathOfBuilding::from_character(&character)
+        .map_err(|err| Error::Error(format!("failed to convert character: {err}")))?;
+    let content = pob
+        .to_export()
+        .map_err(|err| Error::Error(format!("failed to convert character: {err}")))?;
+    sentry::add_attachment_plain(content.clone().into_bytes().into(), "pob.txt");
+
+    let pob = validate_pob(true, content.as_bytes())?;
+    let mut metadata = to_metadata(&pob);
+    metadata.title = match &character.league {
+        Some(league) => format!("{} ({league})", character.name),
+        None => character.name.clone(),
+    };
+
+    sentry::counter(Counters::PobCharacter)
+        .inc(1)
+        .tag("class", pob.class().as_str());
+
+    let id = UserPasteId {
+        user: user.clone(),
+        id: utils::random_string::<9>()?.try_into()?,
+    };
+
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+    let uploaded = pastes
+        .upload(Some(id), content.as_bytes(), &metadata)
+        .await?;
+
+    let resources = PasteResources::of(&pob).union(uploaded.replaced);
+    crate::cache::on_paste_change(rctx, uploaded.id.clone(), resources);
+
+    Ok(uploaded.id)
+}
+
+/// Character names consist of letters and underscores.
+fn is_valid_character_name(name: &str) -> bool {
+    (3..=23).contains(&name.len()) && name.bytes().all(|b| b.is_ascii_alphabetic() || b == b'_')
+}
+
+/// Creates the OAuth state, `<random>[:<character>].<path>`.
+fn create_oauth_state(
+    req_url: &url::Url,
+    referrer: Option<&url::Url>,
+    character: Option<&str>,
+) -> Result<String> {
     let path = referrer
         .filter(|url| url.host_str() == req_url.host_str())
         .map(|url| &url[url::Position::BeforePath..])
         .unwrap_or("/");
-    Ok(format!("{}.{}", utils::random_string::<12>()?, path))
+    let random = utils::random_string::<12>()?;
+    match character {
+        Some(character) => Ok(format!("{random}:{character}.{path}")),
+        None => Ok(format!("{random}.{path}")),
+    }
+}
+
+fn character_from_oauth_state(state: &str) -> Option<&str> {
+    let (prefix, _) = state.split_once('.')?;
+    let (_, character) = prefix.split_once(':')?;
+    is_valid_character_name(character).then_some(character)
 }
 
 fn redirect_from_oauth_state(state: &str) -> &str {
@@ -0,0 +1,132 @@
+//! Public, versioned JSON API.
+//!
+//! Response types are defined in [`shared::api::v1`] and must stay backwards compatible.
+use pob::SerdePathOfBuilding;
+use shared::{api::v1, model::data, PasteId};
+
+use crate::{
+    consts,
+    request_context::RequestContext,
+    utils::{CacheControl, Etag},
+    Error, Response, Result,
+};
+
+/// Part of a build returned by a paste endpoint.
+#[derive(Debug, Clone, Copy)]
+pub enum Resource {
+    Build,
+    Stats,
+    Skills,
+    Items,
+    Tree,
+    Gems,
+}
+
+impl Resource {
+    /// Resources of a paste, purged from the cache when the paste changes.
+    pub const ALL: [Self; 6] = [
+        Self::Build,
+        Self::Stats,
+        Self::Skills,
+        Self::Items,
+        Self::Tree,
+        Self::Gems,
+    ];
+
+    /// URL of the resource of the paste `id`.
+    pub fn url(self, id: &PasteId) -> String {
+        let suffix = match self {
+            Self::Build => "",
+            Self::Stats => "/stats",
+            Self::Skills => "/skills",
+            Self::Items => "/items",
+            Self::Tree => "/tree",
+            Self::Gems => "/gems",
+        };
+        format!("/api/v1/paste/{id}{suffix}")
+    }
+}
+
+#[tracing::instrument(skip(rctx))]
+pub async fn handle_paste(
+    rctx: &RequestContext,
+    id: PasteId,
+    resource: Resource,
+) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get(&id)
+        .await?
+        .ok_or_else(|| Error::NotFound("paste", id.to_string()))?;
+
+    let pob = SerdePathOfBuilding::from_export(&paste.content)
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+
+    let response = match resource {
+        Resource::Build => {
+            let mut build = pob::api::build(&pob);
+            build.id = id.to_string();
+            build.title = match paste.metadata {
+                Some(ref metadata) => metadata.title.clone(),
+                None => app::pob::title(&pob),
+            };
+            build.last_modified = paste.last_modified;
+            Response::ok().json(&build)
+        }
+        Resource::Stats => Response::ok().json(&pob::api::stats(&pob)),
+        Resource::Skills => Response::ok().json(&pob::api::skill_sets(&pob)),
+        Resource::Items => Response::ok().json(&pob::api::item_sets(&pob)),
+        Resource::Tree => Response::ok().json(&pob::api::tree_specs(&pob)),
+        Resource::Gems => {
+            let list = crate::pastes::gem_shopping_list(&pob);
+            Response::ok().json(&gem_shopping_list(list))
+        }
+    };
+
+    response
+        .meta_paste(id, &paste)
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+fn gem_shopping_list(list: data::GemShoppingList) -> v1::GemShoppingList {
+    let purchase = |purchase: data::GemPurchase| v1::GemPurchase {
+        id: purchase.id,
+        name: purchase.name,
+        level: purchase.level,
+        lilly_roth: purchase.lilly_roth,
+        other_character: purchase.other_character,
+    };
+
+    v1::GemShoppingList {
+        quests: list
+            .quests
+            .into_iter()
+            .map(|quest| v1::GemQuest {
+                act: quest.act,
+                quest: quest.quest,
+                npc: quest.npc,
+                gems: quest.gems.into_iter().map(purchase).collect(),
+            })
+            .collect(),
+        unavailable: list.unavailable.into_iter().map(purchase).collect(),
+    }
+}
+
+#[tracing::instrument]
+pub fn handle_openapi() -> Result<Response> {
+    Response::ok()
+        .json(&shared::api::v1::openapi())
+        .cache(
+            CacheControl::default()
+                .public()
+                .max_age(consts::CACHE_A_BIT)
+                .s_max_age(consts::CACHE_A_BIT),
+        )
+        .result()
+}
@@ -20,7 +20,7 @@ pub async fn handle_err(err: crate::Error) -> Response {
 }
 
 async fn handle_inner(rctx: &RequestContext, route: app::Route) -> Result<Response> {
-    let (info, ctx) = build_context(rctx, route).await.unwrap_or_else(|err| {
+    let (mut info, ctx) = build_context(rctx, route).await.unwrap_or_else(|err| {
         tracing::warn!("app error: {err:?}");
         sentry::capture_err(&err, err.level());
         let err = match err {
@@ -31,6 +31,11 @@ async fn handle_inner(rctx: &RequestContext, route: app::Route) -> Result<Respon
         (ResponseInfo::default(), app::Context::error(err))
     });
 
+    info.origin = rctx
+        .url()
+        .ok()
+        .map(|url| url.origin().ascii_serialization());
+
     if let Some(location) = info.redirect {
         return Ok(Response::redirect_perm(&location));
     }
@@ -46,6 +51,7 @@ async fn render(info: ResponseInfo, ctx: app::Context) -> Response {
         meta: resp_ctx.meta.unwrap_or_default(),
         prefetch: resp_ctx.prefetch,
         preload: resp_ctx.preload,
+        origin: info.origin.unwrap_or_default(),
     });
 
     // Not sure if I like that, this requries trunk to run before building the worker.
@@ -84,7 +90,9 @@ async fn build_context(
         User(user) => {
             let pastes = rctx.inject::<crate::pastes::Pastes>();
             let session = rctx.session();
-            let (meta, pastes) = pastes.list_pastes(session, &user).await?;
+            let (meta, pastes) = pastes
+                .list_pastes(session, &user, &Default::default())
+                .await?;
 
             let info = ResponseInfo {
                 etag: Some(meta.etag),
@@ -102,6 +110,7 @@ async fn build_context(
             let location = UserPasteId { user, id }.to_paste_url();
             (ResponseInfo::redirect(location), Context::not_found())
         }
+        Compare(a, b) => compare_page(rctx, a, b).await?,
     };
 
     Ok((info, ctx))
@@ -133,11 +142,42 @@ async fn paste_page(
     Ok(r)
 }
 
+async fn compare_page(
+    rctx: &RequestContext,
+    a: PasteId,
+    b: PasteId,
+) -> Result<(ResponseInfo, app::Context)> {
+    let pastes = rctx.inject::<crate::pastes::Pastes>();
+
+    // Compare pages are not purged when one of the pastes changes,
+    // they are only cached for a short time.
+    let info = ResponseInfo {
+        cache_control: CacheControl::default()
+            .public()
+            .s_max_age(consts::CACHE_SHORT),
+        ..Default::default()
+    };
+
+    let r = match futures::try_join!(pastes.get_paste(&a), pastes.get_paste(&b)) {
+        Ok((Some((meta_a, paste_a)), Some((meta_b, paste_b)))) => {
+            let info = info.with_etag(format!("{}-{}", meta_a.etag, meta_b.etag));
+            (info, app::Context::compare(a, paste_a, b, paste_b))
+        }
+        Err(Error::InvalidId(..)) | Ok(_) => {
+            (info.with_etag("not_found"), app::Context::not_found())
+        }
+        Err(err) => return Err(err),
+    };
+
+    Ok(r)
+}
+
 struct ResponseInfo {
     cache_control: CacheControl,
     etag: Option<String>,
     redirect: Option<String>,
     meta: Option<response::Meta>,
+    origin: Option<String>,
 }
 
 impl ResponseInfo {
@@ -163,6 +203,7 @@ impl Default for ResponseInfo {
             etag: None,
             redirect: None,
             meta: None,
+            origin: None,
         }
     }
 }
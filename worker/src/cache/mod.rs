@@ -0,0 +1,243 @@
+//! Caching of responses with the Workers Cache API.
+use pob::{PathOfBuilding, SerdePathOfBuilding};
+use shared::{model::ApiTokenScope, PasteId};
+
+use crate::{
+    request_context::RequestContext,
+    route::{Api, Route},
+    Response, Result,
+};
+
+mod workers;
+
+pub use self::workers::WorkerCache;
+
+/// Storage for cacheable responses, keyed by the full request url.
+pub trait ResponseCache {
+    async fn get(&self, url: &str) -> Option<Response>;
+
+    async fn put(&self, url: &str, response: Response) -> Result<()>;
+
+    /// Removes the cached response, returns `true` if there was one.
+    async fn delete(&self, url: &str) -> Result<bool>;
+}
+
+struct CacheEntryInner {
+    cache: Cache,
+    key: String,
+    ctx: worker::Context,
+}
+
+pub struct CacheEntry {
+    inner: Option<CacheEntryInner>,
+}
+
+impl CacheEntry {
+    pub async fn load(&self) -> Option<Response> {
+        let inner = self.inner.as_ref()?;
+
+        let cache = inner.cache.open().await;
+        cache.get(&inner.key).await
+    }
+
+    pub async fn store(self, mut response: Response) -> Response {
+        if self.inner.is_none() || !response.is_cacheable() {
+            return response;
+        }
+
+        let CacheEntryInner { cache, key, ctx } = self.inner.unwrap(); // checked for none above
+        let for_cache = response.duplicate();
+
+        ctx.wait_until(async move {
+            tracing::debug!("--> caching response in {cache}");
+            let r = cache.open().await.put(&key, for_cache).await;
+            debug_assert!(r.is_ok(), "failed to cache response: {r:?}");
+            tracing::debug!("<-- response cached in {cache}");
+        });
+
+        response
+            .header("X-Pobbin-Cache", &cache.to_string())
+            .header("Cf-Cache-Status", "MISS")
+    }
+}
+
+impl From<&RequestContext> for CacheEntry {
+    fn from(value: &RequestContext) -> Self {
+        if value.req().method() != worker::Method::Get {
+            return Self { inner: None };
+        }
+
+        let cache = Cache::select(value);
+        let key = value.req().url().expect("request url").to_string();
+        let ctx = value.ctx().clone();
+
+        Self {
+            inner: Some(CacheEntryInner { cache, key, ctx }),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Cache {
+    /// Default cache, used when no other cache is selected
+    Default,
+    /// The owned cache is used for all responses which are specific
+    /// to the owner of the resource
+    Owned,
+}
+
+impl Cache {
+    pub fn select(rctx: &RequestContext) -> Self {
+        let session = rctx.authorized(ApiTokenScope::ReadPrivate);
+
+        match rctx.route() {
+            Route::App(app::Route::User(user))
+            | Route::Api(Api::Get(crate::route::GetEndpoints::User(user))) => {
+                if Some(user) == session.map(|s| &s.name) {
+                    Cache::Owned
+                } else {
+                    Cache::Default
+                }
+            }
+            _ => Cache::Default,
+        }
+    }
+
+    pub async fn open(&self) -> WorkerCache {
+        match self {
+            Self::Default => WorkerCache::default(),
+            Self::Owned => WorkerCache::open("owned").await,
+        }
+    }
+}
+
+impl std::fmt::Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Resources of a paste which are addressed by index or id and need to be purged individually.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct PasteResources {
+    trees: usize,
+    items: Vec<u16>,
+    /// Number of revisions of a user paste, revisions are purged when the paste
+    /// is changed or deleted.
+    revisions: u32,
+}
+
+impl PasteResources {
+    pub fn of(pob: &impl PathOfBuilding) -> Self {
+        Self {
+            trees: pob.tree_specs().len(),
+            items: pob.item_ids(),
+            revisions: 0,
+        }
+    }
+
+    /// Also purges the revisions `1..=revisions` of a user paste.
+    pub fn with_revisions(mut self, revisions: u32) -> Self {
+        self.revisions = revisions;
+        self
+    }
+
+    /// Resources of an exported build, a build which can't be parsed has no resources.
+    pub fn of_export(content: &str) -> Self {
+        SerdePathOfBuilding::from_export(content)
+            .map(|pob| Self::of(&pob))
+            .unwrap_or_default()
+    }
+
+    /// Resources of both pastes, e.g. of the previous and the new content of a paste.
+    pub fn union(mut self, other: Self) -> Self {
+        self.trees = self.trees.max(other.trees);
+        self.items.extend(other.items);
+        self.items.sort_unstable();
+        self.items.dedup();
+        self.revisions = self.revisions.max(other.revisions);
+        self
+    }
+}
+
+pub(crate) fn on_paste_change(rctx: &RequestContext, id: PasteId, resources: PasteResources) {
+    let url = rctx.url().unwrap();
+    rctx.ctx()
+        .wait_until(on_paste_change_async(url, id, resources));
+}
+
+pub(crate) async fn on_paste_change_async(
+    mut url: url::Url,
+    id: PasteId,
+    resources: PasteResources,
+) {
+    url.set_path("");
+    url.set_query(None);
+    url.set_fragment(None);
+    let prefix = url.to_string();
+
+    let cache_default = Cache::Default.open().await;
+    let cache_owned = Cache::Owned.open().await;
+
+    macro_rules! clear {
+        ($e:expr) => {{
+            let r = format!("{prefix}{}", $e.trim_start_matches('/'));
+            let _ = cache_default.delete(&r).await;
+            let _ = cache_owned.delete(&r).await;
+        }};
+    }
+
+    tracing::info!("resetting cached URLs for {id}");
+    clear!(id.to_url());
+    clear!(id.to_raw_url());
+    clear!(id.to_json_url());
+    clear!(id.to_pob_load_url());
+    clear!(id.to_card_url());
+    for resource in crate::api_v1::Resource::ALL {
+        clear!(resource.url(&id));
+    }
+    for index in 0..resources.trees {
+        clear!(id.to_tree_url(index));
+    }
+    for &item_id in &resources.items {
+        clear!(id.to_item_trade_url(item_id));
+    }
+
+    if let PasteId::UserPaste(up) = id {
+        clear!(up.to_pob_long_load_url());
+        clear!(up.to_paste_edit_url());
+        clear!(up.to_user_url());
+        clear!(up.to_user_api_url());
+        clear!(up.to_revisions_url());
+        for revision in 1..=resources.revisions {
+            clear!(up.to_revision_raw_url(revision));
+            clear!(up.to_revision_json_url(revision));
+            clear!(up.to_revision_xml_url(revision));
+        }
+    }
+    tracing::info!("done resetting caches");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paste_resources_union() {
+        let old = PasteResources {
+            trees: 3,
+            items: vec![4, 1, 2],
+            revisions: 0,
+        };
+        let new = PasteResources {
+            trees: 1,
+            items: vec![2, 3],
+            revisions: 5,
+        };
+
+        let union = old.union(new);
+        assert_eq!(union.trees, 3);
+        assert_eq!(union.items, [1, 2, 3, 4]);
+        assert_eq!(union.revisions, 5);
+    }
+}
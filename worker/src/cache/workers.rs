@@ -0,0 +1,37 @@
+use super::ResponseCache;
+use crate::{Response, Result};
+
+/// Cache backed by the Workers Cache API.
+pub struct WorkerCache(worker::Cache);
+
+impl Default for WorkerCache {
+    fn default() -> Self {
+        Self(worker::Cache::default())
+    }
+}
+
+impl WorkerCache {
+    pub async fn open(name: &'static str) -> Self {
+        Self(worker::Cache::open(name).await)
+    }
+}
+
+impl ResponseCache for WorkerCache {
+    async fn get(&self, url: &str) -> Option<Response> {
+        self.0
+            .get(url, true)
+            .await
+            .expect("cache api")
+            .map(Response::from_cache)
+    }
+
+    async fn put(&self, url: &str, mut response: Response) -> Result<()> {
+        self.0.put(url, response.for_cache()).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, url: &str) -> Result<bool> {
+        let outcome = self.0.delete(url, true).await?;
+        Ok(matches!(outcome, worker::CacheDeletionOutcome::Success))
+    }
+}
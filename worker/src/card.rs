@@ -0,0 +1,236 @@
+//! Rasterized build cards, used as `og:image` for social embeds.
+use std::fmt::Write;
+
+use once_cell::sync::OnceCell;
+use pob::{PathOfBuilding, PathOfBuildingExt, SerdePathOfBuilding};
+use resvg::{tiny_skia, usvg};
+use shared::PasteId;
+
+use crate::{
+    consts, net,
+    request_context::RequestContext,
+    retry,
+    utils::{CacheControl, Etag},
+    Error, Response, Result,
+};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+
+/// Font used for all text on the card.
+///
+/// `fontdb` can not read the `woff2` version used by the website.
+const FONT_URL: &str = "https://assets.pobb.in/1/Art/2DArt/Fonts/Fontin-SmallCaps.ttf";
+const FONT_FAMILY: &str = "Fontin SmallCaps";
+
+const MAX_TITLE_LENGTH: usize = 40;
+
+#[tracing::instrument(skip(rctx))]
+pub async fn handle_card(rctx: &RequestContext, id: PasteId) -> Result<Response> {
+    let storage = rctx.inject::<crate::storage::Storage>();
+    let paste = storage
+        .get(&id)
+        .await?
+        .ok_or_else(|| Error::NotFound("paste", id.to_string()))?;
+
+    let pob = SerdePathOfBuilding::from_export(&paste.content)
+        .map_err(|e| Error::InvalidPoB(e, String::new()))?;
+
+    let title = match paste.metadata {
+        Some(ref metadata) => metadata.title.clone(),
+        None => app::pob::title(&pob),
+    };
+
+    let fonts = match fonts().await {
+        Ok(fonts) => fonts,
+        Err(err) => {
+            // Embeds should still show an image, the card is rendered again on the next request.
+            tracing::warn!("failed to load card font: {err:?}");
+            return Response::redirect_temp(app::logo())
+                .cache_for(consts::CACHE_SHORT)
+                .result();
+        }
+    };
+
+    let svg = render_svg(&pob, &title);
+    let png = rasterize(&svg, fonts)?;
+
+    Response::ok()
+        .body(png)
+        .content_type("image/png")
+        .meta_paste(id, &paste)
+        .etag(Etag::strong(&paste.entity_id))
+        .cache(
+            CacheControl::default()
+                .public()
+                .s_max_age(consts::CACHE_FOREVER),
+        )
+        .result()
+}
+
+fn render_svg(pob: &impl PathOfBuilding, title: &str) -> String {
+    let color = app::get_color(pob.ascendancy_or_class());
+
+    let class = pob.ascendancy_or_class().as_str();
+    let subtitle = format!("Level {} {class}", pob.level());
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="{FONT_FAMILY}">"##
+    );
+    let _ = write!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#0f172a"/><rect width="16" height="100%" fill="{color}"/>"##
+    );
+    let _ = write!(
+        svg,
+        r##"<text x="64" y="100" font-size="56" fill="#f8fafc">{}</text>"##,
+        escape(&truncate(title, MAX_TITLE_LENGTH))
+    );
+    let _ = write!(
+        svg,
+        r##"<text x="64" y="156" font-size="36" fill="{color}">{}</text>"##,
+        escape(&subtitle)
+    );
+
+    let stats = app::pob::summary::core_stats(pob)
+        .into_iter()
+        .filter_map(|stat| stat.render_to_string());
+    for (i, stat) in stats.enumerate() {
+        let y = 240 + i * 46;
+        let _ = write!(
+            svg,
+            r##"<text x="64" y="{y}" font-size="34" fill="#e2e8f0">{}</text>"##,
+            escape(&stat)
+        );
+    }
+
+    if let Some((skill, gems)) = main_skill(pob) {
+        let _ = write!(
+            svg,
+            r##"<text x="64" y="{}" font-size="36" fill="#fef3c7">{}</text>"##,
+            HEIGHT - 90,
+            escape(&skill)
+        );
+        let _ = write!(
+            svg,
+            r##"<text x="64" y="{}" font-size="26" fill="#94a3b8">{}</text>"##,
+            HEIGHT - 48,
+            escape(&gems.join(" - "))
+        );
+    }
+
+    let _ = write!(
+        svg,
+        r##"<text x="{}" y="{}" font-size="30" fill="#0ea5e9" text-anchor="end">pobb.in</text></svg>"##,
+        WIDTH - 48,
+        HEIGHT - 48
+    );
+
+    svg
+}
+
+/// Name of the main skill and all enabled gems linked with it.
+fn main_skill(pob: &impl PathOfBuilding) -> Option<(String, Vec<String>)> {
+    let skill_set = pob.skill_sets().into_iter().find(|ss| ss.is_selected)?;
+    let skill = skill_set.skills.into_iter().find(|s| s.is_selected)?;
+
+    let name = pob.main_skill_name()?.to_owned();
+    let gems = skill
+        .gems
+        .into_iter()
+        .filter(|gem| gem.is_enabled && !gem.name.is_empty())
+        .map(|gem| gem.name.to_owned())
+        .collect();
+
+    Some((name, gems))
+}
+
+/// Returns the fonts used for the card, the font is only fetched and loaded once per isolate.
+async fn fonts() -> Result<&'static usvg::fontdb::Database> {
+    static FONTS: OnceCell<usvg::fontdb::Database> = OnceCell::new();
+
+    if let Some(fonts) = FONTS.get() {
+        return Ok(fonts);
+    }
+
+    let font = retry::retry_all(3, |_| fetch_font()).await?;
+    Ok(FONTS.get_or_init(|| {
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.load_font_data(font);
+        fontdb
+    }))
+}
+
+async fn fetch_font() -> Result<Vec<u8>> {
+    let mut response = net::Request::get(FONT_URL).tag("card_font").send().await?;
+
+    if response.status_code() != 200 {
+        return Err(Error::RemoteFailed(
+            response.status_code(),
+            "failed to fetch card font".to_owned(),
+        ));
+    }
+
+    Ok(response.bytes().await?)
+}
+
+fn rasterize(svg: &str, fontdb: &usvg::fontdb::Database) -> Result<Vec<u8>> {
+    let options = usvg::Options {
+        font_family: FONT_FAMILY.to_owned(),
+        ..Default::default()
+    };
+
+    let tree = usvg::Tree::from_str(svg, &options, fontdb)
+        .map_err(|e| Error::Error(format!("invalid card svg: {e}")))?;
+
+    let mut pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT)
+        .ok_or_else(|| Error::Error("failed to allocate card pixmap".to_owned()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| Error::Error(format!("failed to encode card: {e}")))
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => format!("{}...", s[..end].trim_end()),
+        None => s.to_owned(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_card() {
+        let xml = include_str!("../../pob/test/316_poison_occ.xml");
+        let pob = SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let svg = render_svg(&pob, "<Poison> & Occultist");
+        assert!(svg.contains("&lt;Poison&gt; &amp; Occultist"));
+        assert!(svg.contains("Level "));
+
+        // Without fonts the text is skipped, the card is still a valid image.
+        let png = rasterize(&svg, &usvg::fontdb::Database::new()).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), WIDTH);
+        assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), HEIGHT);
+    }
+
+    #[test]
+    fn truncate_title() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a title which is too long", 7), "a title...");
+    }
+}
@@ -22,6 +22,12 @@ pub const ENV_STATS_URL: &str = "STATS_URL";
 pub const ENV_STATS_TOKEN: &str = "STATS_TOKEN";
 
 pub const OAUTH_SCOPE: &str = "account:profile";
+/// Scope used for snapshotting a character, see [`crate::poe_api::PoeApi::fetch_character`].
+pub const OAUTH_SCOPE_CHARACTERS: &str = "account:profile account:characters";
 
+/// How long deleted user pastes can be restored.
+pub const TRASH_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60); // 30 Days
+
+pub const CACHE_SHORT: Duration = Duration::from_secs(300); // 5 Minutes
 pub const CACHE_A_BIT: Duration = Duration::from_secs(21600); // 6 Hours
 pub const CACHE_FOREVER: Duration = Duration::from_secs(31536000);
@@ -0,0 +1,60 @@
+//! Hashing, signing and random values.
+//!
+//! Inside the Workers runtime everything goes through the WebCrypto API,
+//! native builds use pure Rust implementations instead.
+use worker::Result;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod subtle;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::native::NativeCrypto as PlatformCrypto;
+#[cfg(target_arch = "wasm32")]
+pub use self::subtle::SubtleCrypto as PlatformCrypto;
+
+pub struct Sha1(pub [u8; 20]);
+
+impl std::ops::Deref for Sha1 {
+    type Target = [u8; 20];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Sha1 {
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+/// Cryptographic primitives required by the worker.
+pub trait Crypto {
+    async fn sha1(data: &[u8]) -> Result<Sha1>;
+
+    async fn sign_hmac_256(secret: &[u8], payload: &[u8]) -> Result<Vec<u8>>;
+
+    async fn verify_hmac_256(secret: &[u8], signature: &[u8], payload: &[u8]) -> Result<bool>;
+
+    fn fill_random(dest: &mut [u8]) -> Result<()>;
+}
+
+pub async fn sha1(data: &[u8]) -> Result<Sha1> {
+    PlatformCrypto::sha1(data).await
+}
+
+pub async fn sign_hmac_256(secret: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+    PlatformCrypto::sign_hmac_256(secret, payload).await
+}
+
+pub async fn verify_hmac_256(secret: &[u8], signature: &[u8], payload: &[u8]) -> Result<bool> {
+    PlatformCrypto::verify_hmac_256(secret, signature, payload).await
+}
+
+pub fn get_random_values<const N: usize>() -> Result<[u8; N]> {
+    let mut result = [0; N];
+    PlatformCrypto::fill_random(&mut result)?;
+    Ok(result)
+}
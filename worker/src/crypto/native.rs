@@ -0,0 +1,66 @@
+use hmac::{Hmac, Mac};
+use sha1::Digest;
+use worker::{Error, Result};
+
+use super::{Crypto, Sha1};
+
+type HmacSha256 = Hmac<sha2::Sha256>;
+
+/// Pure Rust implementation, used outside of the Workers runtime.
+pub struct NativeCrypto;
+
+impl Crypto for NativeCrypto {
+    async fn sha1(data: &[u8]) -> Result<Sha1> {
+        Ok(Sha1(sha1::Sha1::digest(data).into()))
+    }
+
+    async fn sign_hmac_256(secret: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+        let mut mac = hmac(secret)?;
+        mac.update(payload);
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
+    async fn verify_hmac_256(secret: &[u8], signature: &[u8], payload: &[u8]) -> Result<bool> {
+        let mut mac = hmac(secret)?;
+        mac.update(payload);
+        Ok(mac.verify_slice(signature).is_ok())
+    }
+
+    fn fill_random(dest: &mut [u8]) -> Result<()> {
+        getrandom::getrandom(dest).map_err(|err| Error::RustError(err.to_string()))
+    }
+}
+
+fn hmac(secret: &[u8]) -> Result<HmacSha256> {
+    HmacSha256::new_from_slice(secret).map_err(|err| Error::RustError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha1_hex() {
+        let hash = futures::executor::block_on(NativeCrypto::sha1(b"abc")).unwrap();
+        assert_eq!(hash.to_hex(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn hmac_roundtrip() {
+        futures::executor::block_on(async {
+            let signature = NativeCrypto::sign_hmac_256(b"secret", b"payload")
+                .await
+                .unwrap();
+            assert!(
+                NativeCrypto::verify_hmac_256(b"secret", &signature, b"payload")
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                !NativeCrypto::verify_hmac_256(b"other", &signature, b"payload")
+                    .await
+                    .unwrap()
+            );
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::JsValue;
+use web_sys::{CryptoKey, HmacImportParams, WorkerGlobalScope};
+use worker::wasm_bindgen::JsCast;
+use worker::wasm_bindgen_futures::JsFuture;
+use worker::{js_sys, Result};
+
+use super::{Crypto, Sha1};
+
+/// WebCrypto implementation provided by the Workers runtime.
+pub struct SubtleCrypto;
+
+impl Crypto for SubtleCrypto {
+    async fn sha1(data: &[u8]) -> Result<Sha1> {
+        let worker: WorkerGlobalScope = js_sys::global().unchecked_into();
+        let data = unsafe { Uint8Array::view(data) };
+        let digest = JsFuture::from(
+            worker
+                .crypto()?
+                .subtle()
+                .digest_with_str_and_buffer_source("SHA-1", &data)?,
+        )
+        .await?;
+        assert!(digest.is_instance_of::<js_sys::ArrayBuffer>());
+
+        let digest = Uint8Array::new(&digest);
+        assert_eq!(digest.length(), 20, "a sha1 hash must be 20 bytes");
+
+        let mut result = Sha1(Default::default());
+        digest.copy_to(&mut result.0);
+        Ok(result)
+    }
+
+    async fn sign_hmac_256(secret: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+        let worker: WorkerGlobalScope = js_sys::global().unchecked_into();
+        let subtle = worker.crypto()?.subtle();
+
+        let secret = Uint8Array::from(secret);
+        let algorithm = HmacImportParams::new("HMAC", &JsValue::from_str("SHA-256"));
+
+        let usage = Array::of1(&JsValue::from_str("sign"));
+
+        let key =
+            subtle.import_key_with_object("raw", &secret.buffer(), &algorithm, false, &usage)?;
+        let key = JsFuture::from(key).await?.unchecked_into::<CryptoKey>();
+
+        // The web-sys bindings require mutable buffers.
+        let mut payload = payload.to_vec();
+        let signed = subtle.sign_with_str_and_u8_array("HMAC", &key, &mut payload)?;
+        let signed = JsFuture::from(signed).await?;
+
+        Ok(Uint8Array::new(&signed).to_vec())
+    }
+
+    async fn verify_hmac_256(secret: &[u8], signature: &[u8], payload: &[u8]) -> Result<bool> {
+        let worker: WorkerGlobalScope = js_sys::global().unchecked_into();
+        let subtle = worker.crypto()?.subtle();
+
+        let secret = Uint8Array::from(secret);
+        let algorithm = HmacImportParams::new("HMAC", &JsValue::from_str("SHA-256"));
+        let usage = Array::of1(&JsValue::from_str("verify"));
+        let key =
+            subtle.import_key_with_object("raw", &secret.buffer(), &algorithm, false, &usage)?;
+        let key = JsFuture::from(key).await?.unchecked_into::<CryptoKey>();
+
+        let mut signature = signature.to_vec();
+        let mut payload = payload.to_vec();
+        let signed = subtle.verify_with_str_and_u8_array_and_u8_array(
+            "HMAC",
+            &key,
+            &mut signature,
+            &mut payload,
+        )?;
+
+        Ok(JsFuture::from(signed).await?.as_bool().unwrap_or(false))
+    }
+
+    fn fill_random(dest: &mut [u8]) -> Result<()> {
+        let worker: WorkerGlobalScope = js_sys::global().unchecked_into();
+        worker.crypto()?.get_random_values_with_u8_array(dest)?;
+        Ok(())
+    }
+}
@@ -53,9 +53,9 @@ impl Dangerous {
     where
         T: std::fmt::Debug,
     {
-        let mut payload = serde_json::to_vec(data).map_err(|_| DangerousError::Serialize)?;
+        let payload = serde_json::to_vec(data).map_err(|_| DangerousError::Serialize)?;
 
-        let signature = crypto::sign_hmac_256(&self.secret, &mut payload)
+        let signature = crypto::sign_hmac_256(&self.secret, &payload)
             .await
             .map_err(|_| DangerousError::Crypto)?;
 
@@ -70,12 +70,12 @@ impl Dangerous {
     pub async fn verify<T: DeserializeOwned>(&self, data: &str) -> Result<T> {
         let (payload, signature) = data.rsplit_once('.').ok_or(DangerousError::BadEncoding)?;
 
-        let mut signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+        let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
             .map_err(|_| DangerousError::BadEncoding)?;
-        let mut payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+        let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
             .map_err(|_| DangerousError::BadEncoding)?;
 
-        let verified = crypto::verify_hmac_256(&self.secret, &mut signature, &mut payload)
+        let verified = crypto::verify_hmac_256(&self.secret, &signature, &payload)
             .await
             .map_err(|_| DangerousError::Crypto)?;
 
@@ -1,5 +1,4 @@
 use sentry::Level;
-use serde::Serialize;
 use thiserror::Error;
 
 use crate::dangerous::DangerousError;
@@ -168,8 +167,5 @@ impl From<Error> for crate::Response {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub code: u16,
-    pub message: String,
-}
+/// Error responses are part of the public API and share its stable type.
+pub type ErrorResponse = shared::api::v1::Error;
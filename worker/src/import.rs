@@ -0,0 +1,25 @@
+use shared::import::Import;
+
+use crate::{net, Error, Result};
+
+/// Downloads the export code of a build hosted on another site.
+///
+/// Returns `None` if the remote site does not know the build.
+#[tracing::instrument]
+pub async fn fetch(import: &Import) -> Result<Option<String>> {
+    let mut response = net::Request::get(import.raw_url())
+        .tag("import")
+        .send()
+        .await?;
+
+    let content = match response.status_code() {
+        200 => response.text().await?,
+        404 => return Ok(None),
+        code => {
+            let msg = format!("{} import failed", import.importer);
+            return Err(Error::RemoteFailed(code, msg));
+        }
+    };
+
+    Ok(Some(content.trim().to_owned()))
+}
@@ -1,15 +1,20 @@
 use sentry::WithSentry;
 use statsd::Counters;
-use worker::{event, Context, Env, Request, Response as WorkerResponse};
+use worker::{
+    event, Context, Env, Request, Response as WorkerResponse, ScheduleContext, ScheduledEvent,
+};
 
 mod api;
+mod api_v1;
 mod app;
 mod assets;
 mod cache;
+mod card;
 mod consts;
 mod crypto;
 mod dangerous;
 mod error;
+mod import;
 mod layer;
 mod net;
 mod pastes;
@@ -22,13 +27,16 @@ mod sentry_impl;
 mod stats;
 mod statsd;
 mod storage;
+#[cfg(test)]
+mod test_utils;
+mod tokens;
 mod utils;
 
 mod app_metadata {
     include!(concat!(env!("OUT_DIR"), "/app_metadata.rs"));
 }
 
-use request_context::RequestContext;
+use request_context::{FromEnv, RequestContext};
 use utils::CacheControl;
 
 pub use self::error::{Error, ErrorResponse, Result};
@@ -36,8 +44,7 @@ pub use self::response::Response;
 
 static LOG_INIT: std::sync::Once = std::sync::Once::new();
 
-#[event(fetch)]
-pub async fn main(req: Request, env: Env, ctx: Context) -> worker::Result<WorkerResponse> {
+fn init_logging() {
     LOG_INIT.call_once(|| {
         use tracing_subscriber::prelude::*;
         tracing_subscriber::registry()
@@ -45,6 +52,11 @@ pub async fn main(req: Request, env: Env, ctx: Context) -> worker::Result<Worker
             .with(layer::Layer {})
             .init();
     });
+}
+
+#[event(fetch)]
+pub async fn main(req: Request, env: Env, ctx: Context) -> worker::Result<WorkerResponse> {
+    init_logging();
 
     let mut rctx = RequestContext::new(req, env, ctx).await;
 
@@ -74,6 +86,25 @@ pub async fn main(req: Request, env: Env, ctx: Context) -> worker::Result<Worker
     Ok(worker::Response::from(response))
 }
 
+/// Permanently deletes trashed pastes which exceeded [`consts::TRASH_RETENTION`],
+/// triggered by the cron schedule configured in `wrangler.toml`.
+#[event(scheduled)]
+pub async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    init_logging();
+
+    let env = request_context::Env::new(env);
+    let Some(storage) = storage::Storage::from_env(&env) else {
+        tracing::error!("storage is not configured");
+        return;
+    };
+
+    let now = worker::Date::now().as_millis();
+    match storage.purge_expired_trash(now).await {
+        Ok(purged) => tracing::info!(purged, "purged expired trash"),
+        Err(err) => tracing::error!("failed to purge expired trash: {err:?}"),
+    }
+}
+
 #[tracing::instrument(skip_all)]
 async fn cached(rctx: &mut RequestContext) -> Response {
     sentry::counter(Counters::Request)
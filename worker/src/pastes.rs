@@ -1,62 +1,122 @@
 use std::collections::{BTreeMap, HashMap};
 
 use pob::{PathOfBuilding, SerdePathOfBuilding};
+use poe_data::planner::Purchase;
 use shared::{
     model::{
         data::{self, NodeStat},
-        Paste, PasteSummary,
+        Paste, PasteFilter, PasteMetadata, PasteSummary, TreeWarning,
     },
     PasteId, User, UserPasteId,
 };
 
-use crate::request_context::{Env, FromEnv, Session};
+use crate::{
+    cache::PasteResources,
+    crypto,
+    request_context::{Env, FromEnv, Session},
+    storage::{Bucket, ObjectStore, Storage, StoredPaste},
+    utils,
+};
 
 pub struct Meta {
     pub etag: String,
 }
 
-pub struct Pastes {
-    pub(crate) storage: crate::storage::Storage,
+/// A paste stored with [`Pastes::upload`].
+pub struct Uploaded {
+    pub id: PasteId,
+    /// Resources of the content replaced by the upload, their cached versions are outdated.
+    pub replaced: PasteResources,
+}
+
+pub struct Pastes<S = Bucket> {
+    pub(crate) storage: Storage<S>,
 }
 
-impl FromEnv for Pastes {
+impl<S: FromEnv> FromEnv for Pastes<S> {
     fn from_env(env: &Env) -> Option<Self> {
         Some(Self {
-            storage: crate::storage::Storage::from_env(env)?,
+            storage: Storage::from_env(env)?,
         })
     }
 }
 
-impl Pastes {
+impl<S: ObjectStore> Pastes<S> {
     pub async fn get_paste(&self, id: &PasteId) -> crate::Result<Option<(Meta, Paste)>> {
         let Some(stored) = self.storage.get(id).await? else {
             return Ok(None);
         };
 
-        let pob = SerdePathOfBuilding::from_export(&stored.content)
-            .map_err(|e| crate::Error::InvalidPoB(e, String::new()))?;
+        to_paste(stored).map(Some)
+    }
 
-        let paste = Paste {
-            metadata: stored.metadata,
-            last_modified: stored.last_modified,
-            content: stored.content,
-            data: data::Data {
-                nodes: extract_node_info(&pob),
-                gems: extract_gem_info(&pob),
-            },
+    pub async fn get_revision(
+        &self,
+        id: &UserPasteId,
+        revision: u32,
+    ) -> crate::Result<Option<(Meta, Paste)>> {
+        let Some(stored) = self.storage.get_revision(id, revision).await? else {
+            return Ok(None);
         };
 
-        let meta = Meta {
-            etag: stored.entity_id,
+        to_paste(stored).map(Some)
+    }
+
+    /// Stores a validated build.
+    ///
+    /// Anonymous pastes (`id` is `None`) are addressed by the hash of their content,
+    /// the previous version of an existing user paste is archived as a revision.
+    pub async fn upload(
+        &self,
+        id: Option<UserPasteId>,
+        content: &[u8],
+        metadata: &PasteMetadata,
+    ) -> crate::Result<Uploaded> {
+        let sha1 = crypto::sha1(content).await?;
+
+        let id: PasteId = match id {
+            Some(id) => id.into(),
+            None => PasteId::Paste(utils::hash_to_short_id(&sha1)),
         };
 
-        Ok(Some((meta, paste)))
+        // Cached resources of the replaced paste are only known from its content.
+        let mut replaced = self
+            .storage
+            .get(&id)
+            .await?
+            .map(|paste| PasteResources::of_export(&paste.content))
+            .unwrap_or_default();
+
+        if let PasteId::UserPaste(ref id) = id {
+            if let Some(revision) = self.storage.archive(id, &sha1).await? {
+                tracing::debug!(revision, "archived previous paste version");
+                replaced = replaced.with_revisions(revision);
+            }
+        }
+
+        tracing::debug!("--> uploading paste '{}'", id);
+        self.storage
+            .put(&id, &sha1, content, Some(metadata))
+            .await?;
+        tracing::debug!("<-- paste uploaded");
+
+        Ok(Uploaded { id, replaced })
+    }
+
+    /// Moves a user paste into the trash.
+    pub async fn delete(&self, id: &UserPasteId) -> crate::Result<()> {
+        if !self.storage.trash(id).await? {
+            return Err(crate::Error::NotFound("paste", id.to_string()));
+        }
+
+        Ok(())
     }
 
     pub async fn list_pastes(
         &self,
         session: Session<'_>,
         user: &User,
+        filter: &PasteFilter,
     ) -> crate::Result<(Meta, Vec<PasteSummary>)> {
         let mut pastes = self
             .storage
@@ -88,11 +148,7 @@ impl Pastes {
             })
             .collect::<Vec<_>>();
 
-        pastes.sort_unstable_by(|a, b| {
-            b.rank
-                .cmp(&a.rank)
-                .then(b.last_modified.cmp(&a.last_modified))
-        });
+        filter.apply(&mut pastes);
 
         let etag = pastes
             .first()
@@ -104,6 +160,99 @@ impl Pastes {
     }
 }
 
+fn to_paste(stored: StoredPaste) -> crate::Result<(Meta, Paste)> {
+    let pob = SerdePathOfBuilding::from_export(&stored.content)
+        .map_err(|e| crate::Error::InvalidPoB(e, String::new()))?;
+
+    let paste = Paste {
+        metadata: stored.metadata,
+        last_modified: stored.last_modified,
+        content: stored.content,
+        data: data::Data {
+            nodes: extract_node_info(&pob),
+            gems: extract_gem_info(&pob),
+            warnings: lint(&pob),
+            gem_shopping_list: Some(gem_shopping_list(&pob)),
+            tree_progression: tree_progression(&pob),
+        },
+    };
+
+    let meta = Meta {
+        etag: stored.entity_id,
+    };
+
+    Ok((meta, paste))
+}
+
+fn lint(pob: &impl PathOfBuilding) -> Vec<String> {
+    let latest = poe_tree::Version::latest();
+    let warnings = pob::lint::lint(pob, Some(latest.as_str()));
+    // Outdated tree versions are already reported by the lints.
+    let tree_warnings = validate_trees(pob)
+        .into_iter()
+        .filter(TreeWarning::is_invalid);
+
+    warnings
+        .iter()
+        .map(ToString::to_string)
+        .chain(tree_warnings.map(|w| w.to_string()))
+        .collect()
+}
+
+/// Validates all tree specs against the tree data of their version.
+///
+/// Specs of an unsupported version are not validated, each unsupported version
+/// is reported once.
+///
+/// Tree specs have no level of their own, the points of the active spec are checked
+/// against the level of the build, other specs against the maximum level.
+pub fn validate_trees(pob: &impl PathOfBuilding) -> Vec<TreeWarning> {
+    let mut warnings = Vec::new();
+    let mut unsupported = Vec::new();
+    for (spec_index, spec) in pob.tree_specs().into_iter().enumerate() {
+        let version = match spec.version {
+            Some(version) => match version.parse::<poe_tree::Version>() {
+                Ok(version) => version,
+                Err(_) => {
+                    if !unsupported.contains(&version) {
+                        unsupported.push(version);
+                        warnings.push(TreeWarning::UnsupportedVersion {
+                            spec: spec_index,
+                            version: version.to_owned(),
+                        });
+                    }
+                    continue;
+                }
+            },
+            None => poe_tree::Version::latest(),
+        };
+
+        let level = match spec.active {
+            true => pob.level(),
+            false => poe_tree::validate::MAX_LEVEL,
+        };
+        let issues = poe_tree::validate::validate(version, spec.nodes, spec.mastery_effects, level);
+        warnings.extend(issues.into_iter().map(|issue| {
+            use poe_tree::validate::Issue;
+            let spec = spec_index;
+            match issue {
+                Issue::UnknownNode(node) => TreeWarning::UnknownNode { spec, node },
+                Issue::InvalidMasteryEffect { node, effect } => {
+                    TreeWarning::InvalidMasteryEffect { spec, node, effect }
+                }
+                Issue::TooManyPoints { points, max } => {
+                    TreeWarning::TooManyPoints { spec, points, max }
+                }
+                Issue::TooManyAscendancyPoints { points, max } => {
+                    TreeWarning::TooManyAscendancyPoints { spec, points, max }
+                }
+            }
+        }));
+    }
+
+    warnings
+}
+
 fn extract_node_info(pob: &impl PathOfBuilding) -> Vec<data::Nodes> {
     let mut data = Vec::new();
     for spec in pob.tree_specs() {
@@ -166,6 +315,102 @@ fn extract_node_info(pob: &impl PathOfBuilding) -> Vec<data::Nodes> {
     data
 }
 
+/// Resolves the nodes of the tree progression, the client only includes the latest tree.
+fn tree_progression(pob: &impl PathOfBuilding) -> Vec<data::TreeStep> {
+    let specs = pob.tree_specs();
+    let versions = specs
+        .iter()
+        .map(|spec| {
+            spec.version
+                .and_then(|v| v.parse::<poe_tree::Version>().ok())
+                .unwrap_or_else(poe_tree::Version::latest)
+        })
+        .collect::<Vec<_>>();
+
+    pob::diff::tree_progression(pob)
+        .into_iter()
+        .map(|diff| {
+            let index = diff.index;
+            let previous = index.checked_sub(1);
+
+            let mut added = tree_changes(versions[index], &diff.added_nodes);
+            let mut removed = match previous {
+                Some(previous) => tree_changes(versions[previous], &diff.removed_nodes),
+                None => data::TreeChanges::default(),
+            };
+
+            let mastery_effect = |version, node_id, effect: Option<u32>| {
+                let node = poe_tree::get_node(version, node_id)?;
+                let effect = node
+                    .mastery_effects
+                    .iter()
+                    .find(|m| Some(m.effect) == effect)?;
+                Some(format!("{}: {}", node.name, effect.stats.join(", ")))
+            };
+            for change in &diff.mastery_effects {
+                added.mastery_effects.extend(mastery_effect(
+                    versions[index],
+                    change.node,
+                    change.new,
+                ));
+                if let Some(previous) = previous {
+                    removed.mastery_effects.extend(mastery_effect(
+                        versions[previous],
+                        change.node,
+                        change.old,
+                    ));
+                }
+            }
+
+            data::TreeStep {
+                index,
+                title: diff.title.map(|title| title.to_owned()),
+                points: tree_points(versions[index], specs[index].nodes),
+                previous_points: previous
+                    .map(|previous| tree_points(versions[previous], specs[previous].nodes))
+                    .unwrap_or_default(),
+                added,
+                removed,
+            }
+        })
+        .collect()
+}
+
+fn tree_changes(version: poe_tree::Version, nodes: &[u32]) -> data::TreeChanges {
+    let mut changes = data::TreeChanges::default();
+
+    let nodes = nodes
+        .iter()
+        .filter_map(|&id| poe_tree::get_node(version, id));
+    for node in nodes.filter(|node| node.is_allocatable()) {
+        let kind = node.kind;
+        if kind.is_keystone() {
+            changes.keystones.push(node.name.to_owned());
+        } else if kind.is_notable() || kind.is_alternate_ascendancy_notable() {
+            changes.notables.push(node.name.to_owned());
+        } else if !kind.is_mastery() {
+            changes.small += 1;
+        }
+    }
+
+    changes.keystones.sort_unstable();
+    changes.notables.sort_unstable();
+    changes
+}
+
+/// Start nodes are free, unknown nodes, e.g. cluster jewel nodes, count as passives.
+fn tree_points(version: poe_tree::Version, nodes: &[u32]) -> data::Points {
+    let mut points = data::Points::default();
+    for &id in nodes {
+        match poe_tree::get_node(version, id) {
+            Some(node) if !node.is_allocatable() => {}
+            Some(node) if node.ascendancy.is_some() => points.ascendancy += 1,
+            _ => points.passives += 1,
+        }
+    }
+    points
+}
+
 fn stats_to_owned(id: u32, stats: &[&str]) -> Vec<NodeStat> {
     stats
         .iter()
@@ -219,3 +464,227 @@ fn extract_gem_info(pob: &impl PathOfBuilding) -> HashMap<String, data::Gem> {
     }
     result
 }
+
+/// Leveling shopping list for all gems of the build, see [`poe_data::planner`].
+pub fn gem_shopping_list(pob: &impl PathOfBuilding) -> data::GemShoppingList {
+    let skill_sets = pob.skill_sets();
+    let gem_ids = skill_sets
+        .iter()
+        .flat_map(|set| &set.skills)
+        .flat_map(|skill| &skill.gems)
+        .filter_map(|gem| gem.gem_id);
+
+    let list = poe_data::planner::shopping_list(gem_ids, pob.class());
+
+    let purchase = |purchase: Purchase| data::GemPurchase {
+        id: purchase.gem.id.to_owned(),
+        name: purchase.gem.name.to_owned(),
+        level: purchase.gem.level,
+        lilly_roth: purchase.needs_lilly_roth(),
+        other_character: purchase.needs_other_character(),
+    };
+
+    data::GemShoppingList {
+        quests: list
+            .quests
+            .into_iter()
+            .map(|quest| data::GemQuest {
+                act: quest.act,
+                quest: quest.quest.to_owned(),
+                npc: quest.npc.to_owned(),
+                gems: quest.gems.into_iter().map(purchase).collect(),
+            })
+            .collect(),
+        unavailable: list.unavailable.into_iter().map(purchase).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::{storage::memory::MemoryBucket, test_utils::metadata};
+
+    fn pastes() -> Pastes<MemoryBucket> {
+        Pastes {
+            storage: Storage::new(MemoryBucket::default()),
+        }
+    }
+
+    async fn put(pastes: &Pastes<MemoryBucket>, id: &str, private: bool) {
+        let id: PasteId = id.parse().unwrap();
+        let sha1 = crate::crypto::sha1(id.id().as_bytes()).await.unwrap();
+        let metadata = metadata(id.id(), private);
+        pastes
+            .storage
+            .put(&id, &sha1, id.id().as_bytes(), Some(&metadata))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn list_pastes_hides_private() {
+        let pastes = pastes();
+        let user: User = "someone".parse().unwrap();
+        let owner = app::User { name: user.clone() };
+        let filter = PasteFilter::default();
+
+        block_on(async {
+            put(&pastes, "someone:public", false).await;
+            put(&pastes, "someone:private", true).await;
+
+            let titles = |list: Vec<PasteSummary>| {
+                let mut titles = list.into_iter().map(|p| p.title).collect::<Vec<_>>();
+                titles.sort();
+                titles
+            };
+
+            let (_, list) = pastes.list_pastes(None, &user, &filter).await.unwrap();
+            assert_eq!(titles(list), ["public"]);
+
+            let (meta, list) = pastes
+                .list_pastes(Some(&owner), &user, &filter)
+                .await
+                .unwrap();
+            assert_eq!(titles(list), ["private", "public"]);
+            assert!(meta.etag.starts_with("2-"));
+        });
+    }
+
+    #[test]
+    fn validate_trees_unsupported_version() {
+        let xml = include_str!("../../pob/test/316_poison_occ.xml");
+        let pob = pob::SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let warnings = validate_trees(&pob);
+        let unsupported = warnings
+            .iter()
+            .filter(|w| matches!(w, TreeWarning::UnsupportedVersion { version, .. } if version == "3_9"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unsupported,
+            [&TreeWarning::UnsupportedVersion {
+                spec: 2,
+                version: "3_9".to_owned()
+            }]
+        );
+        // Specs of an unsupported version are not validated.
+        assert!(!warnings.iter().any(|w| match w {
+            TreeWarning::UnknownNode { spec, .. } => *spec == 2,
+            _ => false,
+        }));
+
+        // Unsupported versions are not shown on the paste next to the outdated tree lint.
+        assert!(!lint(&pob)
+            .iter()
+            .any(|w| w.contains("unsupported tree version")));
+    }
+
+    #[test]
+    fn tree_progression_points() {
+        let xml = include_str!("../../pob/test/316_poison_occ.xml");
+        let pob = pob::SerdePathOfBuilding::from_xml(xml).unwrap();
+
+        let steps = tree_progression(&pob);
+        assert_eq!(steps.len(), pob.tree_specs().len());
+        assert_eq!(steps[0].previous_points, data::Points::default());
+        assert_eq!(steps[0].removed, data::TreeChanges::default());
+
+        for (previous, step) in steps.iter().zip(&steps[1..]) {
+            assert_eq!(step.previous_points, previous.points);
+        }
+    }
+
+    #[test]
+    fn upload_anonymous() {
+        let pastes = pastes();
+
+        block_on(async {
+            let id = pastes
+                .upload(None, b"content", &metadata("build", false))
+                .await
+                .unwrap()
+                .id;
+            assert!(matches!(id, PasteId::Paste(_)));
+
+            // Anonymous pastes are content addressed.
+            let again = pastes
+                .upload(None, b"content", &metadata("build", false))
+                .await
+                .unwrap()
+                .id;
+            assert_eq!(id, again);
+
+            let stored = pastes.storage.get(&id).await.unwrap().unwrap();
+            assert_eq!(stored.content, "content");
+            assert_eq!(stored.metadata.unwrap().title, "build");
+        });
+    }
+
+    #[test]
+    fn upload_list_delete() {
+        let pastes = pastes();
+        let user: User = "someone".parse().unwrap();
+        let id: UserPasteId = "someone:build".parse::<PasteId>().unwrap().unwrap_user();
+        let filter = PasteFilter::default();
+
+        block_on(async {
+            let uploaded = pastes
+                .upload(Some(id.clone()), b"one", &metadata("one", false))
+                .await
+                .unwrap()
+                .id;
+            assert_eq!(uploaded, id.clone().into());
+
+            // Updating the paste keeps the previous version as a revision.
+            pastes
+                .upload(Some(id.clone()), b"two", &metadata("two", false))
+                .await
+                .unwrap();
+            let revision = pastes.storage.get_revision(&id, 1).await.unwrap().unwrap();
+            assert_eq!(revision.content, "one");
+
+            let (_, list) = pastes.list_pastes(None, &user, &filter).await.unwrap();
+            let titles = list.iter().map(|p| p.title.as_str()).collect::<Vec<_>>();
+            assert_eq!(titles, ["two"]);
+            assert_eq!(list[0].id, uploaded);
+
+            pastes.delete(&id).await.unwrap();
+            let (meta, list) = pastes.list_pastes(None, &user, &filter).await.unwrap();
+            assert!(list.is_empty());
+            assert_eq!(meta.etag, "empty");
+
+            let err = pastes.delete(&id).await.unwrap_err();
+            assert!(matches!(err, crate::Error::NotFound("paste", _)));
+        });
+    }
+
+    #[test]
+    fn upload_replaced_resources() {
+        let pastes = pastes();
+        let id: UserPasteId = "someone:build".parse::<PasteId>().unwrap().unwrap_user();
+
+        let xml = include_str!("../../pob/test/316_poison_occ.xml");
+        let pob = pob::SerdePathOfBuilding::from_xml_retained(xml.to_owned()).unwrap();
+        let export = pob.to_export().unwrap();
+
+        block_on(async {
+            let uploaded = pastes
+                .upload(Some(id.clone()), export.as_bytes(), &metadata("one", false))
+                .await
+                .unwrap();
+            assert_eq!(uploaded.replaced, PasteResources::default());
+
+            // The replaced content is archived as the first revision.
+            let uploaded = pastes
+                .upload(Some(id.clone()), b"two", &metadata("two", false))
+                .await
+                .unwrap();
+            assert_eq!(
+                uploaded.replaced,
+                PasteResources::of(&pob).with_revisions(1)
+            );
+        });
+    }
+}
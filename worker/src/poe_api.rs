@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use pob::character::Character;
 use serde::Deserialize;
 use worker::Url;
 
@@ -156,6 +157,31 @@ impl PoeApi {
 
         Ok(response.json().await?)
     }
+
+    /// Fetches a character of the account, requires the `account:characters` scope.
+    #[tracing::instrument(skip(self))]
+    pub async fn fetch_character(&self, name: &str) -> crate::Result<Character> {
+        let url = format!("https://api.pathofexile.com/character/{name}");
+        let mut response = net::Request::get(&url)
+            .tag("poe_character")
+            .header("Authorization", &format!("Bearer {}", self.access_token))
+            .header("User-Agent", POE_API_USER_AGENT)
+            .send()
+            .await?;
+
+        if response.status_code() == 404 {
+            return Err(crate::Error::NotFound("character", name.to_owned()));
+        }
+        if response.status_code() != 200 {
+            return Err(handle_error("Character", response).await.into());
+        }
+
+        #[derive(Deserialize)]
+        struct CharacterResponse {
+            character: Character,
+        }
+        Ok(response.json::<CharacterResponse>().await?.character)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,8 +1,9 @@
 use std::ops::Deref;
 
+use shared::model::ApiTokenScope;
 use worker::Bucket;
 
-use crate::{cache::CacheEntry, route, utils::RequestExt};
+use crate::{cache::CacheEntry, route, tokens::TokenSession, utils::RequestExt};
 
 pub struct RequestContext {
     req: worker::Request,
@@ -11,6 +12,7 @@ pub struct RequestContext {
     route: route::Route,
     trace_id: sentry::TraceId,
     session: Option<app::User>,
+    token: Option<TokenSession>,
 }
 
 // TODO this could/should be a Session() type
@@ -21,6 +23,7 @@ impl RequestContext {
         let route = route::Route::new(&req);
         let env = Env::new(env);
         let session = parse_session(&req, &env).await;
+        let token = parse_token(&req, &env).await;
         Self {
             req,
             env,
@@ -28,6 +31,7 @@ impl RequestContext {
             route,
             trace_id: sentry::TraceId::default(),
             session,
+            token,
         }
     }
 
@@ -104,7 +108,10 @@ impl RequestContext {
 
     pub async fn get_sentry_user(&self) -> sentry::User {
         sentry::User {
-            username: self.session().map(|user| user.name.clone().into()),
+            username: self
+                .session()
+                .or_else(|| self.token.as_ref().map(|token| &token.user))
+                .map(|user| user.name.clone().into()),
             ip_address: self.req.headers().get("cf-connecting-ip").ok().flatten(),
             country: self.req.cf().country(),
         }
@@ -123,8 +130,18 @@ impl RequestContext {
         self.session.as_ref()
     }
 
-    pub fn is_logged_in(&self) -> bool {
-        self.session.is_some()
+    /// Returns the user if the request is allowed to act with `scope` on their behalf.
+    ///
+    /// A browser session grants every scope, an API token only the scopes it was created with.
+    pub fn authorized(&self, scope: ApiTokenScope) -> Session<'_> {
+        if let Some(session) = self.session() {
+            return Some(session);
+        }
+
+        self.token
+            .as_ref()
+            .filter(|token| token.scopes.contains(&scope))
+            .map(|token| &token.user)
     }
 
     pub fn cache_entry(&self) -> CacheEntry {
@@ -145,7 +162,7 @@ pub struct Env {
 }
 
 impl Env {
-    fn new(inner: worker::Env) -> Self {
+    pub(crate) fn new(inner: worker::Env) -> Self {
         Self { inner }
     }
 
@@ -178,3 +195,8 @@ async fn parse_session(req: &worker::Request, env: &Env) -> Option<app::User> {
         }
     }
 }
+
+async fn parse_token(req: &worker::Request, env: &Env) -> Option<TokenSession> {
+    let token = req.bearer_token()?;
+    crate::tokens::verify(env, &token).await
+}
@@ -343,6 +343,17 @@ impl Response {
         response
     }
 
+    /// Creates a copy of the response, streamed bodies are split into two streams.
+    pub fn duplicate(&mut self) -> Self {
+        Self {
+            status_code: self.status_code,
+            headers: self.headers.clone(),
+            body: clone_body(&mut self.body),
+            meta: self.meta.clone(),
+            skip_sentry: self.skip_sentry,
+        }
+    }
+
     pub fn for_cache(&mut self) -> worker::Response {
         let body = clone_body(&mut self.body);
         let mut response = worker::Response::from_body(body)
@@ -76,18 +76,45 @@ pub enum GetEndpoints {
     // TODO: maybe this should be moved to a different path?
     #[to("/api/internal/user/<user>")]
     User(User),
+    /// Lists the personal API tokens of the logged in user.
+    #[to("/api/internal/tokens")]
+    Tokens,
     #[to("/<id>/raw")]
     Paste(Id),
     #[to("/u/<name>/<id>/raw")]
     UserPaste(User, Id),
     #[to("/<id>/json")]
     PasteJson(Id),
+    /// PNG summary of a build, used for social embeds.
+    #[to("/<id>/card.png")]
+    PasteCard(Id),
+    #[to("/u/<name>/<id>/card.png")]
+    UserPasteCard(User, Id),
     #[to("/u/<name>/<id>/json")]
     UserPasteJson(User, Id),
     #[to("/<id>/xml")]
     PasteXml(Id),
     #[to("/u/<name>/<id>/xml")]
     UserPasteXml(User, Id),
+    /// SVG of the passive tree of a tree spec of the paste, by index.
+    #[to("/<id>/trees/<n>")]
+    PasteTree(Id, u16),
+    #[to("/u/<name>/<id>/trees/<n>")]
+    UserPasteTree(User, Id, u16),
+    /// Trade site search query for items similar to an item of the paste, by PoB item id.
+    #[to("/<id>/items/<n>/trade")]
+    PasteItemTrade(Id, u16),
+    #[to("/u/<name>/<id>/items/<n>/trade")]
+    UserPasteItemTrade(User, Id, u16),
+    /// Lists all previous revisions of a user paste.
+    #[to("/u/<name>/<id>/revisions")]
+    UserPasteRevisions(User, Id),
+    #[to("/u/<name>/<id>/rev/<rev>")]
+    UserPasteRevision(User, Id, u32),
+    #[to("/u/<name>/<id>/rev/<rev>/json")]
+    UserPasteRevisionJson(User, Id, u32),
+    #[to("/u/<name>/<id>/rev/<rev>/xml")]
+    UserPasteRevisionXml(User, Id, u32),
     /// Path of Building endpoint for importing builds.
     /// This supports the anonymous and user scoped paste IDs.
     /// User scoped paste IDs are used in `pob://` protocol links.
@@ -97,8 +124,25 @@ pub enum GetEndpoints {
     /// Path of Building endpoint for importing user paste URLs.
     #[to("/pob/u/<name>/<id>")]
     PobUserPaste(User, LenientId<Id>),
+    #[to("/api/v1/openapi.json")]
+    V1OpenApi,
+    #[to("/api/v1/paste/<id>")]
+    V1Build(PasteId),
+    #[to("/api/v1/paste/<id>/stats")]
+    V1Stats(PasteId),
+    #[to("/api/v1/paste/<id>/skills")]
+    V1Skills(PasteId),
+    #[to("/api/v1/paste/<id>/items")]
+    V1Items(PasteId),
+    #[to("/api/v1/paste/<id>/tree")]
+    V1Tree(PasteId),
+    #[to("/api/v1/paste/<id>/gems")]
+    V1Gems(PasteId),
     #[to("/login")]
     Login,
+    /// Logs in with the characters scope and snapshots the character as a user paste.
+    #[to("/login/character/<name>")]
+    LoginCharacter(String),
     #[to("/oauth2/authorization/poe")]
     Oauht2Poe,
     #[not_found]
@@ -111,6 +155,14 @@ pub enum PostEndpoints {
     Upload,
     #[to("/pob/")]
     PobUpload,
+    /// Imports a build from another site, see [`shared::import`].
+    #[to("/api/internal/import")]
+    Import,
+    #[to("/api/internal/tokens")]
+    CreateToken,
+    /// Restores a deleted user paste from the trash.
+    #[to("/api/internal/paste/<id>/undelete")]
+    UndeletePaste(PasteId),
     #[not_found]
     NotFound,
 }
@@ -119,6 +171,8 @@ pub enum PostEndpoints {
 pub enum DeleteEndpoints {
     #[to("/api/internal/paste/<id>")]
     DeletePaste(PasteId),
+    #[to("/api/internal/tokens/<id>")]
+    DeleteToken(Id),
     #[not_found]
     NotFound,
 }
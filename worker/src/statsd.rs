@@ -8,6 +8,9 @@ pub enum Counters {
     StorageList,
     Fetch,
     PobUpload,
+    PobImport,
+    PobCharacter,
+    PobInvalidTree,
     ApiLogin,
     ApiLoginSuccess,
 }
@@ -24,6 +27,9 @@ impl sentry::MetricName for Counters {
             Counters::StorageList => "storage.list",
             Counters::Fetch => "fetch.total",
             Counters::PobUpload => "pob.upload",
+            Counters::PobImport => "pob.import",
+            Counters::PobCharacter => "pob.character",
+            Counters::PobInvalidTree => "pob.invalid_tree",
             Counters::ApiLogin => "api.login",
             Counters::ApiLoginSuccess => "api.login_success",
         }
@@ -0,0 +1,67 @@
+use std::{cell::RefCell, collections::BTreeMap, ops::Bound};
+
+use super::object::{Listing, Object, ObjectStore, PutOptions};
+use crate::Result;
+
+/// In-memory [`ObjectStore`] for tests.
+#[derive(Default)]
+pub struct MemoryBucket {
+    objects: RefCell<BTreeMap<String, Object>>,
+    uploads: RefCell<u64>,
+}
+
+impl ObjectStore for MemoryBucket {
+    async fn get(&self, key: &str) -> Result<Option<Object>> {
+        Ok(self.objects.borrow().get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, body: &[u8], options: PutOptions<'_>) -> Result<()> {
+        // Monotonic fake upload time, keeps ordering by upload time deterministic.
+        let uploaded = {
+            let mut uploads = self.uploads.borrow_mut();
+            *uploads += 1;
+            *uploads
+        };
+
+        let object = Object {
+            key: key.to_owned(),
+            etag: format!("{key}-{uploaded}"),
+            uploaded,
+            custom_metadata: options.custom_metadata,
+            body: Some(body.to_vec()),
+        };
+        self.objects.borrow_mut().insert(key.to_owned(), object);
+
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str, limit: u32, cursor: Option<&str>) -> Result<Listing> {
+        let start = match cursor {
+            Some(cursor) => Bound::Excluded(cursor.to_owned()),
+            None => Bound::Included(prefix.to_owned()),
+        };
+
+        let stored = self.objects.borrow();
+        let mut matching = stored
+            .range((start, Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(_, obj)| Object {
+                body: None,
+                ..obj.clone()
+            });
+
+        let objects = matching.by_ref().take(limit as usize).collect::<Vec<_>>();
+        // The cursor is the key of the last returned object, the next page starts after it.
+        let cursor = matching
+            .next()
+            .and_then(|_| objects.last())
+            .map(|obj| obj.key.clone());
+
+        Ok(Listing { objects, cursor })
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.objects.borrow_mut().remove(key);
+        Ok(())
+    }
+}
@@ -1,21 +1,36 @@
 use serde::{Deserialize, Serialize};
 use shared::{
-    model::{ListPaste, PasteMetadata},
-    PasteId, User,
+    import::Import,
+    model::{ApiToken, ListPaste, PasteMetadata, PasteRevision},
+    PasteId, User, UserPasteId,
 };
 
 use crate::{
+    consts,
     crypto::Sha1,
     request_context::{Env, FromEnv},
     statsd::Counters,
     Result,
 };
 
-mod pastebin;
+#[cfg(test)]
+pub mod memory;
+mod object;
+mod objects;
+mod paste;
 mod r2;
 mod utils;
 
-pub(crate) use utils::{strip_prefix, to_path_r2, to_prefix_r2};
+pub use self::{object::ObjectStore, paste::PasteStore};
+
+/// Store used for pastes.
+pub type Bucket = r2::R2Bucket;
+
+pub(crate) use utils::{
+    strip_prefix, to_path_r2, to_prefix_r2, to_revision_path_r2, to_revision_prefix_r2,
+    to_token_path_r2, to_token_prefix_r2, to_trash_path_r2, to_trash_revision_prefix_r2,
+    TRASH_PREFIX_R2,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StoredPaste {
@@ -27,37 +42,138 @@ pub struct StoredPaste {
     pub content: String,
 }
 
-pub struct Storage {
-    r2: r2::R2Storage,
+pub struct Storage<S = Bucket> {
+    objects: objects::ObjectStorage<S>,
 }
 
-impl FromEnv for Storage {
+impl<S: FromEnv> FromEnv for Storage<S> {
     fn from_env(env: &Env) -> Option<Self> {
         Some(Self {
-            r2: r2::R2Storage::from_env(env)?,
+            objects: objects::ObjectStorage::from_env(env)?,
         })
     }
 }
 
-impl Storage {
+impl<S: ObjectStore> Storage<S> {
+    #[cfg(test)]
+    pub fn new(store: S) -> Self {
+        Self {
+            objects: objects::ObjectStorage::new(store),
+        }
+    }
+
     pub async fn get(&self, id: &PasteId) -> Result<Option<StoredPaste>> {
-        if pastebin::could_be_pastebin_id(id) {
-            tracing::info!("fetching from pastebin.com");
+        if let Some(import) = Import::from_paste_id(id) {
+            tracing::info!("fetching from {}", import.importer);
             sentry::counter(Counters::StorageGet)
                 .inc(1)
-                .tag("type", "pastebin");
-            return pastebin::get(id).await;
+                .tag("type", import.importer.name());
+            return import_paste(&import).await;
         }
 
         sentry::counter(Counters::StorageGet)
             .inc(1)
             .tag("type", "r2");
-        self.r2.get(id).await
+        self.objects.get(id).await
     }
 
-    pub async fn delete(&self, id: &PasteId) -> Result<()> {
+    /// Moves a user paste and its revisions into the trash.
+    ///
+    /// The paste can be restored with [`Self::restore`] until [`consts::TRASH_RETENTION`] passed,
+    /// afterwards it is removed by [`Self::purge_expired_trash`].
+    /// A previously trashed paste with the same id is replaced.
+    /// Returns `false` if the paste does not exist.
+    pub async fn trash(&self, id: &UserPasteId) -> Result<bool> {
+        let paste_id: PasteId = id.clone().into();
+        let Some(paste) = self.objects.get(&paste_id).await? else {
+            return Ok(false);
+        };
+        let sha1 = crate::crypto::sha1(paste.content.as_bytes()).await?;
+
+        // Revisions of an older paste with the same id must not be mixed with the new ones.
+        self.purge(id).await?;
+
+        sentry::counter(Counters::StoragePut)
+            .inc(1)
+            .tag("type", "trash");
+        self.objects.put_trash(id, &sha1, &paste).await?;
+        // A new paste with the same id starts with an empty revision history.
+        self.objects.trash_revisions(id).await?;
+
         sentry::counter(Counters::StorageDelete).inc(1);
-        self.r2.delete(id).await
+        self.objects.delete(&paste_id).await?;
+
+        Ok(true)
+    }
+
+    /// Restores a user paste and its revisions from the trash.
+    ///
+    /// `now` is the current time in milliseconds since the unix epoch.
+    /// Returns `false` if there is nothing to restore or the retention window expired.
+    pub async fn restore(&self, id: &UserPasteId, now: u64) -> Result<bool> {
+        sentry::counter(Counters::StorageGet)
+            .inc(1)
+            .tag("type", "trash");
+        let Some((paste, deleted)) = self.objects.get_trash(id).await? else {
+            return Ok(false);
+        };
+
+        if is_expired(now.saturating_sub(deleted)) {
+            tracing::info!(%id, "trash expired, purging");
+            self.purge(id).await?;
+            return Ok(false);
+        }
+
+        let paste_id: PasteId = id.clone().into();
+        if self.objects.get(&paste_id).await?.is_some() {
+            return Err(crate::Error::BadRequest(
+                "A paste with the same id already exists".to_owned(),
+            ));
+        }
+
+        let sha1 = crate::crypto::sha1(paste.content.as_bytes()).await?;
+        sentry::counter(Counters::StoragePut)
+            .inc(1)
+            .tag("type", "restore");
+        self.objects.put_restored(id, &sha1, &paste).await?;
+        self.objects.restore_revisions(id).await?;
+
+        sentry::counter(Counters::StorageDelete)
+            .inc(1)
+            .tag("type", "trash");
+        self.objects.delete_trash(id).await?;
+
+        Ok(true)
+    }
+
+    /// Permanently deletes all trashed user pastes older than [`consts::TRASH_RETENTION`].
+    ///
+    /// `now` is the current time in milliseconds since the unix epoch.
+    /// Returns the number of purged pastes.
+    pub async fn purge_expired_trash(&self, now: u64) -> Result<usize> {
+        sentry::counter(Counters::StorageList)
+            .inc(1)
+            .tag("type", "trash");
+
+        let mut purged = 0;
+        for (id, deleted) in self.objects.list_trash().await? {
+            if is_expired(now.saturating_sub(deleted)) {
+                tracing::info!(%id, "trash expired, purging");
+                self.purge(&id).await?;
+                purged += 1;
+            }
+        }
+
+        Ok(purged)
+    }
+
+    /// Permanently deletes a trashed user paste and all of its revisions.
+    async fn purge(&self, id: &UserPasteId) -> Result<()> {
+        sentry::counter(Counters::StorageDelete)
+            .inc(1)
+            .tag("type", "trash");
+        self.objects.delete_trash_revisions(id).await?;
+        self.objects.delete_trash(id).await
     }
 
     pub async fn put(
@@ -68,11 +184,326 @@ impl Storage {
         metadata: Option<&PasteMetadata>,
     ) -> Result<()> {
         sentry::counter(Counters::StoragePut).inc(1);
-        self.r2.put(id, sha1, data, metadata).await
+        self.objects.put(id, sha1, data, metadata).await
+    }
+
+    /// Moves the current version of a user paste into its revision history,
+    /// before it is replaced with a new version with the checksum `new_sha1`.
+    ///
+    /// Returns the number of the created revision, `None` if the paste does not exist yet
+    /// or its content is unchanged.
+    pub async fn archive(&self, id: &UserPasteId, new_sha1: &Sha1) -> Result<Option<u32>> {
+        let Some(current) = self.objects.get(&id.clone().into()).await? else {
+            return Ok(None);
+        };
+
+        let sha1 = crate::crypto::sha1(current.content.as_bytes()).await?;
+        if sha1.0 == new_sha1.0 {
+            return Ok(None);
+        }
+
+        let revision = self
+            .objects
+            .list_revisions(id)
+            .await?
+            .last()
+            .map_or(1, |r| r.revision + 1);
+
+        sentry::counter(Counters::StoragePut)
+            .inc(1)
+            .tag("type", "revision");
+        self.objects
+            .put_revision(id, revision, &sha1, &current)
+            .await?;
+
+        Ok(Some(revision))
+    }
+
+    pub async fn get_revision(
+        &self,
+        id: &UserPasteId,
+        revision: u32,
+    ) -> Result<Option<StoredPaste>> {
+        sentry::counter(Counters::StorageGet)
+            .inc(1)
+            .tag("type", "revision");
+        self.objects.get_revision(id, revision).await
+    }
+
+    pub async fn list_revisions(&self, id: &UserPasteId) -> Result<Vec<PasteRevision>> {
+        sentry::counter(Counters::StorageList)
+            .inc(1)
+            .tag("type", "revision");
+        self.objects.list_revisions(id).await
     }
 
     pub async fn list(&self, user: &User) -> Result<Vec<ListPaste>> {
         sentry::counter(Counters::StorageList).inc(1);
-        self.r2.list(user).await
+        self.objects.list(user).await
+    }
+
+    pub async fn get_token(&self, user: &User, id: &str) -> Result<Option<ApiToken>> {
+        sentry::counter(Counters::StorageGet)
+            .inc(1)
+            .tag("type", "token");
+        self.objects.get_token(user, id).await
+    }
+
+    pub async fn put_token(&self, user: &User, token: &ApiToken) -> Result<()> {
+        sentry::counter(Counters::StoragePut)
+            .inc(1)
+            .tag("type", "token");
+        self.objects.put_token(user, token).await
+    }
+
+    pub async fn list_tokens(&self, user: &User) -> Result<Vec<ApiToken>> {
+        sentry::counter(Counters::StorageList)
+            .inc(1)
+            .tag("type", "token");
+        self.objects.list_tokens(user).await
+    }
+
+    pub async fn delete_token(&self, user: &User, id: &str) -> Result<()> {
+        sentry::counter(Counters::StorageDelete)
+            .inc(1)
+            .tag("type", "token");
+        self.objects.delete_token(user, id).await
+    }
+}
+
+/// Fetches a build from another site, it is served like a paste but never stored.
+async fn import_paste(import: &Import) -> Result<Option<StoredPaste>> {
+    let Some(content) = crate::import::fetch(import).await? else {
+        return Ok(None);
+    };
+
+    Ok(Some(StoredPaste {
+        content,
+        entity_id: format!("{}-{}", import.importer, import.id()),
+        last_modified: 0,
+        metadata: None,
+    }))
+}
+
+/// Whether a paste trashed `age` milliseconds ago exceeded the retention window.
+fn is_expired(age: u64) -> bool {
+    u128::from(age) > consts::TRASH_RETENTION.as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::test_utils::metadata;
+
+    async fn put(storage: &Storage<memory::MemoryBucket>, id: &str, content: &str) {
+        let id: PasteId = id.parse().unwrap();
+        let sha1 = crate::crypto::sha1(content.as_bytes()).await.unwrap();
+        let metadata = metadata(content, false);
+        storage
+            .put(&id, &sha1, content.as_bytes(), Some(&metadata))
+            .await
+            .unwrap();
+    }
+
+    /// Archives the user paste `id` before it is replaced with `content`.
+    async fn archive(
+        storage: &Storage<memory::MemoryBucket>,
+        id: &UserPasteId,
+        content: &str,
+    ) -> Option<u32> {
+        let sha1 = crate::crypto::sha1(content.as_bytes()).await.unwrap();
+        storage.archive(id, &sha1).await.unwrap()
+    }
+
+    #[test]
+    fn put_list_trash() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let user: User = "someone".parse().unwrap();
+        let id: UserPasteId = "someone:paste1".parse::<PasteId>().unwrap().unwrap_user();
+
+        block_on(async {
+            put(&storage, "someone:paste1", "one").await;
+            put(&storage, "someone:paste2", "two").await;
+            put(&storage, "other:paste1", "three").await;
+
+            let pastes = storage.list(&user).await.unwrap();
+            let names = pastes.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+            assert_eq!(names, ["paste1", "paste2"]);
+            assert_eq!(pastes[0].metadata.title, "one");
+
+            assert!(storage.trash(&id).await.unwrap());
+            assert!(!storage.trash(&id).await.unwrap());
+            assert!(storage.get(&id.clone().into()).await.unwrap().is_none());
+            assert_eq!(storage.list(&user).await.unwrap().len(), 1);
+
+            let (trashed, _) = storage.objects.get_trash(&id).await.unwrap().unwrap();
+            assert_eq!(trashed.content, "one");
+        });
+    }
+
+    #[test]
+    fn list_many_pastes() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let user: User = "someone".parse().unwrap();
+
+        block_on(async {
+            // More pastes than a single listing page returns.
+            for i in 0..1001 {
+                put(&storage, &format!("someone:paste{i}"), "content").await;
+            }
+
+            assert_eq!(storage.list(&user).await.unwrap().len(), 1001);
+        });
+    }
+
+    #[test]
+    fn archive_revisions() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let id: UserPasteId = "someone:paste1".parse::<PasteId>().unwrap().unwrap_user();
+
+        block_on(async {
+            assert_eq!(archive(&storage, &id, "one").await, None);
+
+            put(&storage, "someone:paste1", "one").await;
+            // Unchanged content is not archived.
+            assert_eq!(archive(&storage, &id, "one").await, None);
+            assert_eq!(archive(&storage, &id, "two").await, Some(1));
+            put(&storage, "someone:paste1", "two").await;
+            assert_eq!(archive(&storage, &id, "three").await, Some(2));
+
+            let revisions = storage.list_revisions(&id).await.unwrap();
+            let titles = revisions
+                .iter()
+                .map(|r| r.title.as_deref())
+                .collect::<Vec<_>>();
+            assert_eq!(titles, [Some("one"), Some("two")]);
+
+            let revision = storage.get_revision(&id, 1).await.unwrap().unwrap();
+            assert_eq!(revision.content, "one");
+        });
+    }
+
+    #[test]
+    fn archive_after_many_revisions() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let id: UserPasteId = "someone:paste1".parse::<PasteId>().unwrap().unwrap_user();
+
+        block_on(async {
+            put(&storage, "someone:paste1", "current").await;
+            let paste = storage.get(&id.clone().into()).await.unwrap().unwrap();
+            let sha1 = crate::crypto::sha1(paste.content.as_bytes()).await.unwrap();
+            // More revisions than a single listing returns.
+            for revision in 1..=1001 {
+                storage
+                    .objects
+                    .put_revision(&id, revision, &sha1, &paste)
+                    .await
+                    .unwrap();
+            }
+
+            assert_eq!(storage.list_revisions(&id).await.unwrap().len(), 1001);
+            assert_eq!(archive(&storage, &id, "new").await, Some(1002));
+        });
+    }
+
+    #[test]
+    fn trash_moves_revisions() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let id: UserPasteId = "someone:paste1".parse::<PasteId>().unwrap().unwrap_user();
+
+        block_on(async {
+            put(&storage, "someone:paste1", "one").await;
+            assert_eq!(archive(&storage, &id, "two").await, Some(1));
+            put(&storage, "someone:paste1", "two").await;
+
+            assert!(storage.trash(&id).await.unwrap());
+            assert!(storage.list_revisions(&id).await.unwrap().is_empty());
+
+            let (_, deleted) = storage.objects.get_trash(&id).await.unwrap().unwrap();
+            assert!(storage.restore(&id, deleted).await.unwrap());
+            let paste = storage.get(&id.clone().into()).await.unwrap().unwrap();
+            assert_eq!(paste.content, "two");
+            let revision = storage.get_revision(&id, 1).await.unwrap().unwrap();
+            assert_eq!(revision.content, "one");
+            assert!(storage.objects.get_trash(&id).await.unwrap().is_none());
+
+            // A new paste with the same id does not inherit the trashed revisions.
+            assert!(storage.trash(&id).await.unwrap());
+            put(&storage, "someone:paste1", "new").await;
+            assert!(storage.list_revisions(&id).await.unwrap().is_empty());
+            assert_eq!(archive(&storage, &id, "newer").await, Some(1));
+            let revision = storage.get_revision(&id, 1).await.unwrap().unwrap();
+            assert_eq!(revision.content, "new");
+        });
+    }
+
+    #[test]
+    fn purge_expired_trash() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let id: UserPasteId = "someone:paste1".parse::<PasteId>().unwrap().unwrap_user();
+
+        block_on(async {
+            put(&storage, "someone:paste1", "one").await;
+            assert_eq!(archive(&storage, &id, "two").await, Some(1));
+            assert!(storage.trash(&id).await.unwrap());
+
+            let (_, deleted) = storage.objects.get_trash(&id).await.unwrap().unwrap();
+            assert_eq!(storage.purge_expired_trash(deleted).await.unwrap(), 0);
+            assert!(storage.objects.get_trash(&id).await.unwrap().is_some());
+
+            let expired = deleted + consts::TRASH_RETENTION.as_millis() as u64 + 1;
+            assert_eq!(storage.purge_expired_trash(expired).await.unwrap(), 1);
+            assert!(storage.objects.get_trash(&id).await.unwrap().is_none());
+
+            storage.objects.restore_revisions(&id).await.unwrap();
+            assert!(storage.list_revisions(&id).await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn tokens() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let user: User = "someone".parse().unwrap();
+        let token = |id: &str, created| ApiToken {
+            id: id.to_owned(),
+            name: id.to_owned(),
+            scopes: Vec::new(),
+            created,
+        };
+
+        block_on(async {
+            storage.put_token(&user, &token("b", 2)).await.unwrap();
+            storage.put_token(&user, &token("a", 1)).await.unwrap();
+
+            let ids = storage.list_tokens(&user).await.unwrap();
+            let ids = ids.iter().map(|t| t.id.as_str()).collect::<Vec<_>>();
+            assert_eq!(ids, ["a", "b"]);
+
+            storage.delete_token(&user, "a").await.unwrap();
+            assert!(storage.get_token(&user, "a").await.unwrap().is_none());
+            assert!(storage.get_token(&user, "b").await.unwrap().is_some());
+        });
+    }
+
+    #[test]
+    fn list_many_tokens() {
+        let storage = Storage::new(memory::MemoryBucket::default());
+        let user: User = "someone".parse().unwrap();
+
+        block_on(async {
+            // More tokens than a single listing page returns.
+            for i in 0..1001 {
+                let token = ApiToken {
+                    id: format!("token{i}"),
+                    name: format!("token{i}"),
+                    scopes: Vec::new(),
+                    created: i,
+                };
+                storage.put_token(&user, &token).await.unwrap();
+            }
+            assert_eq!(storage.list_tokens(&user).await.unwrap().len(), 1001);
+        });
     }
 }
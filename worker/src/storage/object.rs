@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::{crypto::Sha1, Result};
+
+/// An object stored in an [`ObjectStore`].
+#[derive(Debug, Clone)]
+pub struct Object {
+    pub key: String,
+    pub etag: String,
+    /// Upload time in milliseconds since the unix epoch.
+    pub uploaded: u64,
+    pub custom_metadata: HashMap<String, String>,
+    /// Content of the object, only available when the object was fetched directly.
+    pub body: Option<Vec<u8>>,
+}
+
+pub struct PutOptions<'a> {
+    pub content_type: &'a str,
+    pub custom_metadata: HashMap<String, String>,
+    /// Checksum the store verifies the uploaded content against.
+    pub sha1: Option<&'a Sha1>,
+}
+
+/// A page of objects returned by [`ObjectStore::list`].
+#[derive(Debug, Default)]
+pub struct Listing {
+    pub objects: Vec<Object>,
+    /// Cursor to continue the listing with, `None` if all objects were listed.
+    pub cursor: Option<String>,
+}
+
+/// Storage backend for pastes, revisions and tokens.
+///
+/// A flat key value store for objects with custom metadata, modelled after R2:
+/// keys are `/` separated paths and listing works by prefix.
+pub trait ObjectStore {
+    async fn get(&self, key: &str) -> Result<Option<Object>>;
+
+    async fn put(&self, key: &str, body: &[u8], options: PutOptions<'_>) -> Result<()>;
+
+    /// Lists at most `limit` objects with the given `prefix`, without their body.
+    ///
+    /// Objects are listed in lexicographical order of their keys, the listing
+    /// continues after the object the `cursor` of a previous listing points to.
+    async fn list(&self, prefix: &str, limit: u32, cursor: Option<&str>) -> Result<Listing>;
+
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Lists all objects with the given `prefix`, following the cursor through all pages.
+    async fn list_all(&self, prefix: &str) -> Result<Vec<Object>> {
+        let mut objects = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let listing = self.list(prefix, 1000, cursor.as_deref()).await?;
+            objects.extend(listing.objects);
+
+            match listing.cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(objects),
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use shared::{
+    model::{ApiToken, ListPaste, PasteMetadata, PasteRevision},
+    PasteId, User, UserPasteId,
+};
+
+use super::{
+    object::{Object, ObjectStore, PutOptions},
+    PasteStore, StoredPaste,
+};
+use crate::{
+    crypto::Sha1,
+    request_context::{Env, FromEnv},
+    utils::{b64_decode, b64_encode},
+    Result,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+struct KvMetadata {
+    #[serde(default)]
+    last_modified: u64,
+    #[serde(default)]
+    entity_id: Option<String>,
+    #[serde(flatten)]
+    metadata: Option<PasteMetadata>,
+}
+
+/// Stores pastes, revisions and tokens as objects in a [`ObjectStore`].
+pub struct ObjectStorage<S> {
+    store: S,
+}
+
+impl<S: FromEnv> FromEnv for ObjectStorage<S> {
+    fn from_env(env: &Env) -> Option<Self> {
+        Some(Self {
+            store: S::from_env(env)?,
+        })
+    }
+}
+
+impl<S: ObjectStore> ObjectStorage<S> {
+    #[cfg(test)]
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_revision(
+        &self,
+        id: &UserPasteId,
+        revision: u32,
+    ) -> Result<Option<StoredPaste>> {
+        let path = super::to_revision_path_r2(id, revision);
+        self.get_path(&path).await
+    }
+
+    async fn get_path(&self, path: &str) -> Result<Option<StoredPaste>> {
+        Ok(self.get_object(path).await?.map(|(paste, _)| paste))
+    }
+
+    /// Returns the paste stored at `path` and the time the object was uploaded.
+    async fn get_object(&self, path: &str) -> Result<Option<(StoredPaste, u64)>> {
+        let Some(obj) = self.store.get(path).await? else {
+            return Ok(None);
+        };
+
+        let (mtime, metadata) = to_metadata(&obj)?;
+        let content = obj.body.map(String::from_utf8).transpose().map_err(|_| {
+            crate::Error::StorageError(format!("paste '{path}' is not valid utf-8"))
+        })?;
+        let Some(content) = content else {
+            return Ok(None);
+        };
+
+        let paste = StoredPaste {
+            content,
+            metadata,
+            entity_id: obj.etag,
+            last_modified: mtime,
+        };
+
+        Ok(Some((paste, obj.uploaded)))
+    }
+
+    /// Stores a copy of `paste` as `revision` of the user paste `id`.
+    #[tracing::instrument(skip(self, sha1, paste))]
+    pub async fn put_revision(
+        &self,
+        id: &UserPasteId,
+        revision: u32,
+        sha1: &Sha1,
+        paste: &StoredPaste,
+    ) -> Result<()> {
+        let path = super::to_revision_path_r2(id, revision);
+        self.put_copy(&path, sha1, paste).await
+    }
+
+    #[tracing::instrument(skip(self, sha1, paste))]
+    pub async fn put_trash(
+        &self,
+        id: &UserPasteId,
+        sha1: &Sha1,
+        paste: &StoredPaste,
+    ) -> Result<()> {
+        let path = super::to_trash_path_r2(id);
+        self.put_copy(&path, sha1, paste).await
+    }
+
+    /// Stores a paste restored from the trash back at its original location.
+    #[tracing::instrument(skip(self, sha1, paste))]
+    pub async fn put_restored(
+        &self,
+        id: &UserPasteId,
+        sha1: &Sha1,
+        paste: &StoredPaste,
+    ) -> Result<()> {
+        let path = super::to_path_r2(&id.clone().into())?;
+        self.put_copy(&path, sha1, paste).await
+    }
+
+    /// Returns a trashed paste together with the time it was deleted.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trash(&self, id: &UserPasteId) -> Result<Option<(StoredPaste, u64)>> {
+        let path = super::to_trash_path_r2(id);
+        self.get_object(&path).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_trash(&self, id: &UserPasteId) -> Result<()> {
+        let path = super::to_trash_path_r2(id);
+        self.store.delete(&path).await
+    }
+
+    /// Writes `paste` to `path`, keeping its metadata and modification time.
+    async fn put_copy(&self, path: &str, sha1: &Sha1, paste: &StoredPaste) -> Result<()> {
+        let mut custom_metdata = HashMap::new();
+        if let Some(metadata) = &paste.metadata {
+            let metadata = b64_encode(serde_json::to_string(metadata)?);
+            custom_metdata.insert("metadata".to_owned(), metadata);
+        }
+        // Keep the modification time of the original paste.
+        let mtime = paste.last_modified as f64 / 1000.0;
+        custom_metdata.insert("mtime".to_owned(), mtime.to_string());
+        custom_metdata.insert("sha1".to_owned(), sha1.to_hex());
+
+        let options = PutOptions {
+            content_type: "text/plain",
+            custom_metadata: custom_metdata,
+            sha1: Some(sha1),
+        };
+        self.store
+            .put(path, paste.content.as_bytes(), options)
+            .await
+    }
+
+    /// Lists all revisions of a user paste, ordered from oldest to newest.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_revisions(&self, id: &UserPasteId) -> Result<Vec<PasteRevision>> {
+        let prefix = super::to_revision_prefix_r2(id);
+
+        // Revisions are never pruned, list all pages to not miss the latest revisions.
+        let mut revisions = self
+            .store
+            .list_all(&prefix)
+            .await?
+            .into_iter()
+            .map(|mut obj| {
+                let name = super::strip_prefix(&obj.key, &prefix)?;
+                let revision = name.parse().map_err(|_| {
+                    crate::Error::StorageError(format!("invalid revision {id}:{name}"))
+                })?;
+                let (mtime, metadata) = to_metadata(&obj)?;
+                let sha1 = obj.custom_metadata.remove("sha1").unwrap_or_default();
+
+                Ok(PasteRevision {
+                    revision,
+                    title: metadata.map(|m| m.title),
+                    sha1,
+                    last_modified: mtime,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        revisions.sort_unstable_by_key(|r| r.revision);
+
+        Ok(revisions)
+    }
+
+    /// Moves all revisions of a user paste into the trash.
+    #[tracing::instrument(skip(self))]
+    pub async fn trash_revisions(&self, id: &UserPasteId) -> Result<()> {
+        let from = super::to_revision_prefix_r2(id);
+        let to = super::to_trash_revision_prefix_r2(id);
+        self.move_all(&from, &to).await
+    }
+
+    /// Moves all revisions of a trashed user paste back into its revision history.
+    #[tracing::instrument(skip(self))]
+    pub async fn restore_revisions(&self, id: &UserPasteId) -> Result<()> {
+        let from = super::to_trash_revision_prefix_r2(id);
+        let to = super::to_revision_prefix_r2(id);
+        self.move_all(&from, &to).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_trash_revisions(&self, id: &UserPasteId) -> Result<()> {
+        let prefix = super::to_trash_revision_prefix_r2(id);
+        for obj in self.store.list_all(&prefix).await? {
+            self.store.delete(&obj.key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists all trashed user pastes together with the time they were deleted.
+    #[tracing::instrument(skip(self))]
+    pub async fn list_trash(&self) -> Result<Vec<(UserPasteId, u64)>> {
+        let prefix = super::TRASH_PREFIX_R2;
+
+        self.store
+            .list_all(prefix)
+            .await?
+            .into_iter()
+            .map(|obj| {
+                let name = super::strip_prefix(&obj.key, prefix)?;
+                let id = name
+                    .split_once('/')
+                    .and_then(|(user, id)| {
+                        Some(UserPasteId {
+                            user: user.parse().ok()?,
+                            id: id.parse().ok()?,
+                        })
+                    })
+                    .ok_or_else(|| {
+                        crate::Error::StorageError(format!("invalid trash entry '{}'", obj.key))
+                    })?;
+
+                Ok((id, obj.uploaded))
+            })
+            .collect()
+    }
+
+    /// Moves all objects with the prefix `from` to the prefix `to`,
+    /// keeping their metadata and modification time.
+    async fn move_all(&self, from: &str, to: &str) -> Result<()> {
+        for obj in self.store.list_all(from).await? {
+            let name = super::strip_prefix(&obj.key, from)?;
+            let Some((paste, _)) = self.get_object(&obj.key).await? else {
+                continue;
+            };
+
+            let sha1 = crate::crypto::sha1(paste.content.as_bytes()).await?;
+            self.put_copy(&format!("{to}{name}"), &sha1, &paste).await?;
+            self.store.delete(&obj.key).await?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_token(&self, user: &User, id: &str) -> Result<Option<ApiToken>> {
+        let path = super::to_token_path_r2(user, id);
+
+        match self.store.get(&path).await?.and_then(|obj| obj.body) {
+            Some(body) => Ok(Some(serde_json::from_slice(&body)?)),
+            None => Ok(None),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn put_token(&self, user: &User, token: &ApiToken) -> Result<()> {
+        let path = super::to_token_path_r2(user, &token.id);
+        let data = serde_json::to_vec(token)?;
+
+        // Also stored as metadata, so tokens can be listed without fetching every object.
+        let mut custom_metdata = HashMap::new();
+        custom_metdata.insert("token".to_owned(), b64_encode(&data));
+
+        let options = PutOptions {
+            content_type: "application/json",
+            custom_metadata: custom_metdata,
+            sha1: None,
+        };
+        self.store.put(&path, &data, options).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn list_tokens(&self, user: &User) -> Result<Vec<ApiToken>> {
+        let prefix = super::to_token_prefix_r2(user);
+
+        let mut tokens = self
+            .store
+            .list_all(&prefix)
+            .await?
+            .into_iter()
+            .map(|mut obj| {
+                let token = obj.custom_metadata.remove("token").ok_or_else(|| {
+                    crate::Error::StorageError(format!("missing metadata on token {}", obj.key))
+                })?;
+                Ok(serde_json::from_slice(&b64_decode(token)?)?)
+            })
+            .collect::<Result<Vec<ApiToken>>>()?;
+        tokens.sort_unstable_by_key(|t| t.created);
+
+        Ok(tokens)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_token(&self, user: &User, id: &str) -> Result<()> {
+        let path = super::to_token_path_r2(user, id);
+        self.store.delete(&path).await
+    }
+}
+
+impl<S: ObjectStore> PasteStore for ObjectStorage<S> {
+    #[tracing::instrument(skip(self))]
+    async fn get(&self, id: &PasteId) -> Result<Option<StoredPaste>> {
+        let path = super::to_path_r2(id)?;
+        self.get_path(&path).await
+    }
+
+    #[tracing::instrument(skip(self, sha1, data))]
+    async fn put(
+        &self,
+        id: &PasteId,
+        sha1: &Sha1,
+        data: &[u8],
+        metadata: Option<&PasteMetadata>,
+    ) -> Result<()> {
+        let path = super::to_path_r2(id)?;
+
+        let metadata = metadata
+            .map(serde_json::to_string)
+            .transpose()?
+            .map(b64_encode);
+
+        let mut custom_metdata = HashMap::new();
+        if let Some(metadata) = metadata {
+            custom_metdata.insert("metadata".to_owned(), metadata);
+        }
+
+        let options = PutOptions {
+            content_type: "text/plain",
+            custom_metadata: custom_metdata,
+            sha1: Some(sha1),
+        };
+        self.store.put(&path, data, options).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete(&self, id: &PasteId) -> Result<()> {
+        let path = super::to_path_r2(id)?;
+        self.store.delete(&path).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list(&self, user: &User) -> Result<Vec<ListPaste>> {
+        let prefix = super::to_prefix_r2(user);
+
+        // Pastes are filtered and sorted after listing, all of them are needed.
+        self.store
+            .list_all(&prefix)
+            .await?
+            .into_iter()
+            .map(|obj| {
+                let (mtime, metadata) = to_metadata(&obj)?;
+                let metadata = metadata.ok_or_else(|| {
+                    crate::Error::StorageError(format!(
+                        "missing metadata on user paste {user}:{}",
+                        obj.key
+                    ))
+                })?;
+                Ok(ListPaste {
+                    name: super::strip_prefix(&obj.key, &prefix)?,
+                    metadata,
+                    last_modified: mtime,
+                })
+            })
+            .collect::<Result<_>>()
+    }
+}
+
+fn to_metadata(obj: &Object) -> Result<(u64, Option<PasteMetadata>)> {
+    let mtime = obj
+        .custom_metadata
+        .get("mtime")
+        .and_then(|mtime| mtime.parse::<f32>().ok())
+        .map(|mtime| (mtime * 1000.0) as u64)
+        .unwrap_or(obj.uploaded);
+
+    let metadata = obj
+        .custom_metadata
+        .get("metadata")
+        .map(b64_decode)
+        .transpose()?
+        .map(|m| serde_json::from_slice(&m))
+        .transpose()?;
+
+    Ok((mtime, metadata))
+}
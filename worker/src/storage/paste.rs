@@ -0,0 +1,29 @@
+use shared::{
+    model::{ListPaste, PasteMetadata},
+    PasteId, User,
+};
+
+use super::StoredPaste;
+use crate::{crypto::Sha1, Result};
+
+/// Storage for pastes and their metadata.
+///
+/// Implemented by [`super::objects::ObjectStorage`] on top of any [`super::ObjectStore`],
+/// revisions, trash and tokens are managed by [`super::Storage`] directly.
+pub trait PasteStore {
+    async fn get(&self, id: &PasteId) -> Result<Option<StoredPaste>>;
+
+    /// Stores a paste, `sha1` is the checksum of `data`.
+    async fn put(
+        &self,
+        id: &PasteId,
+        sha1: &Sha1,
+        data: &[u8],
+        metadata: Option<&PasteMetadata>,
+    ) -> Result<()>;
+
+    async fn delete(&self, id: &PasteId) -> Result<()>;
+
+    /// Lists all pastes of a user together with their metadata.
+    async fn list(&self, user: &User) -> Result<Vec<ListPaste>>;
+}
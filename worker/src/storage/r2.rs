@@ -1,36 +1,17 @@
-use std::collections::HashMap;
+use worker::{Bucket, HttpMetadata, Include};
 
-use serde::{Deserialize, Serialize};
-use shared::{
-    model::{ListPaste, PasteMetadata},
-    PasteId, User,
-};
-use worker::{Bucket, HttpMetadata, Include, Object};
-
-use super::StoredPaste;
+use super::object::{Listing, Object, ObjectStore, PutOptions};
 use crate::{
-    crypto::Sha1,
     request_context::{Env, FromEnv},
-    retry,
-    utils::{b64_decode, b64_encode},
-    Result,
+    retry, Result,
 };
 
-#[derive(Default, Serialize, Deserialize)]
-struct KvMetadata {
-    #[serde(default)]
-    last_modified: u64,
-    #[serde(default)]
-    entity_id: Option<String>,
-    #[serde(flatten)]
-    metadata: Option<PasteMetadata>,
-}
-
-pub struct R2Storage {
+/// [`ObjectStore`] backed by a Cloudflare R2 bucket.
+pub struct R2Bucket {
     bucket: Bucket,
 }
 
-impl FromEnv for R2Storage {
+impl FromEnv for R2Bucket {
     fn from_env(env: &Env) -> Option<Self> {
         Some(Self {
             bucket: env.bucket(crate::consts::R2_STORAGE_BUCKET)?,
@@ -38,127 +19,79 @@ impl FromEnv for R2Storage {
     }
 }
 
-impl R2Storage {
-    #[tracing::instrument(skip(self))]
-    pub async fn get(&self, id: &PasteId) -> Result<Option<StoredPaste>> {
-        let path = super::to_path_r2(id)?;
-
-        let obj = retry::retry_all(3, |_| self.bucket.get(&path).execute()).await?;
+impl ObjectStore for R2Bucket {
+    async fn get(&self, key: &str) -> Result<Option<Object>> {
+        let obj = retry::retry_all(3, |_| self.bucket.get(key).execute()).await?;
 
         let Some(obj) = obj else {
             return Ok(None);
         };
 
-        let content = match obj.body() {
-            Some(body) => body.text().await?,
+        let body = match obj.body() {
+            Some(body) => body.bytes().await?,
             None => return Ok(None),
         };
 
-        let (mtime, metadata) = to_metadata(&obj)?;
-
-        Ok(Some(StoredPaste {
-            content,
-            metadata,
-            entity_id: obj.etag(),
-            last_modified: mtime,
-        }))
+        to_object(&obj, Some(body)).map(Some)
     }
 
-    #[tracing::instrument(skip(self))]
-    pub async fn delete(&self, id: &PasteId) -> Result<()> {
-        let path = super::to_path_r2(id)?;
-
-        retry::retry_all(3, |_| self.bucket.delete(&path)).await?;
-
-        Ok(())
-    }
-
-    #[tracing::instrument(skip(self, sha1, data))]
-    pub async fn put(
-        &self,
-        id: &PasteId,
-        sha1: &Sha1,
-        data: &[u8],
-        metadata: Option<&PasteMetadata>,
-    ) -> Result<()> {
-        let path = super::to_path_r2(id)?;
-
-        let metadata = metadata
-            .map(serde_json::to_string)
-            .transpose()?
-            .map(b64_encode);
-
-        let mut custom_metdata = HashMap::new();
-        if let Some(metadata) = metadata {
-            custom_metdata.insert("metadata".to_owned(), metadata);
-        }
-
+    async fn put(&self, key: &str, body: &[u8], options: PutOptions<'_>) -> Result<()> {
         retry::retry_all(3, |_| {
-            self.bucket
-                .put(&path, worker::Data::Bytes(data))
+            let mut put = self
+                .bucket
+                .put(key, worker::Data::Bytes(body))
                 .http_metadata(HttpMetadata {
-                    content_type: Some("text/plain".to_owned()),
+                    content_type: Some(options.content_type.to_owned()),
                     ..Default::default()
                 })
-                .custom_metdata(custom_metdata.clone())
-                .sha1(sha1.0)
-                .execute()
+                .custom_metdata(options.custom_metadata.clone());
+            if let Some(sha1) = options.sha1 {
+                put = put.sha1(sha1.0);
+            }
+            put.execute()
         })
         .await?;
 
         Ok(())
     }
 
-    #[tracing::instrument(skip(self))]
-    pub async fn list(&self, user: &User) -> Result<Vec<ListPaste>> {
-        let prefix = super::to_prefix_r2(user);
-
+    async fn list(&self, prefix: &str, limit: u32, cursor: Option<&str>) -> Result<Listing> {
         let objects = retry::retry_all(3, |_| {
-            self.bucket
+            let mut list = self
+                .bucket
                 .list()
-                .prefix(&prefix)
+                .prefix(prefix)
                 .include(vec![Include::CustomMetadata])
-                .limit(100)
-                .execute()
+                .limit(limit);
+            if let Some(cursor) = cursor {
+                list = list.cursor(cursor.to_owned());
+            }
+            list.execute()
         })
         .await?;
 
-        objects
-            .objects()
-            .into_iter()
-            .map(|obj| {
-                let (mtime, metadata) = to_metadata(&obj)?;
-                let metadata = metadata.ok_or_else(|| {
-                    crate::Error::StorageError(format!(
-                        "missing metadata on user paste {user}:{}",
-                        obj.key()
-                    ))
-                })?;
-                Ok(ListPaste {
-                    name: super::strip_prefix(&obj.key(), &prefix)?,
-                    metadata,
-                    last_modified: mtime,
-                })
-            })
-            .collect::<Result<_>>()
+        Ok(Listing {
+            objects: objects
+                .objects()
+                .iter()
+                .map(|obj| to_object(obj, None))
+                .collect::<Result<_>>()?,
+            cursor: objects.truncated().then(|| objects.cursor()).flatten(),
+        })
     }
-}
 
-fn to_metadata(obj: &Object) -> Result<(u64, Option<PasteMetadata>)> {
-    let custom_metdata = obj.custom_metadata()?;
-
-    let mtime = custom_metdata
-        .get("mtime")
-        .and_then(|mtime| mtime.parse::<f32>().ok())
-        .map(|mtime| (mtime * 1000.0) as u64)
-        .unwrap_or_else(|| obj.uploaded().as_millis());
-
-    let metadata = custom_metdata
-        .get("metadata")
-        .map(b64_decode)
-        .transpose()?
-        .map(|m| serde_json::from_slice(&m))
-        .transpose()?;
+    async fn delete(&self, key: &str) -> Result<()> {
+        retry::retry_all(3, |_| self.bucket.delete(key)).await?;
+        Ok(())
+    }
+}
 
-    Ok((mtime, metadata))
+fn to_object(obj: &worker::Object, body: Option<Vec<u8>>) -> Result<Object> {
+    Ok(Object {
+        key: obj.key(),
+        etag: obj.etag(),
+        uploaded: obj.uploaded().as_millis(),
+        custom_metadata: obj.custom_metadata()?,
+        body,
+    })
 }
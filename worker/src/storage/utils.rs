@@ -1,4 +1,4 @@
-use shared::{PasteId, User};
+use shared::{PasteId, User, UserPasteId};
 
 use crate::Result;
 
@@ -13,6 +13,35 @@ pub(crate) fn to_prefix_r2(user: &User) -> String {
     format!("users/{}/pastes/", user.normalized())
 }
 
+pub(crate) fn to_revision_prefix_r2(id: &UserPasteId) -> String {
+    format!("users/{}/revisions/{}/", id.user.normalized(), id.id)
+}
+
+pub(crate) fn to_revision_path_r2(id: &UserPasteId, revision: u32) -> String {
+    format!("{}{revision}", to_revision_prefix_r2(id))
+}
+
+/// Prefix of all trashed user pastes, see [`to_trash_path_r2`].
+pub(crate) const TRASH_PREFIX_R2: &str = "trash/users/";
+
+/// Trashed pastes and their revisions share a single top level prefix,
+/// the retention window can then be enforced by a bucket lifecycle rule as well.
+pub(crate) fn to_trash_path_r2(id: &UserPasteId) -> String {
+    format!("{TRASH_PREFIX_R2}{}/{}", id.user.normalized(), id.id)
+}
+
+pub(crate) fn to_trash_revision_prefix_r2(id: &UserPasteId) -> String {
+    format!("trash/revisions/{}/{}/", id.user.normalized(), id.id)
+}
+
+pub(crate) fn to_token_prefix_r2(user: &User) -> String {
+    format!("users/{}/tokens/", user.normalized())
+}
+
+pub(crate) fn to_token_path_r2(user: &User, id: &str) -> String {
+    format!("{}{id}", to_token_prefix_r2(user))
+}
+
 pub(crate) fn strip_prefix(file: &str, prefix: &str) -> Result<String> {
     file.strip_prefix(prefix).map(Into::into).ok_or_else(|| {
         crate::Error::Error(format!("expected file '{file}' to start with '{prefix}'"))
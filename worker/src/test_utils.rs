@@ -0,0 +1,14 @@
+//! Fixtures shared between tests.
+use shared::{model::PasteMetadata, AscendancyOrClass, Class};
+
+/// Metadata of a Witch paste with the given title.
+pub fn metadata(title: &str, private: bool) -> PasteMetadata {
+    PasteMetadata {
+        title: title.to_owned(),
+        ascendancy_or_class: AscendancyOrClass::Class(Class::Witch),
+        version: None,
+        main_skill_name: None,
+        rank: None,
+        private,
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use shared::{
+    model::{ApiToken, ApiTokenScope},
+    User,
+};
+
+use crate::{
+    dangerous::{Dangerous, DangerousError},
+    request_context::{Env, FromEnv},
+    storage::Storage,
+};
+
+/// Signed content of a personal API token.
+///
+/// The signature only proves the token was issued by us, a token is only valid
+/// as long as the matching [`ApiToken`] still exists in storage.
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    user: User,
+    id: String,
+}
+
+/// A user authenticated through a personal API token.
+#[derive(Debug)]
+pub struct TokenSession {
+    pub user: app::User,
+    pub scopes: Vec<ApiTokenScope>,
+}
+
+pub async fn sign(
+    dangerous: &Dangerous,
+    user: &User,
+    token: &ApiToken,
+) -> Result<String, DangerousError> {
+    let claims = Claims {
+        user: user.clone(),
+        id: token.id.clone(),
+    };
+    dangerous.sign(&claims).await
+}
+
+/// Verifies a bearer token and looks up the scopes it was granted.
+pub async fn verify(env: &Env, token: &str) -> Option<TokenSession> {
+    let dangerous = Dangerous::from_env(env).expect("failed to create Dangerous");
+    let claims = match dangerous.verify::<Claims>(token).await {
+        Ok(claims) => claims,
+        Err(err) => {
+            tracing::warn!("failed to decode api token: {err:?}");
+            return None;
+        }
+    };
+
+    let storage = Storage::from_env(env).expect("failed to create Storage");
+    let token = match storage.get_token(&claims.user, &claims.id).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            tracing::info!(user = %claims.user, id = %claims.id, "api token was revoked");
+            return None;
+        }
+        Err(err) => {
+            tracing::warn!("failed to load api token: {err:?}");
+            return None;
+        }
+    };
+
+    Some(TokenSession {
+        user: app::User { name: claims.user },
+        scopes: token.scopes,
+    })
+}
@@ -189,6 +189,13 @@ pub trait RequestExt: Sized {
     fn session(&self) -> Option<String> {
         self.cookie("session")
     }
+
+    fn bearer_token(&self) -> Option<String> {
+        let value = self.header("Authorization")?;
+        value
+            .strip_prefix("Bearer ")
+            .map(|token| token.trim().to_owned())
+    }
 }
 
 impl RequestExt for Request {